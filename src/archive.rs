@@ -0,0 +1,168 @@
+//! Filtering entries of tar, zip and similar archives.
+//!
+//! Entry names use `/` as separator whatever the platform, and can be crafted to
+//! escape the extraction directory, e.g. `../../.bashrc` or `/etc/passwd`.
+//! [`ArchiveEntryFilter`] ignores such names before asking the inner filter, so
+//! extractors can skip members by name without reading them.
+//!
+//! The filter works on names, so it can be used with any archive crate.
+//!
+//! # Examples
+//! ```
+//! use pathfilter::{archive::ArchiveEntryFilter, ExtensionFilter};
+//!
+//! let filter = ArchiveEntryFilter::new(ExtensionFilter::new(".exe"));
+//! let names = ["docs/readme.txt", "setup.exe", "../evil.sh"];
+//! let extracted: Vec<_> = names
+//!     .into_iter()
+//!     .filter(|name| !filter.ignore_name(name))
+//!     .collect();
+//! assert_eq!(extracted, ["docs/readme.txt"]);
+//! ```
+
+use crate::IgnorePath;
+use std::path::{Component, Path};
+
+/// Returns `true` if extracting an entry with this name stays inside the target directory.
+///
+/// Names that are absolute, start with a drive or contain a `..` component or a
+/// NUL byte are unsafe. Both `/` and `\` are treated as separators.
+///
+/// # Examples
+/// ```
+/// use pathfilter::archive::is_safe_entry_name;
+///
+/// assert!(is_safe_entry_name("src/./lib.rs"));
+/// assert!(!is_safe_entry_name("../lib.rs"));
+/// assert!(!is_safe_entry_name("/etc/passwd"));
+/// assert!(!is_safe_entry_name("C:\\Windows\\win.ini"));
+/// ```
+pub fn is_safe_entry_name(name: &str) -> bool {
+    let has_drive = name
+        .split_once(':')
+        .is_some_and(|(drive, _)| !drive.contains(['/', '\\']));
+
+    !name.is_empty()
+        && !name.starts_with(['/', '\\'])
+        && !has_drive
+        && !name.contains('\0')
+        && !name.split(['/', '\\']).any(|component| component == "..")
+}
+
+/// A filter for archive entry names that also ignores unsafe names.
+///
+/// See [`is_safe_entry_name`] for the names that are ignored regardless of the
+/// inner filter. Names that are not valid UTF-8 are checked the same way before
+/// they are passed to the inner filter.
+#[derive(Clone, Debug, Default)]
+pub struct ArchiveEntryFilter<F> {
+    filter: F,
+}
+
+impl<F: IgnorePath> IgnorePath for ArchiveEntryFilter<F> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        match path.to_str() {
+            Some(name) => self.ignore_name(name),
+            None => !is_safe_entry_path(path) || self.filter.ignore(path),
+        }
+    }
+}
+
+/// Like [`is_safe_entry_name`], for names that are not valid UTF-8.
+fn is_safe_entry_path(path: &Path) -> bool {
+    let bytes = path.as_os_str().as_encoded_bytes();
+    let is_separator = |b: &u8| matches!(b, b'/' | b'\\');
+    let has_drive = bytes
+        .iter()
+        .position(|&b| b == b':')
+        .is_some_and(|colon| !bytes[..colon].iter().any(is_separator));
+
+    !bytes.is_empty()
+        && !bytes.first().is_some_and(is_separator)
+        && !has_drive
+        && !bytes.contains(&0)
+        && !bytes
+            .split(is_separator)
+            .any(|component| component == b"..")
+        && path.components().all(|component| {
+            !matches!(
+                component,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_)
+            )
+        })
+}
+
+impl<F: IgnorePath> ArchiveEntryFilter<F> {
+    /// Creates a new filter for entry names.
+    pub fn new(filter: F) -> Self {
+        ArchiveEntryFilter { filter }
+    }
+
+    /// Returns `true` if the entry should not be extracted.
+    ///
+    /// Directory entries may end with `/`.
+    pub fn ignore_name(&self, name: &str) -> bool {
+        !is_safe_entry_name(name) || self.filter.ignore(name.trim_end_matches('/'))
+    }
+
+    /// Returns the inner filter.
+    pub fn inner(&self) -> &F {
+        &self.filter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        archive::{is_safe_entry_name, ArchiveEntryFilter},
+        ExtensionFilter, IgnorePath,
+    };
+
+    #[test]
+    fn safe_entry_names() {
+        for name in ["a", "a/b/", "./a", "a..b/c", "a/b:c", ".../a"] {
+            assert!(is_safe_entry_name(name), "{name}");
+        }
+        for name in [
+            "", "/a", "\\a", "..", "a/../b", "a\\..\\b", "a/..", "C:a", "c:/a", "a\0b",
+        ] {
+            assert!(!is_safe_entry_name(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn archive_entry_filter() {
+        let filter = ArchiveEntryFilter::new(ExtensionFilter::new(".log"));
+        assert!(filter.ignore_name("logs/app.log"));
+        assert!(filter.ignore_name("/README.md"));
+        assert!(!filter.ignore_name("README.md"));
+        assert!(!filter.ignore_name("logs/"));
+        assert!(filter.ignore("../README.md"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_names() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+
+        fn name(bytes: &[u8]) -> &Path {
+            Path::new(OsStr::from_bytes(bytes))
+        }
+
+        let filter = ArchiveEntryFilter::new(ExtensionFilter::new(".log"));
+        assert!(!filter.ignore(name(b"docs/\xff.txt")));
+        assert!(filter.ignore(name(b"logs/\xff.log")));
+        for bytes in [
+            &b"../\xff.txt"[..],
+            b"a/../\xff",
+            b"a\\..\\\xff",
+            b"/etc/\xff",
+            b"\\\xff",
+            b"C:\xff",
+            b"\xff\0.txt",
+        ] {
+            assert!(filter.ignore(name(bytes)), "{bytes:?}");
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{
+    content::{io_error_policy_methods, looks_binary, read_prefix},
+    IgnorePath, IoErrorPolicy,
+};
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+/// A filter that matches files whose contents look like binary data.
+///
+/// Like grep tools do, only the start of a file is sniffed, 8 KiB by default. It
+/// looks binary if it contains a NUL byte, or if more than a tenth of it are
+/// control characters that do not occur in text. Empty files and directories are
+/// not binary.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{BinaryFilter, IgnorePath};
+///
+/// let filter = BinaryFilter::new();
+/// assert!(filter.ignore("target/debug/app"));
+/// assert!(!filter.ignore("src/main.rs"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BinaryFilter {
+    sniff_len: usize,
+    on_error: IoErrorPolicy,
+}
+
+impl Default for BinaryFilter {
+    fn default() -> Self {
+        BinaryFilter::new()
+    }
+}
+
+impl IgnorePath for BinaryFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+impl BinaryFilter {
+    /// Creates a new filter that sniffs 8 KiB and keeps files it cannot read.
+    pub const fn new() -> Self {
+        BinaryFilter {
+            sniff_len: 8 * 1024,
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    /// Sets how many bytes from the start of a file are sniffed.
+    pub const fn with_sniff_len(mut self, sniff_len: usize) -> Self {
+        self.sniff_len = sniff_len;
+        self
+    }
+
+    /// Returns how many bytes from the start of a file are sniffed.
+    pub const fn sniff_len(&self) -> usize {
+        self.sniff_len
+    }
+
+    io_error_policy_methods!(const "files that cannot be read");
+
+    /// Returns whether the path is a file that looks binary.
+    ///
+    /// # Errors
+    /// If the file cannot be read, an error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let path = path.as_ref();
+        if !fs::metadata(path)?.is_file() {
+            return Ok(false);
+        }
+
+        Ok(is_binary_prefix(&read_prefix(path, self.sniff_len)?))
+    }
+
+    /// Returns whether the contents of `reader` look binary, sniffing up to
+    /// [`sniff_len`](Self::sniff_len) bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::BinaryFilter;
+    ///
+    /// let filter = BinaryFilter::new();
+    /// assert!(filter.is_binary(&b"\x7fELF\x02\x01\x01\0"[..]).unwrap());
+    /// assert!(!filter.is_binary("fn main() {}\n".as_bytes()).unwrap());
+    /// ```
+    /// # Errors
+    /// If reading fails, an error is returned.
+    pub fn is_binary<R: Read>(&self, reader: R) -> io::Result<bool> {
+        // The reader may be much shorter than the sniff length, so let the
+        // buffer grow as needed instead of reserving all of it up front.
+        let mut prefix = Vec::new();
+        reader
+            .take(self.sniff_len as u64)
+            .read_to_end(&mut prefix)?;
+        Ok(is_binary_prefix(&prefix))
+    }
+}
+
+fn is_binary_prefix(prefix: &[u8]) -> bool {
+    let is_control =
+        |&byte: &u8| byte < 0x20 && !matches!(byte, b'\t' | b'\n' | b'\r' | 0x0c | 0x1b);
+    looks_binary(prefix)
+        || prefix.iter().filter(|byte| is_control(byte)).count() * 10 > prefix.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{content::tests::TempDir, BinaryFilter, IgnorePath, IoErrorPolicy};
+
+    #[test]
+    fn binary_filter() {
+        let dir = TempDir::new();
+        let text = dir.write("text.txt", "line\r\n\tindented\x1b[0m\n");
+        let nul = dir.write("nul.bin", b"abc\0def");
+        let control = dir.write("control.bin", [0x01, 0x02, b'a', b'b', b'c']);
+        let empty = dir.write("empty", "");
+        let mut late = vec![b'a'; 100];
+        late.push(0);
+        let late = dir.write("late.bin", late);
+
+        let filter = BinaryFilter::new();
+        assert!(!filter.ignore(&text));
+        assert!(filter.ignore(&nul));
+        assert!(filter.ignore(&control));
+        assert!(!filter.ignore(&empty));
+        assert!(filter.ignore(&late));
+        assert!(!filter.ignore(dir.path()));
+        assert!(!filter.with_sniff_len(100).ignore(&late));
+        assert!(filter.with_sniff_len(usize::MAX).ignore(&late));
+        assert!(filter
+            .with_sniff_len(usize::MAX)
+            .is_binary(&b"abc\0"[..])
+            .unwrap());
+
+        let missing = dir.path().join("missing");
+        assert!(!filter.ignore(&missing));
+        assert!(filter
+            .with_io_error_policy(IoErrorPolicy::Ignore)
+            .ignore(&missing));
+    }
+}
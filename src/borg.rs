@@ -0,0 +1,244 @@
+//! Loading BorgBackup exclude and pattern files.
+//!
+//! Borg matches patterns against paths as it stores them in an archive, without a
+//! leading `/`, so paths are matched relative to the root here as well. A leading
+//! `/` of a pattern is removed, except for regular expressions. The style of a
+//! pattern is selected with a prefix:
+//!
+//! | Prefix | Style                                               | Filter          |
+//! |--------|-----------------------------------------------------|-----------------|
+//! | `fm:`  | `fnmatch`, where `*` and `?` also match `/`         | `RegexFilter`   |
+//! | `sh:`  | shell globs, where only `**` matches across `/`     | `RegexFilter`   |
+//! | `re:`  | a regular expression, searched anywhere in the path | `RegexFilter`   |
+//! | `pp:`  | a path prefix                                       | [`PrefixFilter`](crate::PrefixFilter) |
+//! | `pf:`  | the full path                                       | `RegexFilter`   |
+//!
+//! `fm:` and `sh:` patterns match a path, or a directory the path is in, and a
+//! pattern ending with `/` only matches paths inside the directory.
+//!
+//! # Examples
+//! ```
+//! use pathfilter::{borg, IgnorePath};
+//! use std::path::Path;
+//!
+//! let set = borg::parse_excludes("# caches\nhome/*/.cache\npp:/var/tmp\nre:\\.pyc$\n").unwrap();
+//! assert!(set.ignore(Path::new("home/me/.cache/pip/x")));
+//! assert!(set.ignore(Path::new("var/tmp/session")));
+//! assert!(set.ignore(Path::new("srv/app/main.pyc")));
+//! assert!(!set.ignore(Path::new("home/me/.config")));
+//! ```
+
+use crate::{
+    glob::{translate, Dialect},
+    patterns::{PatternError, PatternErrorKind},
+    FilterRule, FilterSet, GlobError, PathFilter, PrefixFilter, RegexFilter,
+};
+
+/// Parses an exclude file as passed to `--exclude-from`.
+///
+/// Every line is an exclude pattern, `fm:` unless a prefix selects another style.
+/// Surrounding whitespace is removed, and blank lines and lines starting with `#`
+/// are skipped.
+///
+/// # Errors
+/// If a pattern is invalid or has an unknown style, an error with its location is
+/// returned.
+pub fn parse_excludes(text: &str) -> Result<FilterSet, PatternError> {
+    let mut set = FilterSet::new();
+    for (index, line) in text.lines().enumerate() {
+        let pattern = line.trim();
+        if !pattern.is_empty() && !pattern.starts_with('#') {
+            let column = line.len() - line.trim_start().len();
+            set.add_ignore(parse_pattern(pattern, "fm", index + 1, column)?);
+        }
+    }
+    Ok(set)
+}
+
+/// Parses a pattern file as passed to `--patterns-from`.
+///
+/// Lines start with `+` to include, `-` to exclude or `!` to exclude without
+/// recursing, followed by a pattern that is `sh:` unless a prefix selects another
+/// style. The first matching line decides, so the rules of the set are in the
+/// reverse order of the lines. `P style` lines change the default style of the
+/// following patterns and `R path` lines, which give a backup root, are skipped.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{borg, IgnorePath};
+/// use std::path::Path;
+///
+/// let set = borg::parse_patterns("R /home\n+ home/me/.cache/keep\n- home/*/.cache\n").unwrap();
+/// assert!(set.ignore(Path::new("home/me/.cache/pip")));
+/// assert!(!set.ignore(Path::new("home/me/.cache/keep/a")));
+/// ```
+/// # Errors
+/// If a line has no known prefix, or a pattern is invalid or has an unknown style,
+/// an error with its location is returned.
+pub fn parse_patterns(text: &str) -> Result<FilterSet, PatternError> {
+    let mut style = "sh";
+    let mut rules = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        let error = |offset: usize, kind| PatternError {
+            file: None,
+            line: index + 1,
+            column: line[..indent + offset].chars().count() + 1,
+            text: trimmed.to_string(),
+            kind,
+        };
+
+        let (kind, value) = trimmed.split_at(trimmed.chars().next().map_or(0, char::len_utf8));
+        let offset = kind.len() + value.len() - value.trim_start().len();
+        let value = value.trim_start();
+        if value.is_empty() {
+            return Err(error(offset, PatternErrorKind::MissingValue));
+        }
+        match kind {
+            "R" => {}
+            "P" if STYLES.contains(&value) => style = value,
+            "P" => {
+                let kind = PatternErrorKind::UnknownKind(value.to_string());
+                return Err(error(offset, kind));
+            }
+            "+" => rules.push(FilterRule::Allow(parse_pattern(
+                value,
+                style,
+                index + 1,
+                indent + offset,
+            )?)),
+            "-" | "!" => rules.push(FilterRule::Ignore(parse_pattern(
+                value,
+                style,
+                index + 1,
+                indent + offset,
+            )?)),
+            _ => return Err(error(0, PatternErrorKind::MissingKind)),
+        }
+    }
+    Ok(rules.into_iter().rev().collect())
+}
+
+const STYLES: [&str; 5] = ["fm", "sh", "re", "pp", "pf"];
+
+/// Parses a pattern with an optional style prefix that starts at byte `column` of its line.
+fn parse_pattern(
+    pattern: &str,
+    default_style: &str,
+    number: usize,
+    column: usize,
+) -> Result<PathFilter, PatternError> {
+    let error = |offset: usize, kind| PatternError {
+        file: None,
+        line: number,
+        column: column + pattern[..offset].chars().count() + 1,
+        text: pattern.to_string(),
+        kind,
+    };
+
+    // Any two letters followed by `:` select a style, known or not.
+    let (style, value, offset) = match pattern.split_once(':') {
+        Some((style, value))
+            if style.len() == 2 && style.bytes().all(|b| b.is_ascii_lowercase()) =>
+        {
+            (style, value, 3)
+        }
+        _ => (default_style, pattern, 0),
+    };
+    if value.is_empty() {
+        return Err(error(offset, PatternErrorKind::MissingValue));
+    }
+
+    let regex = |pattern: &str| {
+        RegexFilter::new_str(pattern)
+            .map(PathFilter::from)
+            .map_err(|err| error(offset, PatternErrorKind::Regex(err)))
+    };
+    let path = value.strip_prefix('/').unwrap_or(value);
+    match style {
+        "re" => regex(value),
+        "pp" => Ok(PrefixFilter::new([path]).into()),
+        "pf" => regex(&format!("^{}$", regex::escape(path.trim_end_matches('/')))),
+        "fm" | "sh" => {
+            let dialect = Dialect {
+                slash_wildcards: style == "fm",
+                ..Dialect::default()
+            };
+            let mut body = translate(path, dialect).map_err(|reason| {
+                let glob = GlobError {
+                    glob: value.to_string(),
+                    reason,
+                };
+                error(offset, PatternErrorKind::Glob(glob))
+            })?;
+            if path.ends_with('/') {
+                body.push_str(".*");
+            }
+            regex(&format!("^{body}(?:/.*)?$"))
+        }
+        style => Err(error(0, PatternErrorKind::UnknownKind(style.to_string()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        borg::{parse_excludes, parse_patterns},
+        patterns::PatternErrorKind,
+        IgnorePath,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn styles() {
+        let text = "*.tmp\n  sh:home/*/junk  \nsh:var/log/\npf:/etc/shadow\npp:srv/cache\nre:^opt/.*\\.bak$\n";
+        let set = parse_excludes(text).unwrap();
+        assert_eq!(set.len(), 6);
+        // `fm:` wildcards match across directories.
+        assert!(set.ignore(Path::new("a/b/c.tmp")));
+        assert!(set.ignore(Path::new("home/me/junk/x")));
+        assert!(!set.ignore(Path::new("home/me/sub/junk")));
+        assert!(set.ignore(Path::new("var/log/syslog")));
+        assert!(!set.ignore(Path::new("var/log")));
+        assert!(set.ignore(Path::new("etc/shadow")));
+        assert!(!set.ignore(Path::new("etc/shadow.bak")));
+        assert!(set.ignore(Path::new("srv/cache/a")));
+        assert!(!set.ignore(Path::new("srv/cache2")));
+        assert!(set.ignore(Path::new("opt/app/data.bak")));
+    }
+
+    #[test]
+    fn patterns_file() {
+        let text = "P fm\n+ home/me/*.keep\n- sh:home/me/*\n! home/*/.cache\n";
+        let set = parse_patterns(text).unwrap();
+        assert!(!set.ignore(Path::new("home/me/a/b.keep")));
+        assert!(set.ignore(Path::new("home/me/a/b.txt")));
+        assert!(set.ignore(Path::new("home/you/x/.cache")));
+        assert!(!set.ignore(Path::new("home/you/x")));
+    }
+
+    #[test]
+    fn errors() {
+        let err = parse_excludes("ok\n  xx:foo\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::UnknownKind(ref style) if style == "xx"));
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = parse_excludes("re:(\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::Regex(_)));
+        assert_eq!(err.column, 4);
+        let err = parse_excludes("sh:[a\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::Glob(_)));
+
+        let err = parse_patterns("home/me\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::MissingKind));
+        let err = parse_patterns("P xy\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::UnknownKind(_)));
+        assert_eq!(err.column, 3);
+        let err = parse_patterns("-\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::MissingValue));
+    }
+}
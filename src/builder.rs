@@ -0,0 +1,404 @@
+use crate::{
+    patterns::{compile, parse_set_line, PatternError},
+    FilterRule, FilterSet, MatchOptions, PathFilter,
+};
+
+/// The methods both builders share, written in terms of their `filter` method
+/// and `options` field.
+macro_rules! builder_methods {
+    () => {
+        /// Adds a filter for a single extension.
+        pub fn extension<S: AsRef<str>>(self, extension: S) -> Self {
+            self.filter(PathFilter::new_extension(extension))
+        }
+
+        /// Adds a filter for multiple extensions.
+        pub fn extensions<S, T>(self, extensions: T) -> Self
+        where
+            S: AsRef<str>,
+            T: AsRef<[S]>,
+        {
+            self.filter(PathFilter::new_extensions(extensions))
+        }
+
+        /// Adds a filter for a glob.
+        ///
+        /// # Errors
+        /// If the glob is invalid, an error is returned.
+        #[cfg(feature = "glob")]
+        pub fn glob(self, glob: &str) -> Result<Self, crate::GlobError> {
+            Ok(self.filter(crate::GlobFilter::new(glob)?))
+        }
+
+        /// Adds a filter for a regular expression.
+        ///
+        /// # Examples
+        /// ```
+        /// use pathfilter::{IgnorePath, PathFilter};
+        /// use std::path::Path;
+        ///
+        /// let filters = PathFilter::builder().regex("^tmp/")?.extension(".log").build();
+        /// assert!(filters.ignore(Path::new("tmp/cache")));
+        /// # Ok::<(), regex::Error>(())
+        /// ```
+        /// # Errors
+        /// If the regex is invalid, an error is returned.
+        #[cfg(feature = "regex")]
+        pub fn regex(self, pattern: &str) -> Result<Self, regex::Error> {
+            Ok(self.filter(crate::RegexFilter::new_str(pattern)?))
+        }
+
+        /// Sets the options that are applied to every filter.
+        pub fn options(mut self, options: MatchOptions) -> Self {
+            self.options = options;
+            self
+        }
+
+        /// Sets whether every filter compares paths case-insensitively.
+        pub fn case_insensitive(mut self, yes: bool) -> Self {
+            self.options = self.options.case_insensitive(yes);
+            self
+        }
+
+        /// Sets whether case-insensitive comparisons fold all of Unicode, not just ASCII.
+        pub fn unicode_case(mut self, yes: bool) -> Self {
+            self.options = self.options.unicode_case(yes);
+            self
+        }
+
+        /// Sets whether every filter matches `\` as `/`.
+        pub fn normalize_separators(mut self, yes: bool) -> Self {
+            self.options = self.options.normalize_separators(yes);
+            self
+        }
+    };
+}
+
+/// A builder for a list of filters that share the same [`MatchOptions`].
+///
+/// The options are applied to every filter when the list is built, no matter
+/// in which order the builder methods were called.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, PathFilter};
+/// use std::path::Path;
+///
+/// let filters = PathFilter::builder()
+///     .extension(".rs")
+///     .extensions([".md", ".txt"])
+///     .case_insensitive(true)
+///     .build();
+/// assert!(filters.ignore(Path::new("src/LIB.RS")));
+/// assert!(filters.ignore(Path::new("README.md")));
+/// assert!(!filters.ignore(Path::new("Cargo.toml")));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PathFilterBuilder {
+    filters: Vec<PathFilter>,
+    options: MatchOptions,
+}
+
+impl PathFilterBuilder {
+    /// Creates an empty builder with the default options.
+    pub fn new() -> Self {
+        PathFilterBuilder::default()
+    }
+
+    /// Adds a filter.
+    pub fn filter<F: Into<PathFilter>>(mut self, filter: F) -> Self {
+        self.filters.push(filter.into());
+        self
+    }
+
+    builder_methods!();
+
+    /// Builds the list of filters, applying the options to every filter.
+    ///
+    /// Settings a filter overrides with [`PathFilter::with_overrides`] keep their value.
+    pub fn build(self) -> Vec<PathFilter> {
+        let options = self.options;
+        self.filters
+            .into_iter()
+            .map(|filter| filter.with_options(options))
+            .collect()
+    }
+}
+
+/// A builder for a [`FilterSet`] whose rules share the same [`MatchOptions`].
+///
+/// Filters are added as ignore rules, except for the ones added with
+/// [`allow`](Self::allow). Like for [`PathFilterBuilder`], the options are applied
+/// to every rule when the set is built.
+///
+/// Rules added with [`pattern`](Self::pattern) are only compiled when the set is
+/// built. With the `parallel` feature, many of them are compiled on multiple
+/// threads, which helps with thousands of regex or glob rules.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{FilterSet, IgnorePath, PathFilter};
+/// use std::path::Path;
+///
+/// let set = FilterSet::builder()
+///     .extension(".log")
+///     .allow(PathFilter::new_file_names(["keep.log"]))
+///     .case_insensitive(true)
+///     .build();
+/// assert!(set.ignore(Path::new("logs/DEBUG.LOG")));
+/// assert!(!set.ignore(Path::new("logs/KEEP.LOG")));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FilterSetBuilder {
+    rules: Vec<PendingRule>,
+    options: MatchOptions,
+    ignore_unmatched: bool,
+}
+
+/// A rule of a [`FilterSetBuilder`], which may still have to be compiled.
+#[derive(Clone, Debug)]
+enum PendingRule {
+    Rule(FilterRule),
+    Pattern(String),
+}
+
+impl FilterSetBuilder {
+    /// Creates an empty builder with the default options.
+    pub fn new() -> Self {
+        FilterSetBuilder::default()
+    }
+
+    /// Adds a rule that ignores the paths `filter` matches.
+    pub fn filter<F: Into<PathFilter>>(mut self, filter: F) -> Self {
+        self.rules
+            .push(PendingRule::Rule(FilterRule::Ignore(filter.into())));
+        self
+    }
+
+    /// Adds a rule that keeps the paths `filter` matches, even if an earlier rule ignores them.
+    pub fn allow<F: Into<PathFilter>>(mut self, filter: F) -> Self {
+        self.rules
+            .push(PendingRule::Rule(FilterRule::Allow(filter.into())));
+        self
+    }
+
+    /// Adds a rule written like a line for [`patterns::parse_set`](crate::patterns::parse_set),
+    /// e.g. `glob:*.o` or `!ext:.rs`, which is compiled when the set is built.
+    pub fn pattern<S: Into<String>>(mut self, rule: S) -> Self {
+        self.rules.push(PendingRule::Pattern(rule.into()));
+        self
+    }
+
+    /// Sets whether the set ignores the paths no rule matches, like [`FilterSet::allowlist`].
+    pub fn ignore_unmatched(mut self, yes: bool) -> Self {
+        self.ignore_unmatched = yes;
+        self
+    }
+
+    builder_methods!();
+
+    /// Builds the set, applying the options to the filters of all rules.
+    ///
+    /// Settings a filter overrides with [`PathFilter::with_overrides`] keep their value.
+    ///
+    /// # Panics
+    /// If a rule added with [`pattern`](Self::pattern) is invalid. Use
+    /// [`try_build`](Self::try_build) to handle invalid rules.
+    pub fn build(self) -> FilterSet {
+        self.try_build().unwrap_or_else(|errors| {
+            panic!("invalid rule {}", errors[0]);
+        })
+    }
+
+    /// Compiles the rules added with [`pattern`](Self::pattern) and builds the set.
+    ///
+    /// The rules keep the order they were added in, no matter how they were compiled.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{FilterSet, IgnorePath};
+    /// use std::path::Path;
+    ///
+    /// let set = FilterSet::builder()
+    ///     .pattern("ext:.log")
+    ///     .pattern("!ext:.rs")
+    ///     .try_build()
+    ///     .unwrap();
+    /// assert!(set.ignore(Path::new("debug.log")));
+    ///
+    /// let errors = FilterSet::builder()
+    ///     .pattern("ext:")
+    ///     .extension(".o")
+    ///     .pattern("path:*.txt")
+    ///     .try_build()
+    ///     .unwrap_err();
+    /// let rules: Vec<_> = errors.iter().map(|err| err.line).collect();
+    /// assert_eq!(rules, [1, 3]);
+    /// ```
+    /// # Errors
+    /// If any of those rules is invalid, the errors of all invalid rules are
+    /// returned. Their `line` is the position of the rule in the builder,
+    /// counting from 1.
+    pub fn try_build(self) -> Result<FilterSet, Vec<PatternError>> {
+        let patterns: Vec<_> = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter_map(|(index, rule)| match rule {
+                PendingRule::Pattern(pattern) => Some((index + 1, pattern.as_str())),
+                PendingRule::Rule(_) => None,
+            })
+            .collect();
+        let mut compiled = compile(&patterns, parse_set_line).into_iter();
+
+        let mut rules = Vec::with_capacity(self.rules.len());
+        let mut errors = Vec::new();
+        for rule in self.rules {
+            match rule {
+                PendingRule::Rule(rule) => rules.push(rule),
+                PendingRule::Pattern(_) => match compiled.next().expect("compiled every pattern") {
+                    Ok(rule) => rules.push(rule),
+                    Err(err) => errors.push(err),
+                },
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(rules
+            .into_iter()
+            .collect::<FilterSet>()
+            .with_options(self.options)
+            .with_ignore_unmatched(self.ignore_unmatched))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{builder::PathFilterBuilder, IgnorePath, MatchOverrides, PathFilter};
+    use std::path::Path;
+
+    #[test]
+    fn build() {
+        let filters = PathFilterBuilder::new()
+            .case_insensitive(true)
+            .extension(".rs")
+            .filter(
+                PathFilter::new_extension(".md")
+                    .with_overrides(MatchOverrides::new().case_insensitive(false)),
+            )
+            .build();
+        assert_eq!(filters.len(), 2);
+        assert!(filters[0].options().is_case_insensitive());
+        assert!(filters.ignore(Path::new("src/LIB.RS")));
+        assert!(filters.ignore(Path::new("README.md")));
+        assert!(!filters.ignore(Path::new("README.MD")));
+        assert!(PathFilterBuilder::new().build().is_empty());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn build_regex() {
+        let filters = PathFilter::builder()
+            .regex("^tmp/")
+            .unwrap()
+            .normalize_separators(true)
+            .build();
+        assert!(filters.ignore(Path::new("tmp\\cache")));
+        assert!(PathFilter::builder().regex("(").is_err());
+    }
+
+    #[test]
+    fn build_set() {
+        use crate::{FilterRule, FilterSet};
+
+        let set = FilterSet::builder()
+            .extensions([".log", ".tmp"])
+            .allow(PathFilter::new_file_names(["keep.log"]))
+            .filter(PathFilter::new_prefixes(["logs/old"]))
+            .case_insensitive(true)
+            .build();
+        assert_eq!(set.len(), 3);
+        assert!(matches!(set.rules()[1], FilterRule::Allow(_)));
+        assert!(set.rules()[2].filter().options().is_case_insensitive());
+        assert!(set.ignore(Path::new("a/B.TMP")));
+        assert!(!set.ignore(Path::new("logs/Keep.log")));
+        assert!(set.ignore(Path::new("logs/old/keep.log")));
+        assert!(FilterSet::builder().build().is_empty());
+        assert!(!set.ignores_unmatched());
+
+        let set = FilterSet::builder()
+            .allow(PathFilter::new_extension(".rs"))
+            .ignore_unmatched(true)
+            .build();
+        assert!(set.ignores_unmatched());
+        assert!(set.ignore(Path::new("README.md")));
+    }
+
+    #[test]
+    fn pattern_rules() {
+        use crate::{patterns::PatternErrorKind, FilterSet};
+
+        // Enough rules to be compiled on multiple threads with the `parallel` feature.
+        let mut builder = FilterSet::builder().case_insensitive(true);
+        for i in 0..200 {
+            builder = match i % 3 {
+                0 => builder.pattern(format!("ext:.e{i}")),
+                1 => builder.pattern(format!("!ext:.e{}", i - 1)),
+                _ => builder.extension(format!(".f{i}")),
+            };
+        }
+        let set = builder.try_build().unwrap();
+        assert_eq!(set.len(), 200);
+        assert!(set.rules()[0].is_ignore());
+        assert!(!set.rules()[1].is_ignore());
+        assert!(set.rules()[2].filter().options().is_case_insensitive());
+        assert!(!set.ignore(Path::new("a.E99")));
+        assert!(set.ignore(Path::new("a.F101")));
+        assert!(!set.ignore(Path::new("a.F200")));
+
+        let mut builder = FilterSet::builder();
+        for i in 0..100 {
+            builder = builder.pattern(if i % 40 == 7 {
+                "kind:x".to_string()
+            } else {
+                format!("ext:.e{i}")
+            });
+        }
+        let errors = builder.try_build().unwrap_err();
+        let rules: Vec<_> = errors.iter().map(|err| err.line).collect();
+        assert_eq!(rules, [8, 48, 88]);
+        assert!(matches!(errors[0].kind, PatternErrorKind::UnknownKind(_)));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid rule")]
+    fn build_invalid_pattern() {
+        crate::FilterSet::builder().pattern("ext:").build();
+    }
+
+    #[cfg(feature = "glob")]
+    #[test]
+    fn build_glob() {
+        use crate::FilterSet;
+
+        let options = PathFilter::builder()
+            .glob("docs/**")
+            .unwrap()
+            .case_insensitive(true)
+            .unicode_case(true)
+            .build()[0]
+            .options();
+        assert!(options.is_case_insensitive() && options.is_unicode_case());
+
+        let set = FilterSet::builder()
+            .glob("docs/**")
+            .unwrap()
+            .allow(PathFilter::new_glob("docs/api/**").unwrap())
+            .build();
+        assert!(set.ignore(Path::new("docs/guide.md")));
+        assert!(!set.ignore(Path::new("docs/api/index.md")));
+        assert!(PathFilter::builder().glob("[").is_err());
+    }
+}
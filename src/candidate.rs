@@ -0,0 +1,273 @@
+use crate::IgnorePath;
+use std::{cell::RefCell, path::Path};
+#[cfg(feature = "regex")]
+use {
+    crate::{
+        normalize::{split_verbatim_prefix, strip_verbatim_prefix, strips_verbatim},
+        MatchOptions,
+    },
+    std::{borrow::Cow, cell::OnceCell},
+};
+
+/// A path prepared for matching against many filters.
+///
+/// Filters that match on the text of a path, like [`RegexFilter`](crate::RegexFilter),
+/// need to convert it to UTF-8 and normalize it first. A candidate does this at most
+/// once and shares the result between all filters it is passed to with
+/// [`IgnorePath::ignore_candidate`](crate::IgnorePath::ignore_candidate).
+/// Collections of filters create a candidate on their own.
+///
+/// For matching many paths without allocating, create candidates with a
+/// [`MatchContext`].
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, MatchCandidate, PathFilter};
+///
+/// let filters = [PathFilter::new_extension(".rs"), PathFilter::new_extension(".md")];
+/// let candidate = MatchCandidate::new("src/lib.rs");
+/// assert!(filters[0].ignore_candidate(&candidate));
+/// assert!(!filters[1].ignore_candidate(&candidate));
+/// ```
+#[derive(Debug)]
+pub struct MatchCandidate<'a> {
+    path: &'a Path,
+    #[cfg(feature = "regex")]
+    text: OnceCell<Option<Cow<'a, str>>>,
+    #[cfg(feature = "regex")]
+    with_slashes: OnceCell<Cow<'a, str>>,
+    buffer: Option<RefCell<&'a mut String>>,
+}
+
+impl<'a> MatchCandidate<'a> {
+    /// Creates a new candidate for a path.
+    pub fn new<P: AsRef<Path> + ?Sized>(path: &'a P) -> Self {
+        MatchCandidate {
+            path: path.as_ref(),
+            #[cfg(feature = "regex")]
+            text: OnceCell::new(),
+            #[cfg(feature = "regex")]
+            with_slashes: OnceCell::new(),
+            buffer: None,
+        }
+    }
+
+    /// Returns the path of the candidate.
+    pub fn path(&self) -> &'a Path {
+        self.path
+    }
+
+    /// Returns the path as text to match against, or `None` if it is not valid UTF-8.
+    ///
+    /// On Windows or if the options normalize separators, extended-length prefixes
+    /// are stripped; with the latter, `\` is also replaced with `/`.
+    #[cfg(feature = "regex")]
+    pub(crate) fn text(&self, options: &MatchOptions) -> Option<&str> {
+        if !strips_verbatim(*options) {
+            return self.path.to_str();
+        }
+
+        let text = self
+            .text
+            .get_or_init(|| self.path.to_str().map(strip_verbatim_prefix))
+            .as_deref()?;
+
+        if options.normalizes_separators() && text.contains('\\') {
+            Some(
+                self.with_slashes
+                    .get_or_init(|| Cow::Owned(text.replace('\\', "/"))),
+            )
+        } else {
+            Some(text)
+        }
+    }
+}
+
+impl MatchCandidate<'_> {
+    /// Calls `f` with a scratch buffer, e.g. for case folding, reused between
+    /// filters if the candidate was created by a [`MatchContext`].
+    pub(crate) fn with_buffer<R>(&self, f: impl FnOnce(&mut String) -> R) -> R {
+        match self
+            .buffer
+            .as_ref()
+            .and_then(|buffer| buffer.try_borrow_mut().ok())
+        {
+            Some(mut buffer) => f(&mut buffer),
+            None => f(&mut String::new()),
+        }
+    }
+}
+
+/// Clones share the normalized text but not the buffer of a [`MatchContext`].
+impl Clone for MatchCandidate<'_> {
+    fn clone(&self) -> Self {
+        MatchCandidate {
+            path: self.path,
+            #[cfg(feature = "regex")]
+            text: self.text.clone(),
+            #[cfg(feature = "regex")]
+            with_slashes: self.with_slashes.clone(),
+            buffer: None,
+        }
+    }
+}
+
+impl AsRef<Path> for MatchCandidate<'_> {
+    fn as_ref(&self) -> &Path {
+        self.path
+    }
+}
+
+/// Reusable buffers for matching paths without allocating.
+///
+/// Normalizing a path for textual rules, e.g. replacing `\` with `/` or stripping
+/// a verbatim UNC prefix, needs a copy of it, and so does folding the case of a
+/// name for case-insensitive lookups. A [`MatchCandidate`] created by a context
+/// writes these copies into the buffers of the context instead, so once the
+/// buffers have grown to the longest path, regex, glob, wildcard, component, file
+/// name and stem filters match without allocating. Keep one context per thread.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{MatchContext, PathFilter};
+///
+/// let filters = vec![PathFilter::new_extension(".o"), PathFilter::new_extension(".a")];
+/// let mut context = MatchContext::new();
+/// let ignored = ["main.o", "main.c", "libm.a"]
+///     .iter()
+///     .filter(|path| context.ignore(&filters, path))
+///     .count();
+/// assert_eq!(ignored, 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MatchContext {
+    #[cfg(feature = "regex")]
+    text: String,
+    #[cfg(feature = "regex")]
+    with_slashes: String,
+    buffer: String,
+}
+
+impl MatchContext {
+    /// Creates a new context with empty buffers.
+    pub fn new() -> Self {
+        MatchContext::default()
+    }
+
+    /// Creates a candidate for a path whose normalized forms live in this context.
+    pub fn candidate<'a, P: AsRef<Path> + ?Sized>(&'a mut self, path: &'a P) -> MatchCandidate<'a> {
+        let mut candidate = MatchCandidate::new(path);
+
+        #[cfg(feature = "regex")]
+        let MatchContext {
+            text,
+            with_slashes,
+            buffer,
+        } = self;
+        #[cfg(not(feature = "regex"))]
+        let MatchContext { buffer } = self;
+        candidate.buffer = Some(RefCell::new(buffer));
+
+        #[cfg(feature = "regex")]
+        {
+            let stripped = candidate
+                .path
+                .to_str()
+                .map(|path| match split_verbatim_prefix(path) {
+                    ("", rest) => rest,
+                    (prefix, rest) => {
+                        text.clear();
+                        text.push_str(prefix);
+                        text.push_str(rest);
+                        text.as_str()
+                    }
+                });
+
+            if let Some(stripped) = stripped.filter(|text| text.contains('\\')) {
+                with_slashes.clear();
+                with_slashes.extend(stripped.chars().map(|c| if c == '\\' { '/' } else { c }));
+                let _ = candidate
+                    .with_slashes
+                    .set(Cow::Borrowed(with_slashes.as_str()));
+            }
+            let _ = candidate.text.set(stripped.map(Cow::Borrowed));
+        }
+
+        candidate
+    }
+
+    /// Returns `true` if the filter ignores the path, reusing the buffers of this context.
+    pub fn ignore<F: IgnorePath + ?Sized, P: AsRef<Path>>(&mut self, filter: &F, path: P) -> bool {
+        filter.ignore_candidate(&self.candidate(path.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "regex")]
+    #[test]
+    fn text() {
+        use crate::{MatchCandidate, MatchOptions};
+
+        let options = MatchOptions::new();
+        let candidate = MatchCandidate::new(r"\\?\C:\src\lib.rs");
+        #[cfg(windows)]
+        assert_eq!(candidate.text(&options), Some(r"C:\src\lib.rs"));
+        #[cfg(not(windows))]
+        assert_eq!(candidate.text(&options), Some(r"\\?\C:\src\lib.rs"));
+
+        let options = options.normalize_separators(true);
+        assert_eq!(candidate.text(&options), Some("C:/src/lib.rs"));
+        assert_eq!(
+            MatchCandidate::new("src/lib.rs").text(&options),
+            Some("src/lib.rs")
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn context() {
+        use crate::{IgnorePath, MatchContext, MatchOptions, RegexFilter};
+
+        let filter = RegexFilter::new_str("^C:/src/")
+            .unwrap()
+            .with_options(MatchOptions::new().normalize_separators(true));
+        let mut context = MatchContext::new();
+        assert!(context.ignore(&filter, r"\\?\C:\src\lib.rs"));
+        assert!(!context.ignore(&filter, r"C:\tests\lib.rs"));
+
+        let candidate = context.candidate(r"\\?\UNC\server\share");
+        assert_eq!(
+            candidate.text(&MatchOptions::new().normalize_separators(true)),
+            Some("//server/share")
+        );
+        assert!(!filter.ignore_candidate(&candidate));
+    }
+
+    #[test]
+    fn context_buffer() {
+        use crate::{ComponentFilter, FileNameFilter, MatchContext, MatchOptions};
+
+        let options = MatchOptions::new().case_insensitive(true);
+        let components = ComponentFilter::new(["Ärger"]).with_options(options.unicode_case(true));
+        let names = FileNameFilter::new(["Ärger.txt"]).with_options(options);
+        let mut context = MatchContext::new();
+        assert!(context.ignore(&components, "docs/ÄRGER/x"));
+        assert!(!context.ignore(&names, "docs/ärger.txt"));
+        assert!(context.ignore(&names, "docs/ÄRGER.TXT"));
+        assert_eq!(context.buffer, "Ärger.txt");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8() {
+        use crate::{IgnorePath, MatchCandidate, PathFilter};
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+
+        let path = Path::new(OsStr::from_bytes(b"src/\xff.rs"));
+        let candidate = MatchCandidate::new(path);
+        assert!(PathFilter::new_extension(".rs").ignore_candidate(&candidate));
+        #[cfg(feature = "regex")]
+        assert_eq!(candidate.text(&crate::MatchOptions::new()), None);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{IgnorePath, MatchCandidate, PathFilter};
+use std::path::Path;
+
+/// How a [`FilterChain`] combines the decisions of its filters.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChainMode {
+    /// Ignore a path if any filter ignores it, like a plain list of filters.
+    #[default]
+    Any,
+    /// Ignore a path only if every filter ignores it.
+    All,
+}
+
+/// An ordered list of filters combined with a [`ChainMode`].
+///
+/// Filters are evaluated in order and evaluation stops as soon as the decision is known.
+/// An empty chain never ignores a path, in either mode.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{FilterChain, IgnorePath, PathFilter};
+/// use std::path::Path;
+///
+/// let chain = FilterChain::all(vec![
+///     PathFilter::new_extensions([".rs", ".md"]),
+///     PathFilter::new_extensions([".md", ".txt"]),
+/// ]);
+/// assert!(chain.ignore(Path::new("README.md")));
+/// assert!(!chain.ignore(Path::new("src/lib.rs")));
+/// assert!(!chain.ignore(Path::new("notes.txt")));
+/// ```
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FilterChain<F = PathFilter> {
+    filters: Vec<F>,
+    mode: ChainMode,
+}
+
+impl<F: IgnorePath> IgnorePath for FilterChain<F> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        match self.mode {
+            ChainMode::Any => self.filters.ignore_candidate(candidate),
+            ChainMode::All => all_ignore(&self.filters, candidate),
+        }
+    }
+}
+
+/// Returns whether every one of at least one filter ignores the candidate.
+///
+/// This is the conjunction of [`ChainMode::All`] and [`PathFilter::And`].
+pub(crate) fn all_ignore<F: IgnorePath>(filters: &[F], candidate: &MatchCandidate<'_>) -> bool {
+    !filters.is_empty()
+        && filters
+            .iter()
+            .all(|filter| filter.ignore_candidate(candidate))
+}
+
+impl<F> FilterChain<F> {
+    /// Creates an empty chain with the given mode.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{ChainMode, FilterChain, IgnorePath, PathFilter};
+    /// use std::path::Path;
+    ///
+    /// let chain = FilterChain::new(ChainMode::Any)
+    ///     .with_filter(PathFilter::new_extension(".rs"))
+    ///     .with_filter(PathFilter::new_extension(".md"));
+    /// assert!(chain.ignore(Path::new("README.md")));
+    /// ```
+    pub fn new(mode: ChainMode) -> Self {
+        FilterChain {
+            filters: Vec::new(),
+            mode,
+        }
+    }
+
+    /// Creates a chain that ignores a path if any of the filters ignores it.
+    pub fn any(filters: Vec<F>) -> Self {
+        FilterChain {
+            filters,
+            mode: ChainMode::Any,
+        }
+    }
+
+    /// Creates a chain that ignores a path only if all of the filters ignore it.
+    pub fn all(filters: Vec<F>) -> Self {
+        FilterChain {
+            filters,
+            mode: ChainMode::All,
+        }
+    }
+
+    /// Adds a filter to the end of the chain.
+    pub fn with_filter(mut self, filter: F) -> Self {
+        self.push(filter);
+        self
+    }
+
+    /// Adds a filter to the end of the chain.
+    pub fn push(&mut self, filter: F) {
+        self.filters.push(filter);
+    }
+
+    /// Returns the filters of the chain in evaluation order.
+    pub fn filters(&self) -> &[F] {
+        &self.filters
+    }
+
+    /// Returns how the chain combines the decisions of its filters.
+    pub fn mode(&self) -> ChainMode {
+        self.mode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        chain::{ChainMode, FilterChain},
+        ExtensionFilter, ExtensionsFilter, IgnorePath,
+    };
+    use std::{cell::Cell, path::Path};
+
+    #[test]
+    fn any() {
+        let chain = FilterChain::any(vec![
+            ExtensionFilter::new(".rs"),
+            ExtensionFilter::new(".md"),
+        ]);
+        assert_eq!(chain.mode(), ChainMode::Any);
+        assert!(chain.ignore(Path::new("src/lib.rs")));
+        assert!(chain.ignore(Path::new("README.md")));
+        assert!(!chain.ignore(Path::new("Cargo.toml")));
+    }
+
+    #[test]
+    fn all() {
+        let chain = FilterChain::all(vec![
+            ExtensionsFilter::new([".rs", ".md"]),
+            ExtensionsFilter::new([".md", ".txt"]),
+        ]);
+        assert!(chain.ignore(Path::new("README.md")));
+        assert!(!chain.ignore(Path::new("src/lib.rs")));
+        assert!(!chain.ignore(Path::new("notes.txt")));
+    }
+
+    #[test]
+    fn empty() {
+        for mode in [ChainMode::Any, ChainMode::All] {
+            let chain = FilterChain::<ExtensionFilter>::new(mode);
+            assert!(!chain.ignore(Path::new("src/lib.rs")));
+        }
+    }
+
+    struct Counting<'a>(bool, &'a Cell<usize>);
+
+    impl IgnorePath for Counting<'_> {
+        fn ignore<P: AsRef<std::path::Path>>(&self, _path: P) -> bool {
+            self.1.set(self.1.get() + 1);
+            self.0
+        }
+    }
+
+    #[test]
+    fn short_circuits() {
+        let calls = Cell::new(0);
+        let chain = FilterChain::any(vec![Counting(true, &calls), Counting(true, &calls)]);
+        assert!(chain.ignore(Path::new("a")));
+        assert_eq!(calls.get(), 1);
+
+        calls.set(0);
+        let chain = FilterChain::all(vec![Counting(false, &calls), Counting(true, &calls)]);
+        assert!(!chain.ignore(Path::new("a")));
+        assert_eq!(calls.get(), 1);
+    }
+}
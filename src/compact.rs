@@ -0,0 +1,340 @@
+//! A compact serde representation of filters, for use with `#[serde(with = "...")]`.
+//!
+//! Filters that can be written as a rule of a [pattern file](crate::patterns) are
+//! serialized as that string, e.g. `"ext:.rs,.md"`; all others, e.g. filters with
+//! options, use the regular representation. Both are accepted when deserializing,
+//! so a config written in the regular form can be read back in the compact form.
+//!
+//! [`compact::vec`](vec) does the same for a list of filters. With the `glob`
+//! feature, [`compact::gitignore`](gitignore) reads a [`GitignoreFilter`] from a
+//! string with the contents of a `.gitignore` file, e.g. a block in a YAML config,
+//! and [`compact::gitignore::set`](gitignore::set) reads a [`FilterSet`] from one.
+//!
+//! [`FilterSet`]: crate::FilterSet
+//!
+//! [`GitignoreFilter`]: crate::GitignoreFilter
+//!
+//! # Examples
+//! ```
+//! use pathfilter::PathFilter;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Config {
+//!     #[serde(with = "pathfilter::compact")]
+//!     output: PathFilter,
+//!     #[serde(with = "pathfilter::compact::vec")]
+//!     ignore: Vec<PathFilter>,
+//! }
+//! ```
+
+use crate::{
+    patterns::{parse_line, to_rule},
+    PathFilter,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Repr {
+    Rule(String),
+    Filter(PathFilter),
+}
+
+/// A filter that serializes in the compact form.
+struct CompactRef<'a>(&'a PathFilter);
+
+impl Serialize for CompactRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match to_rule(self.0) {
+            Some(rule) => serializer.serialize_str(&rule),
+            None => self.0.serialize(serializer),
+        }
+    }
+}
+
+/// A filter that deserializes from the compact or the regular form.
+struct CompactFilter(PathFilter);
+
+impl<'de> Deserialize<'de> for CompactFilter {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Repr::deserialize(deserializer)? {
+            Repr::Rule(rule) => parse_line(&rule, 1)
+                .map(CompactFilter)
+                .map_err(|err| de::Error::custom(format!("{} in rule `{rule}`", err.kind))),
+            Repr::Filter(filter) => Ok(CompactFilter(filter)),
+        }
+    }
+}
+
+/// Serializes a filter in the compact form.
+///
+/// # Errors
+/// Returns the errors of the serializer.
+pub fn serialize<S: Serializer>(filter: &PathFilter, serializer: S) -> Result<S::Ok, S::Error> {
+    CompactRef(filter).serialize(serializer)
+}
+
+/// Deserializes a filter from the compact or the regular form.
+///
+/// # Errors
+/// If the input is neither a valid rule nor a filter, an error is returned.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PathFilter, D::Error> {
+    CompactFilter::deserialize(deserializer).map(|filter| filter.0)
+}
+
+/// The compact representation for a list of filters.
+pub mod vec {
+    use super::{CompactFilter, CompactRef};
+    use crate::PathFilter;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes every filter in the compact form.
+    ///
+    /// # Errors
+    /// Returns the errors of the serializer.
+    pub fn serialize<S: Serializer>(
+        filters: &[PathFilter],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(filters.iter().map(CompactRef))
+    }
+
+    /// Deserializes a list of filters, each in the compact or the regular form.
+    ///
+    /// # Errors
+    /// If an element is neither a valid rule nor a filter, an error is returned.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<PathFilter>, D::Error> {
+        let filters = Vec::<CompactFilter>::deserialize(deserializer)?;
+        Ok(filters.into_iter().map(|filter| filter.0).collect())
+    }
+}
+
+/// The representation of a [`GitignoreFilter`] as the text of a `.gitignore` file.
+///
+/// The patterns are serialized one per line; the options of the filter are not
+/// part of the text.
+///
+/// # Examples
+/// ```
+/// use pathfilter::GitignoreFilter;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Config {
+///     // ignore: |
+///     //   target/
+///     //   *.log
+///     #[serde(with = "pathfilter::compact::gitignore")]
+///     ignore: GitignoreFilter,
+/// }
+/// ```
+#[cfg(feature = "glob")]
+pub mod gitignore {
+    use crate::GitignoreFilter;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::borrow::Cow;
+
+    /// Serializes the patterns of the filter as lines of a string.
+    ///
+    /// # Errors
+    /// Returns the errors of the serializer.
+    pub fn serialize<S: Serializer>(
+        filter: &GitignoreFilter,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&filter.patterns().collect::<Vec<_>>().join("\n"))
+    }
+
+    /// Deserializes a filter from the text of a `.gitignore` file.
+    ///
+    /// # Errors
+    /// If the input is not a string or a pattern is invalid, an error is returned.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<GitignoreFilter, D::Error> {
+        let text = Cow::<str>::deserialize(deserializer)?;
+        GitignoreFilter::new(&text).map_err(de::Error::custom)
+    }
+
+    /// A [`FilterSet`] given as the text of a `.gitignore` file.
+    ///
+    /// The patterns become the rules of the set, see
+    /// [`GitignoreFilter::to_filter_set`]. A set in the regular form is accepted
+    /// as well, and sets are always serialized in the regular form, as their
+    /// rules are not gitignore patterns in general.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::FilterSet;
+    ///
+    /// #[derive(serde::Serialize, serde::Deserialize)]
+    /// struct Config {
+    ///     // ignore: |
+    ///     //   target/
+    ///     //   !target/doc/
+    ///     #[serde(with = "pathfilter::compact::gitignore::set")]
+    ///     ignore: FilterSet,
+    /// }
+    /// ```
+    pub mod set {
+        use crate::{FilterSet, GitignoreFilter};
+        use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Text(String),
+            Set(FilterSet),
+        }
+
+        /// Serializes the set in the regular form.
+        ///
+        /// # Errors
+        /// Returns the errors of the serializer.
+        pub fn serialize<S: Serializer>(set: &FilterSet, serializer: S) -> Result<S::Ok, S::Error> {
+            set.serialize(serializer)
+        }
+
+        /// Deserializes a set from the text of a `.gitignore` file or the regular form.
+        ///
+        /// # Errors
+        /// If a pattern is invalid or the input is neither a string nor a set, an error
+        /// is returned.
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<FilterSet, D::Error> {
+            match Repr::deserialize(deserializer)? {
+                Repr::Text(text) => GitignoreFilter::new(&text)
+                    .map(|filter| filter.to_filter_set())
+                    .map_err(de::Error::custom),
+                Repr::Set(set) => Ok(set),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compact,
+        value::{to_value, Value},
+        IgnorePath, MatchOptions, PathFilter,
+    };
+    use serde::de::{value::Error, IntoDeserializer};
+
+    fn str(s: &str) -> Value {
+        Value::Str(s.to_string())
+    }
+
+    #[test]
+    fn serialize_compact() {
+        struct Compact(Vec<PathFilter>);
+
+        impl serde::Serialize for Compact {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                compact::vec::serialize(&self.0, serializer)
+            }
+        }
+
+        let with_options = PathFilter::new_extension(".rs")
+            .with_options(MatchOptions::new().case_insensitive(true));
+        let filters = vec![
+            PathFilter::new_extensions([".rs", ".md"]),
+            with_options.clone(),
+        ];
+        assert_eq!(
+            to_value(&Compact(filters)).unwrap(),
+            Value::Seq(vec![str("ext:.md,.rs"), to_value(&with_options).unwrap()])
+        );
+    }
+
+    #[test]
+    fn deserialize_compact() {
+        let filter = compact::deserialize(IntoDeserializer::<Error>::into_deserializer(str(
+            "ext:.rs,.md",
+        )))
+        .unwrap();
+        assert!(matches!(filter, PathFilter::Extensions(_)));
+
+        let regular = Value::Map(vec![(
+            str("Extension"),
+            Value::Map(vec![(str("extension"), Value::os_str("o"))]),
+        )]);
+        let filters = compact::vec::deserialize(IntoDeserializer::<Error>::into_deserializer(
+            Value::Seq(vec![str("ext:.a"), regular]),
+        ))
+        .unwrap();
+        assert_eq!(filters.len(), 2);
+        assert!(filters.ignore("main.o"));
+        assert!(filters.ignore("lib.a"));
+
+        let err = compact::deserialize(IntoDeserializer::<Error>::into_deserializer(str(
+            "path:*.o",
+        )))
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown filter kind `path` in rule `path:*.o`"
+        );
+    }
+
+    #[cfg(feature = "glob")]
+    #[test]
+    fn gitignore() {
+        struct Gitignore(crate::GitignoreFilter);
+
+        impl serde::Serialize for Gitignore {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                compact::gitignore::serialize(&self.0, serializer)
+            }
+        }
+
+        let text = "# build output\ntarget/\n\n*.log\n!keep.log\n";
+        let filter = compact::gitignore::deserialize(IntoDeserializer::<Error>::into_deserializer(
+            str(text),
+        ))
+        .unwrap();
+        assert!(filter.ignore("target/debug/main"));
+        assert!(filter.ignore("logs/a.log"));
+        assert!(!filter.ignore("logs/keep.log"));
+        assert_eq!(
+            to_value(&Gitignore(filter)).unwrap(),
+            str("target/\n*.log\n!keep.log")
+        );
+
+        let err = compact::gitignore::deserialize(IntoDeserializer::<Error>::into_deserializer(
+            str("ok\n[abc\n"),
+        ))
+        .unwrap_err();
+        assert!(err.to_string().starts_with("2:1: "));
+    }
+
+    #[cfg(feature = "glob")]
+    #[test]
+    fn gitignore_set() {
+        use crate::FilterSet;
+
+        let deserialize = |value| {
+            compact::gitignore::set::deserialize(IntoDeserializer::<Error>::into_deserializer(
+                value,
+            ))
+        };
+        let set = deserialize(str("target/\n!target/doc/\n*.tmp\n")).unwrap();
+        assert_eq!(set.len(), 3);
+        assert!(set.ignore("target/debug/main"));
+        assert!(!set.ignore("target/doc/index.html"));
+        assert!(set.ignore("notes.tmp"));
+        // Unlike a `GitignoreFilter`, the set combines with other rules and options.
+        let set = set
+            .with_allow(PathFilter::new_file_names(["keep.tmp"]))
+            .with_options(MatchOptions::new().case_insensitive(true));
+        assert!(set.ignore("NOTES.TMP"));
+        assert!(!set.ignore("keep.tmp"));
+
+        let regular =
+            to_value(&FilterSet::new().with_ignore(PathFilter::new_extension(".o"))).unwrap();
+        assert!(deserialize(regular).unwrap().ignore("main.o"));
+        assert!(deserialize(str("[abc\n")).is_err());
+    }
+}
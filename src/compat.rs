@@ -0,0 +1,398 @@
+//! Helpers for checking that two filters make the same decisions.
+//!
+//! This is useful when migrating from one way of expressing rules to another,
+//! e.g. from hand-written regular expressions to extension filters, and
+//! verifying that the behavior over a set of known paths did not change.
+//!
+//! For tools that watch a tree, [`rescan_scope`] and [`rescan_set_scope`] narrow
+//! down which part of it needs to be evaluated again after the rules changed.
+
+use crate::{patterns::to_rule, FilterSet, IgnorePath, PathFilter};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// A path for which two filters came to different decisions.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Difference {
+    /// The path that was evaluated.
+    pub path: PathBuf,
+    /// Whether the left filter ignored the path.
+    pub left: bool,
+    /// Whether the right filter ignored the path.
+    pub right: bool,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: left {}, right {}",
+            self.path.display(),
+            decision(self.left),
+            decision(self.right)
+        )
+    }
+}
+
+fn decision(ignored: bool) -> &'static str {
+    if ignored {
+        "ignores"
+    } else {
+        "keeps"
+    }
+}
+
+/// Evaluates both filters for every path and returns each path where their decisions differ.
+///
+/// The differences are returned in the order the paths were given.
+///
+/// # Examples
+/// ```
+/// use pathfilter::compat::differences;
+/// use pathfilter::{ExtensionFilter, ExtensionsFilter};
+///
+/// let old = ExtensionFilter::new(".rs");
+/// let new = ExtensionsFilter::new([".rs", ".txt"]);
+///
+/// let diff = differences(&old, &new, ["src/lib.rs", "notes.txt", "image.png"]);
+/// assert_eq!(diff.len(), 1);
+/// assert_eq!(diff[0].path.to_str(), Some("notes.txt"));
+/// assert!(!diff[0].left);
+/// assert!(diff[0].right);
+/// ```
+pub fn differences<A, B, I, P>(left: &A, right: &B, paths: I) -> Vec<Difference>
+where
+    A: IgnorePath,
+    B: IgnorePath,
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+{
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let path = path.as_ref();
+            let (l, r) = (left.ignore(path), right.ignore(path));
+            (l != r).then(|| Difference {
+                path: path.to_path_buf(),
+                left: l,
+                right: r,
+            })
+        })
+        .collect()
+}
+
+/// Returns `true` if both filters make the same decision for every path.
+///
+/// # Examples
+/// ```
+/// use pathfilter::compat::equivalent;
+/// use pathfilter::{ExtensionFilter, ExtensionsFilter};
+///
+/// let old = ExtensionFilter::new(".rs");
+/// let new = ExtensionsFilter::new([".rs"]);
+///
+/// assert!(equivalent(&old, &new, ["src/lib.rs", "notes.txt"]));
+/// ```
+pub fn equivalent<A, B, I, P>(left: &A, right: &B, paths: I) -> bool
+where
+    A: IgnorePath,
+    B: IgnorePath,
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+{
+    paths
+        .into_iter()
+        .all(|path| left.ignore(&path) == right.ignore(&path))
+}
+
+/// The part of a tree whose decisions may have changed, see [`rescan_scope`] and
+/// [`rescan_set_scope`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RescanScope {
+    /// No decision changed.
+    Nothing,
+    /// Only paths inside these directories, relative to the root the rules are
+    /// matched against, may have changed. No directory is inside another one.
+    Subtrees(Vec<PathBuf>),
+    /// Any path may have changed.
+    Everything,
+}
+
+/// Returns which paths may be decided differently after the rules changed from
+/// `old` to `new`.
+///
+/// Rules that are in both lists are not looked at, as the order of rules does not
+/// matter for a list. A changed regex rule anchored at a literal directory, like
+/// `^src/gen/`, limits the scope to that directory; any other changed rule, or a
+/// rule that cannot be compared because it has options set, means everything
+/// needs to be rescanned. The result is conservative: paths in the scope do not
+/// necessarily change, but paths outside of it do not.
+///
+/// # Examples
+/// ```
+/// use pathfilter::compat::{rescan_scope, RescanScope};
+/// use pathfilter::PathFilter;
+///
+/// let old = vec![PathFilter::new_extension(".o")];
+/// assert_eq!(rescan_scope(&old, &old), RescanScope::Nothing);
+///
+/// let new = vec![PathFilter::new_extension(".a")];
+/// assert_eq!(rescan_scope(&old, &new), RescanScope::Everything);
+/// ```
+pub fn rescan_scope(old: &[PathFilter], new: &[PathFilter]) -> RescanScope {
+    let (Some(old), Some(new)) = (rules(old), rules(new)) else {
+        return RescanScope::Everything;
+    };
+
+    let changed = old
+        .iter()
+        .filter(|(rule, _)| !new.contains_key(*rule))
+        .chain(new.iter().filter(|(rule, _)| !old.contains_key(*rule)));
+    scope(changed.map(|(_, filter)| *filter))
+}
+
+/// Returns which paths may be decided differently after the rules of a set
+/// changed from `old` to `new`.
+///
+/// Unlike for a list, the order of the rules of a [`FilterSet`] matters, as the
+/// last matching rule decides. Rules up to the first difference and after the
+/// last one are not looked at; every rule in between, in either set, is treated
+/// as changed, including rules that were only moved or turned from
+/// [`Ignore`](crate::FilterRule::Ignore) into [`Allow`](crate::FilterRule::Allow).
+/// The scope is then the union of what the changed rules match, determined as
+/// for [`rescan_scope`].
+///
+/// # Examples
+/// ```
+/// use pathfilter::compat::{rescan_set_scope, RescanScope};
+/// use pathfilter::{FilterSet, PathFilter};
+///
+/// let old = FilterSet::new().with_ignore(PathFilter::new_extension(".o"));
+/// assert_eq!(rescan_set_scope(&old, &old), RescanScope::Nothing);
+///
+/// let new = old.clone().with_allow(PathFilter::new_file_names(["keep.o"]));
+/// assert_eq!(rescan_set_scope(&old, &new), RescanScope::Everything);
+/// ```
+pub fn rescan_set_scope(old: &FilterSet, new: &FilterSet) -> RescanScope {
+    // Rules without a rule form never compare equal, so they end up as changed.
+    let keys = |set: &FilterSet| -> Vec<_> {
+        set.rules()
+            .iter()
+            .map(|rule| to_rule(rule.filter()).map(|text| (rule.is_ignore(), text)))
+            .collect()
+    };
+    let (old_keys, new_keys) = (keys(old), keys(new));
+    let same = |(a, b): (&Option<_>, &Option<_>)| a.is_some() && a == b;
+    let start = old_keys
+        .iter()
+        .zip(&new_keys)
+        .take_while(|&pair| same(pair))
+        .count();
+    let end = old_keys[start..]
+        .iter()
+        .rev()
+        .zip(new_keys[start..].iter().rev())
+        .take_while(|&pair| same(pair))
+        .count();
+
+    let changed = old.rules()[start..old.len() - end]
+        .iter()
+        .chain(&new.rules()[start..new.len() - end]);
+    let mut filters = Vec::new();
+    for rule in changed {
+        if to_rule(rule.filter()).is_none() {
+            return RescanScope::Everything;
+        }
+        filters.push(rule.filter());
+    }
+    scope(filters)
+}
+
+/// Returns the scope of the paths matched by any of the changed filters.
+fn scope<'a>(changed: impl IntoIterator<Item = &'a PathFilter>) -> RescanScope {
+    let mut subtrees = Vec::new();
+    for filter in changed {
+        match subtree(filter) {
+            Some(dir) => subtrees.push(dir),
+            None => return RescanScope::Everything,
+        }
+    }
+    if subtrees.is_empty() {
+        return RescanScope::Nothing;
+    }
+
+    subtrees.sort();
+    subtrees.dedup();
+    let mut outermost: Vec<PathBuf> = Vec::with_capacity(subtrees.len());
+    for dir in subtrees {
+        if !outermost
+            .last()
+            .is_some_and(|parent| dir.starts_with(parent))
+        {
+            outermost.push(dir);
+        }
+    }
+    RescanScope::Subtrees(outermost)
+}
+
+/// Returns the filters by their rule, or `None` if a filter has no rule form.
+fn rules(filters: &[PathFilter]) -> Option<BTreeMap<String, &PathFilter>> {
+    filters
+        .iter()
+        .map(|filter| Some((to_rule(filter)?, filter)))
+        .collect()
+}
+
+/// Returns the directory a filter only matches paths in, if there is one.
+fn subtree(filter: &PathFilter) -> Option<PathBuf> {
+    match filter {
+        #[cfg(feature = "regex")]
+        PathFilter::Regex(regex) => {
+            let pattern = regex.as_str().strip_prefix('^')?;
+            if pattern.contains('|') {
+                return None;
+            }
+            let end = pattern
+                .find(|c: char| "\\.+*?()[]{}^$".contains(c))
+                .unwrap_or(pattern.len());
+            let mut literal = &pattern[..end];
+            if pattern[end..].starts_with(['?', '*', '{']) {
+                let last = literal.chars().last()?;
+                literal = &literal[..literal.len() - last.len_utf8()];
+            }
+            let dir = &literal[..literal.rfind('/')?];
+            (!dir.is_empty()).then(|| PathBuf::from(dir))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{compat::differences, compat::equivalent, PathFilter};
+    use std::path::Path;
+
+    const CORPUS: [&str; 5] = [
+        "src/lib.rs",
+        "src/main.rs",
+        "README.md",
+        "notes.txt",
+        "target/debug/build.log",
+    ];
+
+    #[test]
+    fn no_differences() {
+        let left = PathFilter::new_extension(".rs");
+        let right = vec![PathFilter::new_extensions([".rs"])];
+
+        assert!(differences(&left, &right, CORPUS).is_empty());
+        assert!(equivalent(&left, &right, CORPUS));
+    }
+
+    #[test]
+    fn reports_every_difference() {
+        let left = PathFilter::new_extension(".rs");
+        let right = PathFilter::new_extensions([".md", ".txt"]);
+
+        let diff = differences(&left, &right, CORPUS);
+        assert!(!equivalent(&left, &right, CORPUS));
+        assert_eq!(diff.len(), 4);
+        assert_eq!(diff[0].path, Path::new("src/lib.rs"));
+        assert!(diff[0].left && !diff[0].right);
+        assert_eq!(diff[2].path, Path::new("README.md"));
+        assert!(!diff[2].left && diff[2].right);
+        assert_eq!(diff[2].to_string(), "README.md: left keeps, right ignores");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex_migration() {
+        use regex::Regex;
+
+        let left = PathFilter::new_regex(Regex::new("\\.(rs|txt)$").unwrap());
+        let right = PathFilter::new_extensions([".rs", ".txt"]);
+
+        assert!(equivalent(&left, &right, CORPUS));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn rescan_scope() {
+        use crate::compat::{rescan_scope, RescanScope};
+
+        let regex = |pattern: &str| PathFilter::new_regex(regex::Regex::new(pattern).unwrap());
+        let base = vec![PathFilter::new_extension(".o"), regex("^target/")];
+
+        let mut reordered = base.clone();
+        reordered.reverse();
+        assert_eq!(rescan_scope(&base, &reordered), RescanScope::Nothing);
+
+        let mut new = base.clone();
+        new.extend([
+            regex("^src/gen/"),
+            regex("^src/gen/tmp/"),
+            regex("^docs/a?b"),
+        ]);
+        assert_eq!(
+            rescan_scope(&base, &new),
+            RescanScope::Subtrees(vec!["docs".into(), "src/gen".into()])
+        );
+
+        for pattern in ["^src/?", "^src", "src/gen/", "^src/|^docs/"] {
+            let new = vec![base[0].clone(), regex(pattern)];
+            assert_eq!(
+                rescan_scope(&base, &new),
+                RescanScope::Everything,
+                "{pattern}"
+            );
+        }
+
+        let case_insensitive = base[1]
+            .clone()
+            .with_options(crate::MatchOptions::new().case_insensitive(true));
+        assert_eq!(
+            rescan_scope(&base, &[base[0].clone(), case_insensitive]),
+            RescanScope::Everything
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn rescan_set_scope() {
+        use crate::compat::{rescan_set_scope, RescanScope};
+        use crate::FilterSet;
+
+        let regex = |pattern: &str| PathFilter::new_regex(regex::Regex::new(pattern).unwrap());
+        let base = FilterSet::new()
+            .with_ignore(PathFilter::new_extension(".o"))
+            .with_ignore(regex("^target/"))
+            .with_allow(regex("^target/keep/"))
+            .with_ignore(regex("^docs/"));
+        assert_eq!(rescan_set_scope(&base, &base), RescanScope::Nothing);
+
+        let rules = base.rules().to_vec();
+        let swapped: FilterSet = [&rules[0], &rules[2], &rules[1], &rules[3]]
+            .into_iter()
+            .cloned()
+            .collect();
+        assert_eq!(
+            rescan_set_scope(&base, &swapped),
+            RescanScope::Subtrees(vec!["target".into()])
+        );
+
+        let mut flipped: FilterSet = rules[..3].iter().cloned().collect();
+        flipped.add_allow(regex("^docs/"));
+        assert_eq!(
+            rescan_set_scope(&base, &flipped),
+            RescanScope::Subtrees(vec!["docs".into()])
+        );
+
+        let mut reordered = rules.clone();
+        reordered.reverse();
+        let reordered: FilterSet = reordered.into_iter().collect();
+        assert_eq!(rescan_set_scope(&base, &reordered), RescanScope::Everything);
+    }
+}
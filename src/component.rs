@@ -0,0 +1,125 @@
+use crate::{
+    names::NameSet, normalize::split_verbatim_prefix, options::options_methods, IgnorePath,
+    MatchCandidate, MatchOptions, MatchOverrides,
+};
+use std::path::{Component, Path};
+
+/// A filter that matches paths with a component equal to one of a set of names.
+///
+/// Every component of the path is compared, so `node_modules` matches
+/// `web/node_modules/react/index.js` as well as `node_modules` itself, but not
+/// `node_modules_old`. Components that are not valid UTF-8 never match. With
+/// [`MatchOptions::normalize_separators`], `\` separates components as well and
+/// extended-length prefixes like `\\?\` are skipped.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{ComponentFilter, IgnorePath};
+/// use std::path::Path;
+///
+/// let filter = ComponentFilter::new(["node_modules", "target", ".git"]);
+/// assert!(filter.ignore(Path::new("web/node_modules/react/index.js")));
+/// assert!(filter.ignore(Path::new("target/debug/main")));
+/// assert!(!filter.ignore(Path::new("src/target.rs")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComponentFilter {
+    components: NameSet,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOptions::is_default")
+    )]
+    options: MatchOptions,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOverrides::is_empty")
+    )]
+    overrides: MatchOverrides,
+}
+
+impl IgnorePath for ComponentFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        let options = self.options();
+        let path = candidate.path();
+        candidate.with_buffer(|buffer| match path.to_str() {
+            // Windows paths on other platforms: split the text of the path instead.
+            Some(text) if options.normalizes_separators() => split_verbatim_prefix(text)
+                .1
+                .split(['/', '\\'])
+                .filter(|name| !matches!(*name, "" | "." | ".."))
+                .any(|name| self.components.contains(name, options, buffer)),
+            _ => path.components().any(|component| match component {
+                Component::Normal(name) => name
+                    .to_str()
+                    .is_some_and(|name| self.components.contains(name, options, buffer)),
+                _ => false,
+            }),
+        })
+    }
+}
+
+impl ComponentFilter {
+    /// Creates a new filter for a list of component names.
+    pub fn new<I, S>(components: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ComponentFilter {
+            components: components.into_iter().collect(),
+            options: MatchOptions::new(),
+            overrides: MatchOverrides::new(),
+        }
+    }
+
+    /// Adds a component name to the filter.
+    pub fn with_component<S: Into<String>>(mut self, component: S) -> Self {
+        self.components.insert(component.into());
+        self
+    }
+
+    /// Returns the component names this filter matches, in unspecified order.
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.components.iter()
+    }
+
+    options_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ComponentFilter, IgnorePath, MatchOptions};
+    use std::path::Path;
+
+    #[test]
+    fn components() {
+        let filter = ComponentFilter::new(["target"]).with_component(".git");
+        assert!(filter.ignore(Path::new("target")));
+        assert!(filter.ignore(Path::new("/home/me/repo/.git/HEAD")));
+        assert!(filter.ignore(Path::new("./crates/a/target/")));
+        assert!(!filter.ignore(Path::new("targets/a")));
+        assert!(!filter.ignore(Path::new("TARGET/a")));
+        assert!(!filter.ignore(Path::new("src/lib.rs")));
+
+        let filter = filter.with_options(MatchOptions::new().case_insensitive(true));
+        assert!(filter.ignore(Path::new("TARGET/a")));
+        assert!(filter.ignore(Path::new("repo/.Git")));
+
+        let filter = filter.with_options(MatchOptions::new().normalize_separators(true));
+        assert!(filter.ignore(Path::new("web\\target\\x.js")));
+        assert!(!filter.ignore(Path::new("web\\targets\\x.js")));
+        assert!(filter.ignore(Path::new(r"\\?\C:\repo\target\x.js")));
+        assert!(!ComponentFilter::new(["?"])
+            .with_options(MatchOptions::new().normalize_separators(true))
+            .ignore(Path::new(r"\\?\C:\x")));
+
+        let mut components: Vec<_> = filter.components().collect();
+        components.sort();
+        assert_eq!(components, [".git", "target"]);
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+/// Decides what a filter that reads files does when a file cannot be read.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IoErrorPolicy {
+    /// Keep the path, i.e. do not ignore it. This is the default.
+    #[default]
+    Keep,
+    /// Ignore the path.
+    Ignore,
+}
+
+impl IoErrorPolicy {
+    pub(crate) fn resolve(self, result: io::Result<bool>) -> bool {
+        result.unwrap_or(self == IoErrorPolicy::Ignore)
+    }
+}
+
+/// Implements `with_io_error_policy` and `io_error_policy` for a filter with an
+/// `on_error` field, given the paths the policy applies to, e.g.
+/// `"files that cannot be read"`.
+///
+/// A leading `const` makes both methods `const`, and the optional second string
+/// is appended to the docs of the setter. Attributes before the arguments are
+/// added to the setter.
+macro_rules! io_error_policy_methods {
+    ($(#[$attr:meta])* const $what:literal $(, $detail:literal)?) => {
+        $crate::content::io_error_policy_methods!(@ [const] [$(#[$attr])*] $what $(, $detail)?);
+    };
+    ($(#[$attr:meta])* $what:literal $(, $detail:literal)?) => {
+        $crate::content::io_error_policy_methods!(@ [] [$(#[$attr])*] $what $(, $detail)?);
+    };
+    (@ [$($const:tt)?] [$(#[$attr:meta])*] $what:literal $(, $detail:literal)?) => {
+        #[doc = concat!("Sets what happens to ", $what, $(", ", $detail,)? ".")]
+        $(#[$attr])*
+        pub $($const)? fn with_io_error_policy(mut self, policy: $crate::IoErrorPolicy) -> Self {
+            self.on_error = policy;
+            self
+        }
+
+        #[doc = concat!("Returns what happens to ", $what, ".")]
+        pub $($const)? fn io_error_policy(&self) -> $crate::IoErrorPolicy {
+            self.on_error
+        }
+    };
+}
+pub(crate) use io_error_policy_methods;
+
+/// Reads up to `len` bytes from the start of the file.
+///
+/// The buffer is sized for the file rather than for `len`, so a huge `len`, e.g.
+/// `usize::MAX` to read whole files, does not allocate more than the file needs.
+pub(crate) fn read_prefix(path: &Path, len: usize) -> io::Result<Vec<u8>> {
+    let file = File::open(path)?;
+    let size = file.metadata().map_or(0, |metadata| metadata.len());
+    let mut prefix = Vec::with_capacity(usize::try_from(size).unwrap_or(usize::MAX).min(len));
+    file.take(len as u64).read_to_end(&mut prefix)?;
+    Ok(prefix)
+}
+
+/// Returns `true` if the start of a file looks like binary data, i.e. contains a NUL byte.
+pub(crate) fn looks_binary(prefix: &[u8]) -> bool {
+    prefix.contains(&0)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::IoErrorPolicy;
+    use std::{
+        io,
+        path::{Path, PathBuf},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    /// A directory that is removed when dropped.
+    pub(crate) struct TempDir(PathBuf);
+
+    impl TempDir {
+        pub(crate) fn new() -> Self {
+            static COUNT: AtomicUsize = AtomicUsize::new(0);
+            let path = std::env::temp_dir().join(format!(
+                "pathfilter-{}-{}",
+                std::process::id(),
+                COUNT.fetch_add(1, Ordering::Relaxed)
+            ));
+            std::fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+
+        pub(crate) fn write(&self, name: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+            let path = self.0.join(name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        }
+
+        pub(crate) fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn io_error_policy() {
+        let err = || Err(io::Error::from(io::ErrorKind::NotFound));
+        assert!(!IoErrorPolicy::Keep.resolve(err()));
+        assert!(IoErrorPolicy::Ignore.resolve(err()));
+        assert!(IoErrorPolicy::Keep.resolve(Ok(true)));
+        assert!(!IoErrorPolicy::Ignore.resolve(Ok(false)));
+    }
+}
@@ -0,0 +1,241 @@
+//! Generating synthetic path corpora for tests and benchmarks.
+//!
+//! This module is only available with the `test-util` feature. A [`Corpus`]
+//! produces realistic looking relative paths from a seed, so the same seed always
+//! yields the same paths, without shipping fixture trees.
+//!
+//! # Examples
+//! ```
+//! use pathfilter::{corpus::Corpus, IgnorePath, PathFilter};
+//!
+//! let paths = Corpus::new(42).with_max_depth(3).generate(1000);
+//! assert_eq!(paths.len(), 1000);
+//! assert_eq!(paths, Corpus::new(42).with_max_depth(3).generate(1000));
+//!
+//! let filter = PathFilter::new_extension(".rs");
+//! let ignored = paths.iter().filter(|path| filter.ignore(path)).count();
+//! assert!(ignored > 0 && ignored < paths.len());
+//! ```
+
+/// The separator style of generated paths.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum PathStyle {
+    /// Components are separated by `/`.
+    #[default]
+    Unix,
+    /// Components are separated by `\`.
+    Windows,
+}
+
+/// A generator for synthetic relative paths.
+///
+/// By default paths are up to 4 directories deep, use [`DEFAULT_EXTENSIONS`] with
+/// their weights, contain only ASCII names and use `/` as separator.
+#[derive(Clone, Debug)]
+pub struct Corpus {
+    seed: u64,
+    max_depth: usize,
+    extensions: Vec<(String, u32)>,
+    odd_names: bool,
+    style: PathStyle,
+}
+
+/// The extensions generated by default, with their relative weights.
+///
+/// An empty extension generates files without one, like `Makefile`.
+pub const DEFAULT_EXTENSIONS: &[(&str, u32)] = &[
+    ("rs", 20),
+    ("md", 8),
+    ("toml", 4),
+    ("json", 6),
+    ("txt", 5),
+    ("png", 4),
+    ("o", 6),
+    ("log", 3),
+    ("tar.gz", 1),
+    ("", 3),
+];
+
+const DIRS: &[&str] = &[
+    "src",
+    "tests",
+    "docs",
+    "target",
+    "build",
+    "assets",
+    "lib",
+    "bin",
+    "vendor",
+    "config",
+    "scripts",
+    "node_modules",
+    ".git",
+    "debug",
+    "release",
+    "images",
+    "data",
+    "tmp",
+];
+
+const STEMS: &[&str] = &[
+    "main", "lib", "mod", "index", "README", "util", "config", "test", "parser", "lexer", "build",
+    "logo", "data", "notes", "LICENSE", "Makefile", "app", "server", "client",
+];
+
+const ODD_NAMES: &[&str] = &[
+    "über",
+    "日本語",
+    "naïve file",
+    "emoji 🎉",
+    "with\\backslash",
+    "trailing.",
+    ".hidden",
+    "UPPER",
+    "a,b",
+    "semi;colon",
+    "Ελληνικά",
+    "-dash",
+];
+
+impl Corpus {
+    /// Creates a generator with the default settings for a seed.
+    pub fn new(seed: u64) -> Self {
+        Corpus {
+            seed,
+            max_depth: 4,
+            extensions: DEFAULT_EXTENSIONS
+                .iter()
+                .map(|&(ext, weight)| (ext.to_string(), weight))
+                .collect(),
+            odd_names: false,
+            style: PathStyle::Unix,
+        }
+    }
+
+    /// Sets the maximum number of directories above a file.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the extensions to generate, without a leading `.`, with their weights.
+    ///
+    /// # Panics
+    /// Panics if the list is empty or all weights are zero.
+    pub fn with_extensions<S: AsRef<str>>(mut self, extensions: &[(S, u32)]) -> Self {
+        assert!(
+            extensions.iter().any(|(_, weight)| *weight > 0),
+            "at least one extension needs a weight"
+        );
+        self.extensions = extensions
+            .iter()
+            .map(|(ext, weight)| (ext.as_ref().trim_start_matches('.').to_string(), *weight))
+            .collect();
+        self
+    }
+
+    /// Sets whether some names contain non-ASCII text, spaces and other unusual characters.
+    pub fn with_odd_names(mut self, odd_names: bool) -> Self {
+        self.odd_names = odd_names;
+        self
+    }
+
+    /// Sets the separator style of the paths.
+    pub fn with_style(mut self, style: PathStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Generates `count` paths.
+    pub fn generate(&self, count: usize) -> Vec<String> {
+        let mut rng = SplitMix64(self.seed);
+        (0..count).map(|_| self.path(&mut rng)).collect()
+    }
+
+    fn path(&self, rng: &mut SplitMix64) -> String {
+        let separator = match self.style {
+            PathStyle::Unix => '/',
+            PathStyle::Windows => '\\',
+        };
+
+        let mut path = String::new();
+        for _ in 0..rng.below(self.max_depth as u64 + 1) {
+            path.push_str(self.name(rng, DIRS));
+            path.push(separator);
+        }
+
+        path.push_str(self.name(rng, STEMS));
+        let ext = self.extension(rng);
+        if !ext.is_empty() {
+            path.push('.');
+            path.push_str(ext);
+        }
+        path
+    }
+
+    fn name(&self, rng: &mut SplitMix64, names: &'static [&'static str]) -> &'static str {
+        if self.odd_names && rng.below(8) == 0 {
+            rng.pick(ODD_NAMES)
+        } else {
+            rng.pick(names)
+        }
+    }
+
+    fn extension(&self, rng: &mut SplitMix64) -> &str {
+        let total: u64 = self.extensions.iter().map(|(_, w)| u64::from(*w)).sum();
+        let mut roll = rng.below(total);
+        for (ext, weight) in &self.extensions {
+            match roll.checked_sub(u64::from(*weight)) {
+                Some(rest) => roll = rest,
+                None => return ext,
+            }
+        }
+        unreachable!("roll is below the total weight")
+    }
+}
+
+/// A small, fast generator, good enough for test data.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::corpus::{Corpus, PathStyle};
+
+    #[test]
+    fn generate() {
+        let corpus = Corpus::new(7)
+            .with_max_depth(2)
+            .with_extensions(&[(".rs", 1), ("md", 0)]);
+        let paths = corpus.generate(200);
+        assert_eq!(paths, corpus.generate(200));
+        assert_ne!(paths, Corpus::new(8).with_max_depth(2).generate(200));
+        assert!(paths.iter().all(|path| path.ends_with(".rs")));
+        assert!(paths.iter().all(|path| path.matches('/').count() <= 2));
+        assert!(paths.iter().any(|path| !path.contains('/')));
+
+        let paths = Corpus::new(7)
+            .with_odd_names(true)
+            .with_style(PathStyle::Windows)
+            .generate(500);
+        assert!(paths.iter().all(|path| !path.contains('/')));
+        assert!(paths.iter().any(|path| !path.is_ascii()));
+    }
+}
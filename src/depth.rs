@@ -0,0 +1,89 @@
+use crate::IgnorePath;
+use std::path::{Component, Path};
+
+/// A filter that matches paths by their depth, the number of names they consist of.
+///
+/// `src/lib.rs` has a depth of 2. Roots, prefixes and `.` components are not
+/// counted, `..` components are. Paths relative to the root of the walk give the
+/// most useful results.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{DepthFilter, IgnorePath};
+/// use std::path::Path;
+///
+/// let filter = DepthFilter::deeper_than(2);
+/// assert!(!filter.ignore(Path::new("vendor/zlib")));
+/// assert!(filter.ignore(Path::new("vendor/zlib/contrib")));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DepthFilter {
+    depth: usize,
+    deeper: bool,
+}
+
+impl IgnorePath for DepthFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.matches_depth(depth(path.as_ref()))
+    }
+}
+
+fn depth(path: &Path) -> usize {
+    path.components()
+        .filter(|component| matches!(component, Component::Normal(_) | Component::ParentDir))
+        .count()
+}
+
+impl DepthFilter {
+    /// Creates a new filter for paths with more than `depth` names.
+    pub fn deeper_than(depth: usize) -> Self {
+        DepthFilter {
+            depth,
+            deeper: true,
+        }
+    }
+
+    /// Creates a new filter for paths with fewer than `depth` names.
+    pub fn shallower_than(depth: usize) -> Self {
+        DepthFilter {
+            depth,
+            deeper: false,
+        }
+    }
+
+    /// Returns the depth paths are compared to.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns whether a path with `depth` names is matched.
+    pub fn matches_depth(&self, depth: usize) -> bool {
+        if self.deeper {
+            depth > self.depth
+        } else {
+            depth < self.depth
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DepthFilter, IgnorePath};
+    use std::path::Path;
+
+    #[test]
+    fn depth_filter() {
+        let filter = DepthFilter::deeper_than(2);
+        assert!(!filter.ignore(Path::new("src")));
+        assert!(!filter.ignore(Path::new("./src/lib.rs")));
+        assert!(!filter.ignore(Path::new("/src/lib.rs")));
+        assert!(filter.ignore(Path::new("src/bin/main.rs")));
+        assert!(filter.ignore(Path::new("../src/lib.rs")));
+
+        let filter = DepthFilter::shallower_than(2);
+        assert!(filter.ignore(Path::new("")));
+        assert!(filter.ignore(Path::new("src/")));
+        assert!(!filter.ignore(Path::new("src/lib.rs")));
+        assert_eq!(filter.depth(), 2);
+    }
+}
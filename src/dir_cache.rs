@@ -0,0 +1,307 @@
+use crate::{FilterRule, FilterSet, IgnorePath, MatchCandidate, PathFilter};
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// A cache for a [`FilterSet`] that remembers which directories the set decides as a whole.
+///
+/// For every path, the decision for its parent directory, if the rules allow to
+/// infer one, is computed once and then reused for all other paths in that
+/// directory, without evaluating the rules per path. See
+/// [`FilterSet::decide_dir`] for when a directory can be decided. The cache can
+/// be shared between threads and never evicts entries, so create a new one when
+/// the set changes or when walking another tree.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{DirCache, FilterSet, IgnorePath, PathFilter};
+/// use std::path::Path;
+///
+/// let set = FilterSet::new()
+///     .with_ignore(PathFilter::new_extension(".log"))
+///     .with_ignore(PathFilter::new_components(["target"]));
+/// let cache = DirCache::new(&set);
+/// assert!(cache.ignore(Path::new("target/debug/main")));
+/// assert!(cache.ignore(Path::new("target/debug/main.d")));
+/// assert!(!cache.ignore(Path::new("src/lib.rs")));
+/// assert_eq!(cache.cached(Path::new("target/debug")), Some(Some(true)));
+/// ```
+#[derive(Debug)]
+pub struct DirCache<'a> {
+    set: &'a FilterSet,
+    dirs: Mutex<HashMap<PathBuf, Option<bool>>>,
+}
+
+impl IgnorePath for DirCache<'_> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        let decided = candidate
+            .path()
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .and_then(|dir| self.decide(dir));
+        decided.unwrap_or_else(|| self.set.ignore_candidate(candidate))
+    }
+}
+
+impl<'a> DirCache<'a> {
+    /// Creates an empty cache for the set.
+    pub fn new(set: &'a FilterSet) -> Self {
+        DirCache {
+            set,
+            dirs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the set this cache is for.
+    pub fn set(&self) -> &'a FilterSet {
+        self.set
+    }
+
+    /// Returns the cached decision for the directory, or `None` if it was not computed yet.
+    ///
+    /// The decision is `Some(true)` if every path inside is ignored, `Some(false)`
+    /// if every path inside is kept and `None` if the paths have to be evaluated.
+    pub fn cached(&self, dir: &Path) -> Option<Option<bool>> {
+        self.dirs().get(dir).copied()
+    }
+
+    /// Returns the number of cached directories.
+    pub fn len(&self) -> usize {
+        self.dirs().len()
+    }
+
+    /// Returns `true` if no directory is cached.
+    pub fn is_empty(&self) -> bool {
+        self.dirs().is_empty()
+    }
+
+    /// Removes all cached decisions.
+    pub fn clear(&mut self) {
+        self.dirs
+            .get_mut()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+    }
+
+    fn decide(&self, dir: &Path) -> Option<bool> {
+        if let Some(decision) = self.cached(dir) {
+            return decision;
+        }
+        let decision = self.set.decide_dir(dir);
+        self.dirs().insert(dir.to_path_buf(), decision);
+        decision
+    }
+
+    fn dirs(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, Option<bool>>> {
+        // The map is valid even if another thread panicked while holding the lock.
+        self.dirs.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl FilterSet<PathFilter> {
+    /// Returns whether every path inside of `dir` is ignored, if the rules decide
+    /// the directory as a whole.
+    ///
+    /// A directory is decided by the last rule matching it, written with a
+    /// trailing `/`, if that rule is directory-decidable, i.e. matching a
+    /// directory implies matching everything inside of it, and no later rule of
+    /// the other kind can match inside of the directory. The directory-decidable
+    /// filters are:
+    /// - [`ComponentFilter`](crate::ComponentFilter) and [`PrefixFilter`](crate::PrefixFilter),
+    /// - regexes matching the whole path that end with `/.*$` or `(?:/.*)?$`,
+    ///   which [`GitignoreFilter::to_filter_set`](crate::GitignoreFilter::to_filter_set)
+    ///   and the pattern file loaders use for directory rules,
+    /// - `And` and `Or` of directory-decidable filters.
+    ///
+    /// Of the later rules, only prefixes outside of the directory are known to not
+    /// match inside of it. Returns `Some(true)` if all paths inside are ignored,
+    /// `Some(false)` if all of them are kept and `None` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{FilterSet, PathFilter};
+    /// use std::path::Path;
+    ///
+    /// let set = FilterSet::new()
+    ///     .with_ignore(PathFilter::new_prefixes(["vendor"]))
+    ///     .with_allow(PathFilter::new_prefixes(["vendor/patched"]));
+    /// assert_eq!(set.decide_dir(Path::new("vendor/other")), Some(true));
+    /// assert_eq!(set.decide_dir(Path::new("vendor/patched/src")), Some(false));
+    /// assert_eq!(set.decide_dir(Path::new("vendor")), None);
+    /// assert_eq!(set.decide_dir(Path::new("src")), None);
+    /// ```
+    pub fn decide_dir(&self, dir: &Path) -> Option<bool> {
+        let mut text = OsString::from(dir);
+        text.push("/");
+        let candidate = MatchCandidate::new(Path::new(&text));
+
+        let (index, rule) = self
+            .rules()
+            .iter()
+            .enumerate()
+            .rfind(|(_, rule)| rule.filter().ignore_candidate(&candidate))?;
+        if !contains_dir_contents(rule.filter()) {
+            return None;
+        }
+        let overruled = self.rules()[index + 1..]
+            .iter()
+            .filter(|later| later.is_ignore() != rule.is_ignore())
+            .any(|later| may_match_inside(later.filter(), dir));
+        (!overruled).then_some(matches!(rule, FilterRule::Ignore(_)))
+    }
+}
+
+/// Returns `true` if a directory matching the filter implies that everything
+/// inside of it matches too.
+fn contains_dir_contents(filter: &PathFilter) -> bool {
+    match filter {
+        PathFilter::Component(_) | PathFilter::Prefix(_) => true,
+        PathFilter::And(x) | PathFilter::Or(x) => x.iter().all(contains_dir_contents),
+        #[cfg(feature = "regex")]
+        PathFilter::Regex(x) => x.target().is_path() && is_dir_regex(x.as_str()),
+        _ => false,
+    }
+}
+
+/// Returns `false` only if the filter is known to never match inside of `dir`.
+fn may_match_inside(filter: &PathFilter, dir: &Path) -> bool {
+    match filter {
+        PathFilter::Prefix(x) => x.has_prefix_inside(dir),
+        PathFilter::Or(x) => x.iter().any(|filter| may_match_inside(filter, dir)),
+        PathFilter::And(x) => x.iter().all(|filter| may_match_inside(filter, dir)),
+        _ => true,
+    }
+}
+
+/// Returns `true` if the regex ends with a match of anything after a `/`, so a
+/// match of a directory extends to everything inside of it.
+///
+/// A `|` outside of groups and classes would split off alternatives without
+/// that ending, so such regexes are rejected.
+#[cfg(feature = "regex")]
+fn is_dir_regex(regex: &str) -> bool {
+    let Some(body) = ["/.*$", "(?:/.*)?$"]
+        .iter()
+        .find_map(|end| regex.strip_suffix(end))
+    else {
+        return false;
+    };
+
+    let (mut depth, mut class, mut chars) = (0usize, false, body.chars());
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => class = true,
+            ']' => class = false,
+            '(' if !class => depth += 1,
+            ')' if !class => depth = depth.saturating_sub(1),
+            '|' if !class && depth == 0 => return false,
+            _ => {}
+        }
+    }
+    // The ending must not be escaped or inside a class.
+    !class && !body.ends_with('\\')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DirCache, FilterSet, IgnorePath, PathFilter};
+    use std::path::Path;
+
+    #[test]
+    fn decide_dir() {
+        let set = FilterSet::new()
+            .with_ignore(PathFilter::new_components(["target"]))
+            .with_ignore(PathFilter::new_extension(".log"))
+            .with_allow(PathFilter::new_file_names(["keep.log"]));
+        // An allow rule for file names can match inside of any directory.
+        assert_eq!(set.decide_dir(Path::new("a/target")), None);
+
+        let set = FilterSet::new()
+            .with_allow(PathFilter::new_file_names(["keep.log"]))
+            .with_ignore(PathFilter::new_components(["target"]))
+            .with_ignore(PathFilter::new_extension(".log"));
+        assert_eq!(set.decide_dir(Path::new("a/target")), Some(true));
+        assert_eq!(set.decide_dir(Path::new("a/src")), None);
+
+        let set = FilterSet::new()
+            .with_ignore(PathFilter::new_extension(".o"))
+            .with_allow(PathFilter::new_or(vec![
+                PathFilter::new_prefixes(["src"]),
+                PathFilter::new_components(["docs"]),
+            ]));
+        assert_eq!(set.decide_dir(Path::new("src/a")), Some(false));
+        assert_eq!(set.decide_dir(Path::new("lib/docs")), Some(false));
+        assert_eq!(set.decide_dir(Path::new("lib")), None);
+    }
+
+    #[cfg(feature = "glob")]
+    #[test]
+    fn gitignore_rules() {
+        use crate::GitignoreFilter;
+
+        let set = GitignoreFilter::new("*.log\n!keep.log\nbuild/\n/out\n")
+            .unwrap()
+            .to_filter_set();
+        assert_eq!(set.decide_dir(Path::new("crate/build")), Some(true));
+        assert_eq!(set.decide_dir(Path::new("out/x")), Some(true));
+        assert_eq!(set.decide_dir(Path::new("src")), None);
+
+        let cache = DirCache::new(&set);
+        for path in [
+            "crate/build/a.o",
+            "crate/build/keep.log",
+            "src/keep.log",
+            "src/a.log",
+        ] {
+            assert_eq!(
+                cache.ignore(Path::new(path)),
+                set.ignore(Path::new(path)),
+                "{path}"
+            );
+        }
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.cached(Path::new("src")), Some(None));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn dir_regexes() {
+        use super::is_dir_regex;
+
+        assert!(is_dir_regex("^(?:.*/)?logs/.*$"));
+        assert!(is_dir_regex("(?:^|/)a(?:/.*)?$"));
+        assert!(is_dir_regex("^[|]/.*$"));
+        assert!(!is_dir_regex("^a|b/.*$"));
+        assert!(!is_dir_regex("^a\\/.*$"));
+        assert!(!is_dir_regex("^a/.*"));
+        assert!(!is_dir_regex("^a\\.[/.*$"));
+    }
+
+    #[test]
+    fn cache() {
+        let set = FilterSet::allowlist()
+            .with_allow(PathFilter::new_prefixes(["src"]))
+            .with_ignore(PathFilter::new_prefixes(["src/generated"]));
+        let mut cache = DirCache::new(&set);
+        assert!(!cache.ignore(Path::new("src/lib.rs")));
+        assert!(cache.ignore(Path::new("src/generated/a.rs")));
+        assert!(cache.ignore(Path::new("README.md")));
+        assert_eq!(cache.cached(Path::new("src")), Some(None));
+        assert_eq!(cache.cached(Path::new("src/generated")), Some(Some(true)));
+        assert_eq!(cache.len(), 2);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(std::ptr::eq(cache.set(), &set));
+    }
+}
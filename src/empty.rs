@@ -0,0 +1,141 @@
+use crate::{content::io_error_policy_methods, IgnorePath, IoErrorPolicy};
+use std::{fs, io, path::Path};
+
+/// A filter that matches files with a size of zero bytes.
+///
+/// The size is read with [`fs::metadata`], so symlinks are followed. Directories
+/// are never matched, see [`EmptyDirFilter`] for those.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{EmptyFileFilter, IgnorePath};
+///
+/// let filter = EmptyFileFilter::new();
+/// assert!(filter.ignore("logs/empty.log"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EmptyFileFilter {
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for EmptyFileFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+impl EmptyFileFilter {
+    /// Creates a new filter for empty files that keeps paths it cannot check.
+    pub fn new() -> Self {
+        EmptyFileFilter::default()
+    }
+
+    io_error_policy_methods!(
+        "paths whose metadata cannot be read",
+        "e.g. because they do not exist"
+    );
+
+    /// Returns whether the path is an empty file.
+    ///
+    /// # Errors
+    /// If the metadata cannot be read, an error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let metadata = fs::metadata(path)?;
+        Ok(metadata.is_file() && metadata.len() == 0)
+    }
+}
+
+/// A filter that matches directories without any entries.
+///
+/// Only the directory itself is read, so a directory that only contains empty
+/// directories is not empty. Symlinks are followed. Files are never matched, see
+/// [`EmptyFileFilter`] for those.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{EmptyDirFilter, IgnorePath};
+///
+/// let filter = EmptyDirFilter::new();
+/// assert!(filter.ignore("build/unused"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EmptyDirFilter {
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for EmptyDirFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+impl EmptyDirFilter {
+    /// Creates a new filter for empty directories that keeps paths it cannot check.
+    pub fn new() -> Self {
+        EmptyDirFilter::default()
+    }
+
+    io_error_policy_methods!(
+        "paths that cannot be read",
+        "e.g. because they do not exist"
+    );
+
+    /// Returns whether the path is an empty directory.
+    ///
+    /// # Errors
+    /// If the metadata or the directory cannot be read, an error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let path = path.as_ref();
+        if !fs::metadata(path)?.is_dir() {
+            return Ok(false);
+        }
+
+        Ok(fs::read_dir(path)?.next().transpose()?.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        content::tests::TempDir, EmptyDirFilter, EmptyFileFilter, IgnorePath, IoErrorPolicy,
+    };
+    use std::fs;
+
+    #[test]
+    fn empty_files() {
+        let dir = TempDir::new();
+        let empty = dir.write("empty", "");
+        let full = dir.write("full", "a");
+
+        let filter = EmptyFileFilter::new();
+        assert!(filter.ignore(&empty));
+        assert!(!filter.ignore(&full));
+        assert!(!filter.ignore(dir.path()));
+
+        let missing = dir.path().join("missing");
+        assert!(!filter.ignore(&missing));
+        assert!(filter
+            .with_io_error_policy(IoErrorPolicy::Ignore)
+            .ignore(&missing));
+    }
+
+    #[test]
+    fn empty_dirs() {
+        let dir = TempDir::new();
+        let outer = dir.path().join("outer");
+        let inner = outer.join("inner");
+        fs::create_dir_all(&inner).unwrap();
+        let file = dir.write("outer/file", "");
+
+        let filter = EmptyDirFilter::new();
+        assert!(filter.ignore(&inner));
+        assert!(!filter.ignore(&outer));
+        assert!(!filter.ignore(&file));
+
+        let missing = dir.path().join("missing");
+        assert!(!filter.ignore(&missing));
+        assert!(filter
+            .with_io_error_policy(IoErrorPolicy::Ignore)
+            .ignore(&missing));
+    }
+}
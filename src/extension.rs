@@ -1,21 +1,52 @@
-use crate::IgnorePath;
-use std::{collections::HashSet, ffi::OsString, path::Path};
+use crate::{options::options_methods, IgnorePath, MatchOptions, MatchOverrides};
+use std::{
+    collections::HashSet,
+    ffi::{OsStr, OsString},
+    path::Path,
+};
 
 /// A filter that matches paths based on their extension.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExtensionFilter {
     extension: OsString,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOptions::is_default")
+    )]
+    options: MatchOptions,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOverrides::is_empty")
+    )]
+    overrides: MatchOverrides,
 }
 
 impl IgnorePath for ExtensionFilter {
     fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
         path.as_ref()
             .extension()
-            .map_or(false, |ext| ext == self.extension)
+            .is_some_and(|ext| name_eq(ext, &self.extension, self.options()))
     }
 }
 
+pub(crate) fn name_eq(ext: &OsStr, other: &OsStr, options: MatchOptions) -> bool {
+    if !options.is_case_insensitive() {
+        return ext == other;
+    }
+    match (options.is_unicode_case(), ext.to_str(), other.to_str()) {
+        (true, Some(ext), Some(other)) => unicode_fold(ext).eq(unicode_fold(other)),
+        _ => ext.eq_ignore_ascii_case(other),
+    }
+}
+
+/// Folds text for case-insensitive comparisons, see [`MatchOptions::unicode_case`].
+pub(crate) fn unicode_fold(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.chars()
+        .flat_map(char::to_uppercase)
+        .flat_map(char::to_lowercase)
+}
+
 impl ExtensionFilter {
     /// Creates a new extension filter for a string containing an extension.
     ///
@@ -34,22 +65,116 @@ impl ExtensionFilter {
     pub fn new<S: AsRef<str>>(extension: S) -> Self {
         ExtensionFilter {
             extension: extension.as_ref().trim_start_matches('.').into(),
+            options: MatchOptions::new(),
+            overrides: MatchOverrides::new(),
+        }
+    }
+
+    /// Creates a new extension filter for an extension that might not be valid UTF-8.
+    ///
+    /// A leading `.` is removed the same way as in [`new`](Self::new) if the
+    /// extension is valid UTF-8; other extensions are used as they are.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::ExtensionFilter;
+    /// use pathfilter::IgnorePath;
+    /// use std::ffi::OsStr;
+    /// use std::path::Path;
+    ///
+    /// let filter = ExtensionFilter::from_os_str(OsStr::new(".rs"));
+    /// assert!(filter.ignore(Path::new("src/lib.rs")));
+    ///
+    /// ```
+    pub fn from_os_str(extension: &OsStr) -> Self {
+        match extension.to_str() {
+            Some(extension) => ExtensionFilter::new(extension),
+            None => ExtensionFilter {
+                extension: extension.to_os_string(),
+                options: MatchOptions::new(),
+                overrides: MatchOverrides::new(),
+            },
         }
     }
+
+    /// Creates a new extension filter that matches files with the same extension as the example.
+    ///
+    /// Only the last extension is used, so `archive.tar.gz` creates a filter for `gz`.
+    /// Returns `None` if the example has no extension.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::ExtensionFilter;
+    /// use pathfilter::IgnorePath;
+    /// use std::path::Path;
+    ///
+    /// let filter = ExtensionFilter::from_path_example("backups/archive.tar.gz").unwrap();
+    /// assert_eq!(filter.extension(), "gz");
+    /// assert!(filter.ignore(Path::new("logs.gz")));
+    /// assert!(ExtensionFilter::from_path_example("Makefile").is_none());
+    ///
+    /// ```
+    pub fn from_path_example<P: AsRef<Path>>(path: P) -> Option<Self> {
+        path.as_ref().extension().map(ExtensionFilter::from_os_str)
+    }
+
+    options_methods! {
+        ///
+        /// Case-insensitive matching compares ASCII letters only.
+        ///
+        /// # Examples
+        /// ```
+        /// use pathfilter::{ExtensionFilter, IgnorePath, MatchOptions};
+        /// use std::path::Path;
+        ///
+        /// let filter = ExtensionFilter::new(".rs").with_options(MatchOptions::new().case_insensitive(true));
+        /// assert!(filter.ignore(Path::new("src/lib.RS")));
+        /// ```
+    }
+
+    /// Returns the extension this filter matches, without a leading `.`.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::ExtensionFilter;
+    ///
+    /// let filter = ExtensionFilter::new(".rs");
+    /// assert_eq!(filter.extension(), "rs");
+    /// ```
+    pub fn extension(&self) -> &OsStr {
+        &self.extension
+    }
 }
 
 /// A filter that matches paths based on their extension. Supports multiple extensions.
+///
+/// With the `serde` feature the filter is serialized as a plain list of extensions,
+/// e.g. `[".rs", ".txt"]`, unless it has non-default options, in which case it is
+/// serialized as a map with `extensions`, `options` and `overrides` entries. Both
+/// forms can be deserialized.
 #[derive(Clone, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExtensionsFilter {
     extensions: HashSet<OsString>,
+    /// The extensions with ASCII letters lowercased, for case-insensitive matching.
+    folded: HashSet<OsString>,
+    options: MatchOptions,
+    overrides: MatchOverrides,
 }
 
 impl IgnorePath for ExtensionsFilter {
     fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
-        path.as_ref()
-            .extension()
-            .map_or(false, |ext| self.extensions.contains(ext))
+        let options = self.options();
+        path.as_ref().extension().is_some_and(|ext| {
+            if options.is_unicode_case() && options.is_case_insensitive() {
+                self.extensions
+                    .iter()
+                    .any(|other| name_eq(ext, other, options))
+            } else if options.is_case_insensitive() {
+                self.contains_folded(ext)
+            } else {
+                self.extensions.contains(ext)
+            }
+        })
     }
 }
 
@@ -74,23 +199,310 @@ impl ExtensionsFilter {
         S: AsRef<str>,
         T: AsRef<[S]>,
     {
-        ExtensionsFilter {
-            extensions: extensions
+        Self::from_set(
+            extensions
                 .as_ref()
                 .iter()
                 .map(|ext| ext.as_ref().trim_start_matches('.').to_string().into())
                 .collect(),
+            MatchOptions::new(),
+            MatchOverrides::new(),
+        )
+    }
+
+    /// Creates a new extensions filter for the extensions of example paths.
+    ///
+    /// Like [`ExtensionFilter::from_path_example`], only the last extension of each
+    /// example is used. Examples without an extension are skipped and duplicates are
+    /// only kept once. Extensions are taken as they are, so `LOG` and `log` are
+    /// different extensions unless the filter is made case-insensitive.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{ExtensionsFilter, IgnorePath};
+    /// use std::path::Path;
+    ///
+    /// let filter = ExtensionsFilter::from_examples(["src/lib.rs", "Cargo.toml", "src/main.rs", "LICENSE"]);
+    /// let mut extensions: Vec<_> = filter.extensions().collect();
+    /// extensions.sort();
+    /// assert_eq!(extensions, ["rs", "toml"]);
+    /// assert!(filter.ignore(Path::new("build.rs")));
+    /// ```
+    pub fn from_examples<I, P>(examples: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        Self::from_set(
+            examples
+                .into_iter()
+                .filter_map(|path| path.as_ref().extension().map(OsStr::to_os_string))
+                .collect(),
+            MatchOptions::new(),
+            MatchOverrides::new(),
+        )
+    }
+
+    pub(crate) fn from_set(
+        extensions: HashSet<OsString>,
+        options: MatchOptions,
+        overrides: MatchOverrides,
+    ) -> Self {
+        ExtensionsFilter {
+            folded: extensions
+                .iter()
+                .map(|ext| ext.to_ascii_lowercase())
+                .collect(),
+            extensions,
+            options,
+            overrides,
+        }
+    }
+
+    /// Returns `true` if the extension is in the set, ignoring ASCII case.
+    ///
+    /// Short UTF-8 extensions are folded on the stack, only others allocate.
+    fn contains_folded(&self, ext: &OsStr) -> bool {
+        const MAX_LEN: usize = 32;
+
+        match ext.to_str() {
+            Some(ext) if ext.len() <= MAX_LEN => {
+                let mut buf = [0; MAX_LEN];
+                let buf = &mut buf[..ext.len()];
+                buf.copy_from_slice(ext.as_bytes());
+                buf.make_ascii_lowercase();
+                std::str::from_utf8(buf)
+                    .is_ok_and(|folded| self.folded.contains(OsStr::new(folded)))
+            }
+            _ => self.folded.contains(&ext.to_ascii_lowercase()),
         }
     }
 
+    options_methods! {
+        ///
+        /// Case-insensitive matching compares ASCII letters only.
+        ///
+        /// # Examples
+        /// ```
+        /// use pathfilter::{ExtensionsFilter, IgnorePath, MatchOptions};
+        /// use std::path::Path;
+        ///
+        /// let filter = ExtensionsFilter::new([".rs", ".txt"])
+        ///     .with_options(MatchOptions::new().case_insensitive(true));
+        /// assert!(filter.ignore(Path::new("NOTES.TXT")));
+        /// ```
+    }
+
+    /// Returns an iterator over the extensions this filter matches, without a leading `.`.
+    ///
+    /// The order of the extensions is unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::ExtensionsFilter;
+    ///
+    /// let filter = ExtensionsFilter::new([".rs", ".txt"]);
+    /// let mut extensions: Vec<_> = filter.extensions().collect();
+    /// extensions.sort();
+    /// assert_eq!(extensions, ["rs", "txt"]);
+    /// ```
+    pub fn extensions(&self) -> impl Iterator<Item = &OsStr> {
+        self.extensions.iter().map(OsString::as_os_str)
+    }
+
     /// Adds an extension to the filter.
     pub fn with_extension(mut self, extension: &str) -> Self {
-        self.extensions
-            .insert(extension.trim_start_matches('.').into());
+        let extension = OsString::from(extension.trim_start_matches('.'));
+        self.folded.insert(extension.to_ascii_lowercase());
+        self.extensions.insert(extension);
         self
     }
 }
 
+#[cfg(feature = "serde")]
+mod extensions_serde {
+    use super::ExtensionsFilter;
+    use crate::{MatchOptions, MatchOverrides};
+    use serde::{
+        de::{value::MapAccessDeserializer, MapAccess, SeqAccess, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+    use std::{ffi::OsString, fmt};
+
+    #[derive(Serialize, Deserialize)]
+    struct ExtensionsFilterDef<E> {
+        extensions: Vec<E>,
+        #[serde(default, skip_serializing_if = "MatchOptions::is_default")]
+        options: MatchOptions,
+        #[serde(default, skip_serializing_if = "MatchOverrides::is_empty")]
+        overrides: MatchOverrides,
+    }
+
+    /// An extension as a string, or in the serde representation of `OsString` used
+    /// by earlier versions.
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Extension {
+        Str(String),
+        Os(OsString),
+    }
+
+    impl From<Extension> for OsString {
+        fn from(value: Extension) -> Self {
+            match value {
+                Extension::Str(s) => s.trim_start_matches('.').into(),
+                Extension::Os(s) => s,
+            }
+        }
+    }
+
+    impl Serialize for ExtensionsFilter {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let Some(mut extensions) = self
+                .extensions()
+                .map(|ext| ext.to_str().map(|ext| format!(".{ext}")))
+                .collect::<Option<Vec<_>>>()
+            else {
+                // Extensions that are not valid UTF-8 keep the `OsString` form.
+                let mut extensions: Vec<_> = self.extensions().collect();
+                extensions.sort();
+                return ExtensionsFilterDef {
+                    extensions,
+                    options: self.options,
+                    overrides: self.overrides,
+                }
+                .serialize(serializer);
+            };
+            extensions.sort();
+
+            if self.options.is_default() && self.overrides.is_empty() {
+                extensions.serialize(serializer)
+            } else {
+                ExtensionsFilterDef {
+                    extensions,
+                    options: self.options,
+                    overrides: self.overrides,
+                }
+                .serialize(serializer)
+            }
+        }
+    }
+
+    /// Deserializes either a list of extensions or the map form.
+    struct ExtensionsFilterVisitor;
+
+    impl<'de> Visitor<'de> for ExtensionsFilterVisitor {
+        type Value = ExtensionsFilterDef<Extension>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a list of extensions or a map with `extensions`")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut extensions = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(extension) = seq.next_element()? {
+                extensions.push(extension);
+            }
+            Ok(ExtensionsFilterDef {
+                extensions,
+                options: MatchOptions::new(),
+                overrides: MatchOverrides::new(),
+            })
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+            ExtensionsFilterDef::deserialize(MapAccessDeserializer::new(map))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ExtensionsFilter {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let def = deserializer.deserialize_any(ExtensionsFilterVisitor)?;
+            Ok(ExtensionsFilter::from_set(
+                def.extensions.into_iter().map(OsString::from).collect(),
+                def.options,
+                def.overrides,
+            ))
+        }
+    }
+}
+
+/// A filter that matches paths based on a static list of extensions.
+///
+/// Unlike [`ExtensionsFilter`] it can be constructed in a `const` context, so it can
+/// be defined in a `static` without allocating or lazy initialization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StaticExtensionsFilter {
+    extensions: &'static [&'static str],
+    options: MatchOptions,
+}
+
+impl IgnorePath for StaticExtensionsFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        path.as_ref().extension().is_some_and(|ext| {
+            self.extensions()
+                .any(|other| name_eq(ext, OsStr::new(other), self.options))
+        })
+    }
+}
+
+impl StaticExtensionsFilter {
+    /// Creates a new extensions filter for a static list of extensions.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::StaticExtensionsFilter;
+    /// use pathfilter::IgnorePath;
+    /// use std::path::Path;
+    ///
+    /// static FILTER: StaticExtensionsFilter = StaticExtensionsFilter::new(&[".rs", ".txt"]);
+    /// assert!(FILTER.ignore(Path::new("src/lib.rs")));
+    /// assert!(FILTER.ignore(Path::new("src/main.txt")));
+    /// assert!(!FILTER.ignore(Path::new("src/main.png")));
+    ///
+    /// ```
+    pub const fn new(extensions: &'static [&'static str]) -> Self {
+        StaticExtensionsFilter {
+            extensions,
+            options: MatchOptions::new(),
+        }
+    }
+
+    /// Sets the options used for matching.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{IgnorePath, MatchOptions, StaticExtensionsFilter};
+    /// use std::path::Path;
+    ///
+    /// const FILTER: StaticExtensionsFilter = StaticExtensionsFilter::new(&["png"])
+    ///     .with_options(MatchOptions::new().case_insensitive(true));
+    /// assert!(FILTER.ignore(Path::new("IMAGE.PNG")));
+    /// ```
+    pub const fn with_options(mut self, options: MatchOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the options used for matching.
+    pub const fn options(&self) -> MatchOptions {
+        self.options
+    }
+
+    /// Returns an iterator over the extensions this filter matches, without a leading `.`.
+    pub fn extensions(&self) -> impl Iterator<Item = &'static str> {
+        self.extensions
+            .iter()
+            .map(|ext| ext.trim_start_matches('.'))
+    }
+}
+
+impl From<StaticExtensionsFilter> for ExtensionsFilter {
+    fn from(value: StaticExtensionsFilter) -> Self {
+        ExtensionsFilter::new(value.extensions).with_options(value.options)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
@@ -109,10 +521,221 @@ mod tests {
     fn extensions_filter() {
         use crate::{extension::ExtensionsFilter, IgnorePath};
 
+        // Passing a reference to an array must keep compiling.
+        #[allow(clippy::needless_borrows_for_generic_args)]
         let filter = ExtensionsFilter::new(&[".rs", ".txt"]);
         assert!(filter.ignore(Path::new("src/lib.rs")));
         assert!(filter.ignore(Path::new("src/main.rs")));
         assert!(filter.ignore(Path::new("src/main.txt")));
         assert!(!filter.ignore(Path::new("src/main.png")));
+
+        let filter = ExtensionsFilter::new([".rs", ".txt"]);
+        assert!(filter.ignore(Path::new("src/lib.rs")));
+        assert!(!filter.ignore(Path::new("src/main.png")));
+    }
+
+    #[test]
+    fn case_insensitive() {
+        use crate::{
+            extension::{ExtensionFilter, ExtensionsFilter},
+            IgnorePath, MatchOptions,
+        };
+
+        let options = MatchOptions::new().case_insensitive(true);
+
+        let filter = ExtensionFilter::new(".rs");
+        assert!(!filter.ignore(Path::new("src/lib.RS")));
+        let filter = filter.with_options(options);
+        assert!(filter.ignore(Path::new("src/lib.RS")));
+        assert!(filter.ignore(Path::new("src/lib.rs")));
+        assert!(!filter.ignore(Path::new("src/lib.rsx")));
+
+        let filter = ExtensionsFilter::new([".rs", ".TXT"]);
+        assert!(!filter.ignore(Path::new("notes.txt")));
+        let filter = filter.with_options(options);
+        assert!(filter.ignore(Path::new("notes.txt")));
+        assert!(filter.ignore(Path::new("src/lib.Rs")));
+        assert!(!filter.ignore(Path::new("image.png")));
+
+        let long = "X".repeat(40);
+        let filter = filter.with_extension(&long.to_lowercase());
+        assert!(filter.ignore(Path::new(&format!("file.{long}"))));
+        assert!(filter.ignore(Path::new("notes.Ä.txt")));
+        assert!(!filter.ignore(Path::new("notes.tXtä")));
+
+        let unicode = options.unicode_case(true);
+        let filter = ExtensionsFilter::new([".straße", ".é"]);
+        assert!(!filter.clone().with_options(options).ignore("a.STRASSE"));
+        let filter = filter.with_options(unicode);
+        assert!(filter.ignore("a.STRASSE"));
+        assert!(filter.ignore("a.É"));
+        assert!(!filter.ignore("a.e"));
+        assert!(ExtensionFilter::new(".Übung")
+            .with_options(unicode)
+            .ignore("a.üBUNG"));
+    }
+
+    #[test]
+    fn extension_filter_from_examples() {
+        use crate::{
+            extension::{ExtensionFilter, ExtensionsFilter},
+            IgnorePath,
+        };
+        use std::ffi::OsStr;
+
+        let filter = ExtensionFilter::from_os_str(OsStr::new("rs"));
+        assert_eq!(filter, ExtensionFilter::new(".rs"));
+
+        let filter = ExtensionFilter::from_path_example("src/lib.rs").unwrap();
+        assert_eq!(filter, ExtensionFilter::new(".rs"));
+        assert!(filter.ignore(Path::new("src/main.rs")));
+
+        assert!(ExtensionFilter::from_path_example("a.tar.gz")
+            .unwrap()
+            .ignore("b.gz"));
+        assert!(ExtensionFilter::from_path_example("Makefile").is_none());
+        assert!(ExtensionFilter::from_path_example(".gitignore").is_none());
+
+        let filter =
+            ExtensionsFilter::from_examples(["a.tar.gz", "b.gz", ".gitignore", "c.TXT", "d.txt"]);
+        assert_eq!(filter.extensions().count(), 3);
+        assert!(filter.ignore("e.gz"));
+        assert!(!filter.ignore("f.tar"));
+        assert!(ExtensionsFilter::from_examples(Vec::<&str>::new())
+            .extensions()
+            .next()
+            .is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extension_filter_from_non_utf8() {
+        use crate::{extension::ExtensionFilter, IgnorePath};
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let extension = OsStr::from_bytes(b"r\xffs");
+        let filter = ExtensionFilter::from_os_str(extension);
+        assert_eq!(filter.extension(), extension);
+        assert!(filter.ignore(Path::new(OsStr::from_bytes(b"src/lib.r\xffs"))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_extensions_filter() {
+        use crate::{extension::ExtensionsFilter, value::Value, IgnorePath};
+        use serde::{
+            de::{value::Error, IntoDeserializer},
+            Deserialize,
+        };
+
+        let str = |s: &str| Value::Str(s.to_string());
+        let deserialize = |value: Value| {
+            ExtensionsFilter::deserialize(IntoDeserializer::<Error>::into_deserializer(value))
+        };
+
+        let filter = deserialize(Value::Seq(vec![str(".rs"), str("txt")])).unwrap();
+        assert_eq!(filter, ExtensionsFilter::new([".rs", ".txt"]));
+
+        let filter = deserialize(Value::Map(vec![
+            (str("extensions"), Value::Seq(vec![str(".rs")])),
+            (
+                str("options"),
+                Value::Map(vec![(str("case_insensitive"), Value::Bool(true))]),
+            ),
+        ]))
+        .unwrap();
+        assert!(filter.ignore(Path::new("src/lib.RS")));
+
+        let os_str = if cfg!(windows) {
+            Value::Map(vec![(
+                str("Windows"),
+                Value::Seq("md".encode_utf16().map(|c| Value::U64(c.into())).collect()),
+            )])
+        } else {
+            Value::Map(vec![(
+                str("Unix"),
+                Value::Seq("md".bytes().map(|b| Value::U64(b.into())).collect()),
+            )])
+        };
+        let legacy = Value::Map(vec![(str("extensions"), Value::Seq(vec![os_str]))]);
+        assert_eq!(deserialize(legacy).unwrap(), ExtensionsFilter::new(["md"]));
+
+        assert_eq!(
+            deserialize(str(".rs")).unwrap_err().to_string(),
+            "invalid type: string \".rs\", expected a list of extensions or a map with `extensions`"
+        );
+        let err = deserialize(Value::Map(vec![(str("extensions"), Value::U64(5))])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: integer `5`, expected a sequence"
+        );
+    }
+
+    #[cfg(all(feature = "serde", unix))]
+    #[test]
+    fn serialize_non_utf8_extensions() {
+        use crate::{
+            extension::ExtensionsFilter,
+            value::{to_value, Value},
+            MatchOptions, MatchOverrides,
+        };
+        use serde::{
+            de::{value::Error, IntoDeserializer},
+            Deserialize,
+        };
+        use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+        let extension = OsString::from_vec(b"r\xffs".to_vec());
+        let filter = ExtensionsFilter::from_set(
+            [extension.clone()].into(),
+            MatchOptions::new(),
+            MatchOverrides::new(),
+        );
+        let value = to_value(&filter).unwrap();
+        let bytes = b"r\xffs".iter().map(|&b| Value::U64(b.into())).collect();
+        assert_eq!(
+            value,
+            Value::Map(vec![(
+                Value::Str("extensions".to_string()),
+                Value::Seq(vec![Value::Map(vec![(
+                    Value::Str("Unix".to_string()),
+                    Value::Seq(bytes)
+                )])])
+            )])
+        );
+        let restored =
+            ExtensionsFilter::deserialize(IntoDeserializer::<Error>::into_deserializer(value))
+                .unwrap();
+        assert_eq!(restored, filter);
+    }
+
+    #[test]
+    fn static_extensions_filter() {
+        use crate::{extension::StaticExtensionsFilter, ExtensionsFilter, IgnorePath};
+
+        static FILTER: StaticExtensionsFilter = StaticExtensionsFilter::new(&[".rs", "txt"]);
+        assert!(FILTER.ignore(Path::new("src/lib.rs")));
+        assert!(FILTER.ignore(Path::new("notes.txt")));
+        assert!(!FILTER.ignore(Path::new("notes.TXT")));
+        assert!(!FILTER.ignore(Path::new("Makefile")));
+        assert_eq!(FILTER.extensions().collect::<Vec<_>>(), ["rs", "txt"]);
+
+        let owned = ExtensionsFilter::from(FILTER);
+        assert!(owned.ignore(Path::new("src/lib.rs")));
+        assert!(owned.ignore(Path::new("notes.txt")));
+    }
+
+    #[test]
+    fn overrides() {
+        use crate::{extension::ExtensionFilter, IgnorePath, MatchOptions, MatchOverrides};
+
+        let filter = ExtensionFilter::new(".rs")
+            .with_overrides(MatchOverrides::new().case_insensitive(false))
+            .with_options(MatchOptions::new().case_insensitive(true));
+        assert!(!filter.options().is_case_insensitive());
+        assert!(!filter.ignore(Path::new("src/lib.RS")));
+
+        let filter = filter.with_overrides(MatchOverrides::new());
+        assert!(filter.ignore(Path::new("src/lib.RS")));
     }
 }
@@ -0,0 +1,114 @@
+use crate::{
+    names::{self, NameSet},
+    options::options_methods,
+    IgnorePath, MatchCandidate, MatchOptions, MatchOverrides,
+};
+use std::path::Path;
+
+/// A filter that matches paths whose file name is one of a set of names.
+///
+/// Only the last component of the path is compared, so `Cargo.lock` matches
+/// `Cargo.lock` and `crates/a/Cargo.lock` but not `Cargo.lock/x` or `Cargo.lock.bak`.
+/// File names that are not valid UTF-8 never match. With
+/// [`MatchOptions::normalize_separators`], the name starts after the last `\` too.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{FileNameFilter, IgnorePath};
+/// use std::path::Path;
+///
+/// let filter = FileNameFilter::new(["Thumbs.db", "desktop.ini", ".DS_Store"]);
+/// assert!(filter.ignore(Path::new("photos/2024/Thumbs.db")));
+/// assert!(filter.ignore(Path::new(".DS_Store")));
+/// assert!(!filter.ignore(Path::new("photos/Thumbs.db.bak")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileNameFilter {
+    names: NameSet,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOptions::is_default")
+    )]
+    options: MatchOptions,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOverrides::is_empty")
+    )]
+    overrides: MatchOverrides,
+}
+
+impl IgnorePath for FileNameFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        candidate
+            .path()
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                let options = self.options();
+                let name = names::file_name(name, options);
+                candidate.with_buffer(|buffer| self.names.contains(name, options, buffer))
+            })
+    }
+}
+
+impl FileNameFilter {
+    /// Creates a new filter for a list of file names.
+    pub fn new<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        FileNameFilter {
+            names: names.into_iter().collect(),
+            options: MatchOptions::new(),
+            overrides: MatchOverrides::new(),
+        }
+    }
+
+    /// Adds a file name to the filter.
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.names.insert(name.into());
+        self
+    }
+
+    /// Returns the file names this filter matches, in unspecified order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter()
+    }
+
+    options_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FileNameFilter, IgnorePath, MatchOptions};
+    use std::path::Path;
+
+    #[test]
+    fn names() {
+        let filter = FileNameFilter::new(["Cargo.lock"]).with_name("desktop.ini");
+        assert!(filter.ignore(Path::new("Cargo.lock")));
+        assert!(filter.ignore(Path::new("/home/me/crates/a/Cargo.lock")));
+        assert!(filter.ignore(Path::new("docs/desktop.ini/")));
+        assert!(!filter.ignore(Path::new("Cargo.lock/x")));
+        assert!(!filter.ignore(Path::new("Cargo.lock.bak")));
+        assert!(!filter.ignore(Path::new("Desktop.ini")));
+        assert!(!filter.ignore(Path::new("..")));
+
+        let filter = filter.with_options(MatchOptions::new().case_insensitive(true));
+        assert!(filter.ignore(Path::new("Desktop.INI")));
+
+        let filter = filter.with_options(MatchOptions::new().normalize_separators(true));
+        assert!(filter.ignore(Path::new("photos\\desktop.ini")));
+        assert!(!filter.ignore(Path::new("desktop.ini\\x")));
+
+        let mut names: Vec<_> = filter.names().collect();
+        names.sort();
+        assert_eq!(names, ["Cargo.lock", "desktop.ini"]);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{content::io_error_policy_methods, IgnorePath, IoErrorPolicy};
+use std::{fs, io, path::Path};
+
+/// A filter that matches paths by the type of the entry: file, directory or symlink.
+///
+/// The type is read with [`fs::symlink_metadata`], so symlinks are not followed
+/// and a symlink to a directory is a symlink, not a directory. Other entries, like
+/// sockets or named pipes, are never matched. Paths whose metadata cannot be read,
+/// including paths that do not exist, are handled by the [`IoErrorPolicy`] and
+/// kept by default.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{FileTypeFilter, IgnorePath};
+///
+/// let filter = FileTypeFilter::symlinks();
+/// assert!(filter.ignore("target/current"));
+///
+/// let filter = FileTypeFilter::directories().with_symlinks(true);
+/// assert!(filter.ignore("target"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FileTypeFilter {
+    files: bool,
+    directories: bool,
+    symlinks: bool,
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for FileTypeFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+impl FileTypeFilter {
+    /// Creates a new filter that matches nothing, to be extended with the `with_*` methods.
+    pub fn new() -> Self {
+        FileTypeFilter::default()
+    }
+
+    /// Creates a new filter for regular files.
+    pub fn files() -> Self {
+        FileTypeFilter::new().with_files(true)
+    }
+
+    /// Creates a new filter for directories.
+    pub fn directories() -> Self {
+        FileTypeFilter::new().with_directories(true)
+    }
+
+    /// Creates a new filter for symlinks.
+    pub fn symlinks() -> Self {
+        FileTypeFilter::new().with_symlinks(true)
+    }
+
+    /// Sets whether regular files are matched.
+    pub fn with_files(mut self, files: bool) -> Self {
+        self.files = files;
+        self
+    }
+
+    /// Sets whether directories are matched.
+    pub fn with_directories(mut self, directories: bool) -> Self {
+        self.directories = directories;
+        self
+    }
+
+    /// Sets whether symlinks are matched.
+    pub fn with_symlinks(mut self, symlinks: bool) -> Self {
+        self.symlinks = symlinks;
+        self
+    }
+
+    io_error_policy_methods!("paths whose metadata cannot be read");
+
+    /// Returns whether an entry of this type is matched.
+    pub fn matches_type(&self, file_type: fs::FileType) -> bool {
+        (self.files && file_type.is_file())
+            || (self.directories && file_type.is_dir())
+            || (self.symlinks && file_type.is_symlink())
+    }
+
+    /// Returns whether the path is an entry of a matching type.
+    ///
+    /// # Errors
+    /// If the metadata cannot be read, e.g. because the path does not exist, an
+    /// error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        Ok(self.matches_type(fs::symlink_metadata(path)?.file_type()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{content::tests::TempDir, FileTypeFilter, IgnorePath, IoErrorPolicy};
+
+    #[test]
+    fn file_type_filter() {
+        let dir = TempDir::new();
+        let file = dir.write("file", "");
+        let sub = dir.path().join("sub");
+        std::fs::create_dir(&sub).unwrap();
+
+        assert!(FileTypeFilter::files().ignore(&file));
+        assert!(!FileTypeFilter::files().ignore(&sub));
+        assert!(FileTypeFilter::directories().ignore(&sub));
+        assert!(!FileTypeFilter::directories().ignore(&file));
+        assert!(!FileTypeFilter::symlinks().ignore(&file));
+        assert!(!FileTypeFilter::new().ignore(&file));
+
+        #[cfg(unix)]
+        {
+            let link = dir.path().join("link");
+            std::os::unix::fs::symlink(&sub, &link).unwrap();
+            assert!(FileTypeFilter::symlinks().ignore(&link));
+            assert!(!FileTypeFilter::directories().ignore(&link));
+            assert!(FileTypeFilter::directories()
+                .with_symlinks(true)
+                .ignore(&link));
+        }
+
+        let missing = dir.path().join("missing");
+        assert!(!FileTypeFilter::files().ignore(&missing));
+        assert!(FileTypeFilter::files()
+            .with_io_error_policy(IoErrorPolicy::Ignore)
+            .ignore(&missing));
+    }
+}
@@ -0,0 +1,444 @@
+use crate::{FilterSetBuilder, IgnorePath, MatchCandidate, MatchOptions, PathFilter};
+use std::path::Path;
+
+/// A rule of a [`FilterSet`]: a filter and what happens to the paths it matches.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FilterRule<F = PathFilter> {
+    /// Paths matched by the filter are ignored.
+    #[cfg_attr(feature = "serde", serde(alias = "ignore"))]
+    Ignore(F),
+    /// Paths matched by the filter are kept, even if an earlier rule ignores them.
+    #[cfg_attr(feature = "serde", serde(alias = "allow"))]
+    Allow(F),
+}
+
+impl<F> FilterRule<F> {
+    /// Returns the filter of the rule.
+    pub fn filter(&self) -> &F {
+        match self {
+            FilterRule::Ignore(filter) | FilterRule::Allow(filter) => filter,
+        }
+    }
+
+    /// Returns `true` if the rule ignores the paths it matches.
+    pub fn is_ignore(&self) -> bool {
+        matches!(self, FilterRule::Ignore(_))
+    }
+
+    fn filter_mut(&mut self) -> &mut F {
+        match self {
+            FilterRule::Ignore(filter) | FilterRule::Allow(filter) => filter,
+        }
+    }
+}
+
+/// An ordered list of rules where the last matching rule decides, like in a `.gitignore`.
+///
+/// A path is ignored if the last rule whose filter matches it is an
+/// [`Ignore`](FilterRule::Ignore) rule; paths no rule matches are kept, unless
+/// the set is an [`allowlist`](Self::allowlist), which ignores them. Unlike a
+/// plain list of filters, where any filter ignoring a path wins, an
+/// [`Allow`](FilterRule::Allow) rule can take back an earlier ignore. This is the
+/// recommended type for a complete set of rules, e.g. loaded from a config.
+///
+/// With the `serde` feature the set is serialized as its list of rules, each a
+/// map from `Ignore` or `Allow` to the filter. An allowlist is serialized as a
+/// map with the `rules` and `ignore_unmatched: true`.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{FilterSet, IgnorePath, PathFilter};
+/// use std::path::Path;
+///
+/// let mut set = FilterSet::new();
+/// set.add_ignore(PathFilter::new_extension(".log"));
+/// set.add_allow(PathFilter::new_file_names(["important.log"]));
+/// assert!(set.ignore(Path::new("logs/debug.log")));
+/// assert!(!set.ignore(Path::new("logs/important.log")));
+/// assert!(!set.ignore(Path::new("src/lib.rs")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FilterSet<F = PathFilter> {
+    rules: Vec<FilterRule<F>>,
+    ignore_unmatched: bool,
+}
+
+impl<F> Default for FilterSet<F> {
+    fn default() -> Self {
+        FilterSet {
+            rules: Vec::new(),
+            ignore_unmatched: false,
+        }
+    }
+}
+
+impl<F: IgnorePath> IgnorePath for FilterSet<F> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        self.decisive_rule(candidate)
+            .map_or(self.ignore_unmatched, FilterRule::is_ignore)
+    }
+}
+
+impl<F> FilterSet<F> {
+    /// Creates an empty set, which ignores nothing.
+    pub fn new() -> Self {
+        FilterSet::default()
+    }
+
+    /// Creates an empty set that ignores the paths no rule matches, so only the
+    /// paths of [`Allow`](FilterRule::Allow) rules are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{FilterSet, IgnorePath, PathFilter};
+    /// use std::path::Path;
+    ///
+    /// let set = FilterSet::allowlist()
+    ///     .with_allow(PathFilter::new_extension(".rs"))
+    ///     .with_ignore(PathFilter::new_prefixes(["target"]));
+    /// assert!(!set.ignore(Path::new("src/lib.rs")));
+    /// assert!(set.ignore(Path::new("target/build.rs")));
+    /// assert!(set.ignore(Path::new("README.md")));
+    /// ```
+    pub fn allowlist() -> Self {
+        FilterSet::new().with_ignore_unmatched(true)
+    }
+
+    /// Sets whether the paths no rule matches are ignored.
+    pub fn with_ignore_unmatched(mut self, ignore_unmatched: bool) -> Self {
+        self.set_ignore_unmatched(ignore_unmatched);
+        self
+    }
+
+    /// Sets whether the paths no rule matches are ignored.
+    pub fn set_ignore_unmatched(&mut self, ignore_unmatched: bool) {
+        self.ignore_unmatched = ignore_unmatched;
+    }
+
+    /// Returns `true` if the paths no rule matches are ignored.
+    pub fn ignores_unmatched(&self) -> bool {
+        self.ignore_unmatched
+    }
+
+    /// Appends a rule that ignores the paths `filter` matches.
+    pub fn add_ignore(&mut self, filter: F) {
+        self.rules.push(FilterRule::Ignore(filter));
+    }
+
+    /// Appends a rule that keeps the paths `filter` matches.
+    pub fn add_allow(&mut self, filter: F) {
+        self.rules.push(FilterRule::Allow(filter));
+    }
+
+    /// Appends a rule.
+    pub fn add_rule(&mut self, rule: FilterRule<F>) {
+        self.rules.push(rule);
+    }
+
+    /// Appends a rule that ignores the paths `filter` matches.
+    pub fn with_ignore(mut self, filter: F) -> Self {
+        self.add_ignore(filter);
+        self
+    }
+
+    /// Appends a rule that keeps the paths `filter` matches.
+    pub fn with_allow(mut self, filter: F) -> Self {
+        self.add_allow(filter);
+        self
+    }
+
+    /// Returns the rules in the order they are evaluated.
+    pub fn rules(&self) -> &[FilterRule<F>] {
+        &self.rules
+    }
+
+    /// Returns the number of rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns `true` if the set has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+impl<F: IgnorePath> FilterSet<F> {
+    /// Returns the rule that decides about the path, i.e. the last rule matching it.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{FilterRule, FilterSet, PathFilter};
+    ///
+    /// let set = FilterSet::new()
+    ///     .with_ignore(PathFilter::new_extension(".log"))
+    ///     .with_allow(PathFilter::new_prefixes(["keep"]));
+    /// assert!(matches!(set.matching_rule("keep/a.log"), Some(FilterRule::Allow(_))));
+    /// assert!(set.matching_rule("src/lib.rs").is_none());
+    /// ```
+    pub fn matching_rule<P: AsRef<Path>>(&self, path: P) -> Option<&FilterRule<F>> {
+        self.decisive_rule(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn decisive_rule(&self, candidate: &MatchCandidate<'_>) -> Option<&FilterRule<F>> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.filter().ignore_candidate(candidate))
+    }
+}
+
+impl FilterSet<PathFilter> {
+    /// Creates a builder for a set whose rules share the same options.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::FilterSet;
+    ///
+    /// let set = FilterSet::builder().extension(".o").case_insensitive(true).build();
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    pub fn builder() -> FilterSetBuilder {
+        FilterSetBuilder::new()
+    }
+
+    /// Sets the options used for matching on the filters of all rules.
+    pub fn with_options(mut self, options: MatchOptions) -> Self {
+        self.set_options(options);
+        self
+    }
+
+    /// Sets the options used for matching on the filters of all rules.
+    pub fn set_options(&mut self, options: MatchOptions) {
+        for rule in &mut self.rules {
+            rule.filter_mut().set_options(options);
+        }
+    }
+
+    /// Converts the rules into the contents of an rsync filter file.
+    ///
+    /// Ignore rules become exclude rules `- pattern` and allow rules include rules
+    /// `+ pattern`, translated like [`rsync::to_rules`](crate::rsync::to_rules).
+    /// As rsync stops at the first matching rule, the rules are written in
+    /// reverse. rsync does not descend into excluded directories, so an allow rule
+    /// after an ignore rule for components, file names or prefixes, which rsync
+    /// also matches against directories, cannot be exported, and neither can an
+    /// [`allowlist`](Self::allowlist).
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{FilterSet, PathFilter};
+    ///
+    /// let set = FilterSet::new()
+    ///     .with_ignore(PathFilter::new_extension(".log"))
+    ///     .with_allow(PathFilter::new_file_names(["important.log"]));
+    /// assert_eq!(set.to_rsync_rules().unwrap(), "+ important.log\n- *.log\n");
+    /// ```
+    /// # Errors
+    /// If the filter of a rule has no rsync equivalent, or an allow rule would not
+    /// re-include paths inside an ignored directory, an error with the index of
+    /// that rule is returned. For an allowlist the index is the number of rules.
+    pub fn to_rsync_rules(&self) -> Result<String, crate::rsync::RsyncError> {
+        crate::rsync::set_to_rules(self)
+    }
+}
+
+/// A plain list of filters becomes a set of ignore rules, which ignores the same paths.
+impl<F> From<Vec<F>> for FilterSet<F> {
+    fn from(filters: Vec<F>) -> Self {
+        filters.into_iter().map(FilterRule::Ignore).collect()
+    }
+}
+
+impl<F> FromIterator<FilterRule<F>> for FilterSet<F> {
+    fn from_iter<T: IntoIterator<Item = FilterRule<F>>>(iter: T) -> Self {
+        FilterSet {
+            rules: iter.into_iter().collect(),
+            ignore_unmatched: false,
+        }
+    }
+}
+
+impl<F> Extend<FilterRule<F>> for FilterSet<F> {
+    fn extend<T: IntoIterator<Item = FilterRule<F>>>(&mut self, iter: T) {
+        self.rules.extend(iter);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::{FilterRule, FilterSet};
+    use serde::{
+        de::{value::MapAccessDeserializer, MapAccess, SeqAccess, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+    use std::{fmt, marker::PhantomData};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "FilterSet")]
+    struct FilterSetDef<R> {
+        rules: R,
+        #[serde(default)]
+        ignore_unmatched: bool,
+    }
+
+    impl<F: Serialize> Serialize for FilterSet<F> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if self.ignore_unmatched {
+                FilterSetDef {
+                    rules: &self.rules,
+                    ignore_unmatched: true,
+                }
+                .serialize(serializer)
+            } else {
+                self.rules.serialize(serializer)
+            }
+        }
+    }
+
+    /// Deserializes either a list of rules or the map form.
+    struct FilterSetVisitor<F>(PhantomData<F>);
+
+    impl<'de, F: Deserialize<'de>> Visitor<'de> for FilterSetVisitor<F> {
+        type Value = FilterSet<F>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a list of rules or a map with `rules`")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut rules = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(rule) = seq.next_element::<FilterRule<F>>()? {
+                rules.push(rule);
+            }
+            Ok(rules.into_iter().collect())
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+            let def =
+                FilterSetDef::<Vec<FilterRule<F>>>::deserialize(MapAccessDeserializer::new(map))?;
+            Ok(FilterSet {
+                rules: def.rules,
+                ignore_unmatched: def.ignore_unmatched,
+            })
+        }
+    }
+
+    impl<'de, F: Deserialize<'de>> Deserialize<'de> for FilterSet<F> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(FilterSetVisitor(PhantomData))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ExtensionFilter, FilterRule, FilterSet, IgnorePath, MatchOptions, PathFilter};
+    use std::path::Path;
+
+    #[test]
+    fn last_match_wins() {
+        let set = FilterSet::new()
+            .with_ignore(ExtensionFilter::new(".log"))
+            .with_allow(ExtensionFilter::new(".log"))
+            .with_ignore(ExtensionFilter::new(".tmp"));
+        assert!(!set.ignore(Path::new("a.log")));
+        assert!(set.ignore(Path::new("a.tmp")));
+        assert!(!set.ignore(Path::new("a.rs")));
+        assert_eq!(set.len(), 3);
+
+        let set: FilterSet<_> = vec![ExtensionFilter::new(".o")].into();
+        assert!(set.ignore(Path::new("main.o")));
+        assert!(matches!(set.rules(), [FilterRule::Ignore(_)]));
+        assert!(!FilterSet::<PathFilter>::new().ignore(Path::new("main.o")));
+    }
+
+    #[test]
+    fn options() {
+        let set = FilterSet::new()
+            .with_ignore(PathFilter::new_extension(".log"))
+            .with_options(MatchOptions::new().case_insensitive(true));
+        assert!(set.ignore(Path::new("A.LOG")));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use crate::value::{to_value, Value};
+        use serde::de::IntoDeserializer;
+
+        let set = FilterSet::new()
+            .with_ignore(PathFilter::new_extension(".log"))
+            .with_allow(PathFilter::new_file_names(["important.log"]));
+        let value = to_value(&set).unwrap();
+        let Value::Seq(rules) = &value else {
+            panic!("expected a sequence, got {value:?}");
+        };
+        assert!(matches!(&rules[0], Value::Map(map) if map[0].0 == Value::Str("Ignore".into())));
+
+        let deserialized: FilterSet =
+            serde::Deserialize::deserialize(
+                IntoDeserializer::<serde::de::value::Error>::into_deserializer(value.clone()),
+            )
+            .unwrap();
+        assert_eq!(to_value(&deserialized).unwrap(), value);
+        assert!(!deserialized.ignore(Path::new("important.log")));
+        assert!(deserialized.ignore(Path::new("debug.log")));
+    }
+
+    #[test]
+    fn allowlist() {
+        let include_then_exclude = FilterSet::allowlist()
+            .with_allow(PathFilter::new_extension(".rs"))
+            .with_ignore(PathFilter::new_prefixes(["target"]))
+            .with_allow(PathFilter::new_file_names(["build.rs"]));
+        assert!(include_then_exclude.ignores_unmatched());
+        assert!(!include_then_exclude.ignore(Path::new("src/lib.rs")));
+        assert!(include_then_exclude.ignore(Path::new("target/gen.rs")));
+        assert!(!include_then_exclude.ignore(Path::new("target/build.rs")));
+        assert!(include_then_exclude.ignore(Path::new("README.md")));
+
+        // The exception only applies to the rules before it.
+        let exclude_then_include = FilterSet::allowlist()
+            .with_ignore(PathFilter::new_prefixes(["target"]))
+            .with_allow(PathFilter::new_extension(".rs"));
+        assert!(!exclude_then_include.ignore(Path::new("target/gen.rs")));
+        assert!(exclude_then_include.ignore(Path::new("target/gen.o")));
+
+        assert!(FilterSet::<PathFilter>::allowlist().ignore(Path::new("a")));
+        let set = FilterSet::<PathFilter>::allowlist().with_ignore_unmatched(false);
+        assert!(!set.ignore(Path::new("a")));
+        assert!(FilterSet::allowlist().to_rsync_rules().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_allowlist() {
+        use crate::value::{to_value, Value};
+        use serde::de::IntoDeserializer;
+
+        let set = FilterSet::allowlist().with_allow(PathFilter::new_extension(".rs"));
+        let value = to_value(&set).unwrap();
+        let Value::Map(fields) = &value else {
+            panic!("expected a map, got {value:?}");
+        };
+        assert_eq!(
+            fields[1],
+            (Value::Str("ignore_unmatched".into()), Value::Bool(true))
+        );
+
+        let deserialized: FilterSet =
+            serde::Deserialize::deserialize(
+                IntoDeserializer::<serde::de::value::Error>::into_deserializer(value.clone()),
+            )
+            .unwrap();
+        assert!(deserialized.ignores_unmatched());
+        assert!(deserialized.ignore(Path::new("README.md")));
+        assert!(!deserialized.ignore(Path::new("src/lib.rs")));
+    }
+}
@@ -0,0 +1,332 @@
+use crate::{
+    glob::to_regex,
+    options::options_methods,
+    patterns::{PatternError, PatternErrorKind, PatternFileError},
+    FilterRule, FilterSet, GlobError, IgnorePath, MatchCandidate, MatchOptions, MatchOverrides,
+    RegexFilter,
+};
+use std::{fs, path::Path};
+
+/// A filter that follows the rules of a `.gitignore` file.
+///
+/// Patterns are globs as for [`GlobFilter`](crate::GlobFilter), with the
+/// semantics git gives them:
+/// - blank lines and lines starting with `#` are skipped, and trailing spaces
+///   are removed unless escaped with `\`,
+/// - a leading `!` re-includes paths an earlier pattern ignored, unless a parent
+///   directory of the path is ignored,
+/// - a trailing `/` makes a pattern only match directories,
+/// - a pattern with a `/` at its start or in its middle is anchored at the
+///   directory of the file, other patterns match at any depth,
+/// - the last matching pattern decides.
+///
+/// Paths are matched relative to the directory of the `.gitignore` file. As the
+/// filter only sees paths, a path names a directory if it ends with `/`; paths
+/// inside a directory are decided by the directory too. Like git, invalid
+/// patterns are reported as errors instead of being skipped.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{GitignoreFilter, IgnorePath};
+/// use std::path::Path;
+///
+/// let filter = GitignoreFilter::new("target/\n*.log\n!keep.log\n/Cargo.lock\n").unwrap();
+/// assert!(filter.ignore(Path::new("target/debug/main")));
+/// assert!(filter.ignore(Path::new("logs/build.log")));
+/// assert!(!filter.ignore(Path::new("logs/keep.log")));
+/// assert!(filter.ignore(Path::new("Cargo.lock")));
+/// assert!(!filter.ignore(Path::new("crates/a/Cargo.lock")));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GitignoreFilter {
+    rules: Vec<Rule>,
+    options: MatchOptions,
+    overrides: MatchOverrides,
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    pattern: String,
+    regex: regex::Regex,
+    negated: bool,
+    dir_only: bool,
+}
+
+impl IgnorePath for GitignoreFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        let Some(text) = candidate.text(&self.options()) else {
+            return false;
+        };
+        let (text, is_dir) = match text.strip_suffix('/') {
+            Some(dir) => (dir, true),
+            None => (text, false),
+        };
+
+        // A path inside an ignored directory can't be re-included.
+        text.match_indices('/')
+            .map(|(end, _)| &text[..end])
+            .filter(|dir| !dir.is_empty())
+            .any(|dir| self.decide(dir, true) == Some(true))
+            || self.decide(text, is_dir) == Some(true)
+    }
+}
+
+impl GitignoreFilter {
+    /// Parses the contents of a `.gitignore` file.
+    ///
+    /// # Errors
+    /// If a pattern is not a valid glob, an error with its location is returned.
+    pub fn new(text: &str) -> Result<Self, PatternError> {
+        let rules = text
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| Rule::parse(line, index + 1).transpose())
+            .collect::<Result<_, _>>()?;
+        Ok(GitignoreFilter {
+            rules,
+            options: MatchOptions::new(),
+            overrides: MatchOverrides::new(),
+        })
+    }
+
+    /// Reads and parses a `.gitignore` file.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use pathfilter::GitignoreFilter;
+    ///
+    /// let filter = GitignoreFilter::from_file(".gitignore").unwrap();
+    /// ```
+    /// # Errors
+    /// If the file cannot be read or a pattern is invalid, an error is returned.
+    /// Pattern errors carry the name of the file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, PatternFileError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| PatternFileError::Io {
+            file: path.to_path_buf(),
+            source,
+        })?;
+
+        GitignoreFilter::new(&text).map_err(|mut err| {
+            err.file = Some(path.to_path_buf());
+            err.into()
+        })
+    }
+
+    options_methods! {
+        ///
+        /// Case-insensitive matching is how git matches with `core.ignoreCase` set.
+        on_change = recompile
+    }
+
+    /// Converts the patterns into the rules of a [`FilterSet`], in order.
+    ///
+    /// Every pattern becomes an ignore rule, or an allow rule if it starts with `!`,
+    /// whose filter matches the paths the pattern does and everything inside of
+    /// them. The filters get the options and overrides of this filter. Unlike git,
+    /// the set lets an allow rule re-include a path inside an ignored directory.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{GitignoreFilter, IgnorePath};
+    /// use std::path::Path;
+    ///
+    /// let filter = GitignoreFilter::new("target/\n*.log\n!keep.log\n").unwrap();
+    /// let set = filter.to_filter_set();
+    /// assert_eq!(set.len(), 3);
+    /// assert!(set.ignore(Path::new("target/debug/main")));
+    /// assert!(!set.ignore(Path::new("logs/keep.log")));
+    /// ```
+    pub fn to_filter_set(&self) -> FilterSet {
+        self.rules
+            .iter()
+            .map(|rule| {
+                let pattern = rule.regex.as_str();
+                let pattern = pattern.strip_suffix('$').unwrap_or(pattern);
+                let end = if rule.dir_only { "/.*$" } else { "(?:/.*)?$" };
+                let filter = RegexFilter::new_str(&format!("{pattern}{end}"))
+                    .expect("translated glob is a valid regex")
+                    .with_options(self.options)
+                    .with_overrides(self.overrides);
+                if rule.negated {
+                    FilterRule::Allow(filter.into())
+                } else {
+                    FilterRule::Ignore(filter.into())
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the patterns of the file in order, as written.
+    pub fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|rule| rule.pattern.as_str())
+    }
+
+    fn recompile(&mut self) {
+        let case_insensitive = self.options().is_case_insensitive();
+        for rule in &mut self.rules {
+            // See `RegexFilter::recompile`.
+            rule.regex = regex::RegexBuilder::new(rule.regex.as_str())
+                .case_insensitive(case_insensitive)
+                .size_limit(usize::MAX)
+                .build()
+                .expect("regex compiled before");
+        }
+    }
+
+    /// Returns `Some(true)` if the last matching rule ignores the path.
+    fn decide(&self, path: &str, is_dir: bool) -> Option<bool> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| (is_dir || !rule.dir_only) && rule.regex.is_match(path))
+            .map(|rule| !rule.negated)
+    }
+}
+
+impl Rule {
+    fn parse(line: &str, number: usize) -> Result<Option<Rule>, PatternError> {
+        let line = trim_trailing_spaces(line);
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+
+        let (negated, glob) = match line.strip_prefix('!') {
+            Some(glob) => (true, glob),
+            None => (
+                false,
+                line.strip_prefix('\\')
+                    .filter(|glob| glob.starts_with(['#', '!']))
+                    .unwrap_or(line),
+            ),
+        };
+        let (dir_only, glob) = match glob.strip_suffix('/') {
+            Some(dir) => (true, dir),
+            None => (false, glob),
+        };
+        let regex = to_regex(glob, false).map_err(|reason| PatternError {
+            file: None,
+            line: number,
+            column: usize::from(negated) + 1,
+            text: line.to_string(),
+            kind: PatternErrorKind::Glob(GlobError {
+                glob: line.to_string(),
+                reason,
+            }),
+        })?;
+
+        Ok(Some(Rule {
+            pattern: line.to_string(),
+            regex: regex::Regex::new(&regex).expect("translated glob is a valid regex"),
+            negated,
+            dir_only,
+        }))
+    }
+}
+
+/// Removes trailing spaces that are not escaped with `\`.
+fn trim_trailing_spaces(line: &str) -> &str {
+    let mut end = line.len();
+    while line[..end].ends_with(' ') {
+        let backslashes = line[..end - 1].len() - line[..end - 1].trim_end_matches('\\').len();
+        if backslashes % 2 == 1 {
+            break;
+        }
+        end -= 1;
+    }
+    &line[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        patterns::PatternErrorKind, GitignoreFilter, IgnorePath, MatchOptions, MatchOverrides,
+    };
+    use std::path::Path;
+
+    fn ignores(gitignore: &str, path: &str) -> bool {
+        GitignoreFilter::new(gitignore)
+            .unwrap()
+            .ignore(Path::new(path))
+    }
+
+    #[test]
+    fn comments_and_spaces() {
+        let filter = GitignoreFilter::new("# comment\n\n\\#hash\nspace\\ \ntrailing  \n").unwrap();
+        assert_eq!(
+            filter.patterns().collect::<Vec<_>>(),
+            ["\\#hash", "space\\ ", "trailing"]
+        );
+        assert!(filter.ignore(Path::new("#hash")));
+        assert!(filter.ignore(Path::new("space ")));
+        assert!(filter.ignore(Path::new("trailing")));
+        assert!(!filter.ignore(Path::new("# comment")));
+    }
+
+    #[test]
+    fn negation() {
+        let gitignore = "*.log\n!important.log\n";
+        assert!(ignores(gitignore, "debug.log"));
+        assert!(!ignores(gitignore, "logs/important.log"));
+        assert!(ignores("!important.log\n*.log\n", "important.log"));
+        assert!(ignores("\\!bang\n", "!bang"));
+
+        // A file in an ignored directory can't be re-included.
+        let gitignore = "build/\n!build/keep.txt\n";
+        assert!(ignores(gitignore, "build/keep.txt"));
+        let gitignore = "build/*\n!build/keep.txt\n";
+        assert!(!ignores(gitignore, "build/keep.txt"));
+        assert!(ignores(gitignore, "build/other.txt"));
+    }
+
+    #[test]
+    fn directories_and_anchoring() {
+        let gitignore = "target/\n/dist\ndocs/*.pdf\n";
+        assert!(ignores(gitignore, "target/"));
+        assert!(ignores(gitignore, "crates/a/target/debug/main"));
+        assert!(!ignores(gitignore, "target"));
+        assert!(ignores(gitignore, "dist"));
+        assert!(!ignores(gitignore, "web/dist"));
+        assert!(ignores(gitignore, "docs/manual.pdf"));
+        assert!(!ignores(gitignore, "web/docs/manual.pdf"));
+        assert!(!ignores(gitignore, "docs/api/manual.pdf"));
+        assert!(ignores("**/cache\n", "a/b/cache"));
+        assert!(ignores("logs/**\n", "logs/a/b.txt"));
+    }
+
+    #[test]
+    fn filter_set() {
+        let filter = GitignoreFilter::new("build/\n/Cargo.lock\n*.log\n!keep.log\n").unwrap();
+        let set = filter.to_filter_set();
+        assert_eq!(set.len(), 4);
+        assert!(set.ignore(Path::new("a/build/")));
+        assert!(set.ignore(Path::new("a/build/out.o")));
+        assert!(!set.ignore(Path::new("a/build")));
+        assert!(set.ignore(Path::new("Cargo.lock")));
+        assert!(!set.ignore(Path::new("a/Cargo.lock")));
+        assert!(set.ignore(Path::new("x/debug.log")));
+        assert!(!set.ignore(Path::new("x/keep.log")));
+
+        let filter = filter.with_options(MatchOptions::new().case_insensitive(true));
+        assert!(filter.to_filter_set().ignore(Path::new("x/DEBUG.LOG")));
+    }
+
+    #[test]
+    fn options_and_errors() {
+        let filter = GitignoreFilter::new("*.LOG\n").unwrap();
+        assert!(!filter.ignore(Path::new("debug.log")));
+        let filter = filter.with_options(MatchOptions::new().case_insensitive(true));
+        assert!(filter.ignore(Path::new("debug.log")));
+        let filter = filter.with_overrides(MatchOverrides::new().case_insensitive(false));
+        assert!(!filter.ignore(Path::new("debug.log")));
+        assert!(!filter.options().is_case_insensitive());
+
+        let err = GitignoreFilter::new("ok\n![abc\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::Glob(_)));
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+}
@@ -0,0 +1,475 @@
+use crate::{options::options_methods, IgnorePath, MatchCandidate, MatchOptions, MatchOverrides};
+use std::{error::Error, fmt, path::Path, str::FromStr};
+
+/// A filter that matches paths against a gitignore-style glob.
+///
+/// - `*` matches anything except `/`, and `?` matches a single character except `/`.
+/// - `[abc]`, `[a-z]` and `[!abc]` match a character from, or not from, a set.
+/// - `**/` matches any number of directories, and a trailing `/**` everything
+///   inside a directory. Any other `**` is the same as `*`.
+/// - `\` matches the next character literally.
+///
+/// A glob without a `/`, like `*.min.js`, matches the name of any path component.
+/// A glob with a `/`, like `src/**/*.rs`, is anchored at the start of the path; a
+/// leading `/` only anchors. A trailing `/` is ignored. When a glob matches a
+/// directory, it matches everything inside of it as well, so `target` matches
+/// `target/debug/main`.
+///
+/// With the `serde` feature the filter is serialized as its glob together with its
+/// options and overrides.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{GlobFilter, IgnorePath};
+/// use std::path::Path;
+///
+/// let filter = GlobFilter::new("src/**/*.rs").unwrap();
+/// assert!(filter.ignore(Path::new("src/lib.rs")));
+/// assert!(filter.ignore(Path::new("src/bin/main.rs")));
+/// assert!(!filter.ignore(Path::new("tests/main.rs")));
+///
+/// let filter = GlobFilter::new("*.min.js").unwrap();
+/// assert!(filter.ignore(Path::new("dist/app.min.js")));
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "GlobFilterDef")
+)]
+pub struct GlobFilter {
+    glob: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    regex: regex::Regex,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "MatchOptions::is_default")
+    )]
+    options: MatchOptions,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "MatchOverrides::is_empty")
+    )]
+    overrides: MatchOverrides,
+}
+
+/// The serialized form of a [`GlobFilter`], compiled with its options on deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GlobFilterDef {
+    glob: String,
+    #[serde(default)]
+    options: MatchOptions,
+    #[serde(default)]
+    overrides: MatchOverrides,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<GlobFilterDef> for GlobFilter {
+    type Error = GlobError;
+
+    fn try_from(value: GlobFilterDef) -> Result<Self, GlobError> {
+        Ok(GlobFilter::new(&value.glob)?
+            .with_options(value.options)
+            .with_overrides(value.overrides))
+    }
+}
+
+/// An error returned for an invalid glob.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GlobError {
+    /// The glob that could not be compiled.
+    pub glob: String,
+    /// Why the glob is invalid.
+    pub reason: &'static str,
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid glob `{}`: {}", self.glob, self.reason)
+    }
+}
+
+impl Error for GlobError {}
+
+impl IgnorePath for GlobFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        candidate
+            .text(&self.options())
+            .is_some_and(|text| self.regex.is_match(text))
+    }
+}
+
+impl FromStr for GlobFilter {
+    type Err = GlobError;
+
+    fn from_str(s: &str) -> Result<Self, GlobError> {
+        GlobFilter::new(s)
+    }
+}
+
+impl GlobFilter {
+    /// Creates a new glob filter.
+    ///
+    /// # Errors
+    /// If the glob is empty, has an unclosed `[` or ends with a single `\`, an
+    /// error is returned.
+    pub fn new(glob: &str) -> Result<Self, GlobError> {
+        let pattern = to_regex(glob, true).map_err(|reason| GlobError {
+            glob: glob.to_string(),
+            reason,
+        })?;
+        Ok(GlobFilter {
+            glob: glob.to_string(),
+            regex: regex::Regex::new(&pattern).expect("translated glob is a valid regex"),
+            options: MatchOptions::new(),
+            overrides: MatchOverrides::new(),
+        })
+    }
+
+    /// Returns the glob this filter was created from.
+    pub fn as_str(&self) -> &str {
+        &self.glob
+    }
+
+    options_methods! {
+        ///
+        /// # Examples
+        /// ```
+        /// use pathfilter::{GlobFilter, IgnorePath, MatchOptions};
+        /// use std::path::Path;
+        ///
+        /// let options = MatchOptions::new().case_insensitive(true).normalize_separators(true);
+        /// let filter = GlobFilter::new("src/*.rs").unwrap().with_options(options);
+        /// assert!(filter.ignore(Path::new("SRC\\lib.rs")));
+        /// ```
+        on_change = recompile
+    }
+
+    fn recompile(&mut self) {
+        // See `RegexFilter::recompile`.
+        self.regex = regex::RegexBuilder::new(self.regex.as_str())
+            .case_insensitive(self.options().is_case_insensitive())
+            .size_limit(usize::MAX)
+            .build()
+            .expect("regex compiled before");
+    }
+}
+
+/// Translates a glob into an equivalent regex pattern.
+///
+/// With `inside`, the regex also matches every path inside a matching directory.
+pub(crate) fn to_regex(glob: &str, inside: bool) -> Result<String, &'static str> {
+    let (anchored, body) = match glob.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (glob.trim_end_matches('/').contains('/'), glob),
+    };
+    let body = body.strip_suffix('/').unwrap_or(body);
+    if body.is_empty() {
+        return Err("the glob is empty");
+    }
+
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let chars: Vec<char> = body.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                let at_start = i == 0 || chars[i - 1] == '/';
+                match chars.get(i + 2) {
+                    Some('/') if at_start => {
+                        regex.push_str("(?:.*/)?");
+                        i += 3;
+                    }
+                    None if at_start => {
+                        regex.push_str(".*");
+                        i += 2;
+                    }
+                    _ => {
+                        regex.push_str("[^/]*");
+                        i += 2;
+                    }
+                }
+                continue;
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => i = class(&chars, i, &mut regex)?,
+            '\\' => {
+                i += 1;
+                let c = chars.get(i).ok_or("the glob ends with an escape")?;
+                regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4])));
+            }
+            c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+        i += 1;
+    }
+
+    regex.push_str(if inside { "(?:/.*)?$" } else { "$" });
+    Ok(regex)
+}
+
+/// The syntax of the globs of another tool, see [`translate`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Dialect {
+    /// `*` and `?` also match `/`, like `fnmatch` without `FNM_PATHNAME`.
+    pub(crate) slash_wildcards: bool,
+    /// `{a,b}` matches either alternative, which may contain wildcards.
+    pub(crate) braces: bool,
+    /// `{{regex}}` embeds a regular expression, as in rclone.
+    pub(crate) regex_blocks: bool,
+}
+
+/// Translates a glob of another tool into a regex pattern without anchors.
+///
+/// Unlike in [`to_regex`], `**` matches across `/` wherever it appears, and `**/`
+/// at the start of a component also matches no directory at all. Callers add the
+/// anchors, as the tools differ in where a glob may match.
+pub(crate) fn translate(glob: &str, dialect: Dialect) -> Result<String, &'static str> {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut regex = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '{' if dialect.regex_blocks && chars.get(i + 1) == Some(&'{') => {
+                let rest: String = chars[i + 2..].iter().collect();
+                let end = rest.find("}}").ok_or("a `{{` is not closed")?;
+                regex.push_str("(?:");
+                regex.push_str(&rest[..end]);
+                regex.push(')');
+                i += rest[..end].chars().count() + 4;
+                continue;
+            }
+            '{' if dialect.braces => {
+                let (alternatives, end) = alternatives(&chars, i)?;
+                regex.push_str("(?:");
+                for (n, alternative) in alternatives.iter().enumerate() {
+                    if n > 0 {
+                        regex.push('|');
+                    }
+                    regex.push_str(&translate(alternative, dialect)?);
+                }
+                regex.push(')');
+                i = end;
+            }
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                let at_start = i == 0 || chars[i - 1] == '/';
+                if at_start && chars.get(i + 2) == Some(&'/') {
+                    regex.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    regex.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' if dialect.slash_wildcards => regex.push_str(".*"),
+            '*' => regex.push_str("[^/]*"),
+            '?' if dialect.slash_wildcards => regex.push('.'),
+            '?' => regex.push_str("[^/]"),
+            '[' => i = class(&chars, i, &mut regex)?,
+            '\\' => {
+                i += 1;
+                let c = chars.get(i).ok_or("the glob ends with an escape")?;
+                regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4])));
+            }
+            c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+        i += 1;
+    }
+    Ok(regex)
+}
+
+/// Splits the `{a,b}` group starting at `start`, returning the index of its `}`.
+fn alternatives(chars: &[char], start: usize) -> Result<(Vec<String>, usize), &'static str> {
+    let mut alternatives = vec![String::new()];
+    let mut depth = 0;
+    let mut i = start + 1;
+    loop {
+        let c = *chars.get(i).ok_or("a `{` is not closed")?;
+        let alternative = alternatives.last_mut().expect("never empty");
+        match c {
+            '}' if depth == 0 => return Ok((alternatives, i)),
+            ',' if depth == 0 => alternatives.push(String::new()),
+            '\\' => {
+                i += 1;
+                alternative.push(c);
+                alternative.push(*chars.get(i).ok_or("the glob ends with an escape")?);
+            }
+            c => {
+                match c {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+                alternative.push(c);
+            }
+        }
+        i += 1;
+    }
+}
+
+/// Translates the character class starting at `start`, returning the index of its `]`.
+fn class(chars: &[char], start: usize, regex: &mut String) -> Result<usize, &'static str> {
+    let mut i = start + 1;
+    let negated = matches!(chars.get(i), Some('!' | '^'));
+    if negated {
+        i += 1;
+    }
+
+    let first = i;
+    regex.push_str(if negated { "[^/" } else { "[" });
+    loop {
+        match chars.get(i) {
+            None => return Err("a character class is not closed"),
+            Some(']') if i > first => break,
+            Some(&c) => {
+                let (c, escaped) = if c == '\\' {
+                    i += 1;
+                    (*chars.get(i).ok_or("the glob ends with an escape")?, true)
+                } else {
+                    (c, false)
+                };
+                // An unescaped `-` forms a range, other characters special in regex
+                // classes are always literal.
+                if matches!(c, '\\' | '[' | ']' | '^' | '&' | '~') || (escaped && c == '-') {
+                    regex.push('\\');
+                }
+                regex.push(c);
+            }
+        }
+        i += 1;
+    }
+    regex.push(']');
+    Ok(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GlobFilter, IgnorePath, MatchOptions};
+    use std::path::Path;
+
+    fn matches(glob: &str, path: &str) -> bool {
+        GlobFilter::new(glob).unwrap().ignore(Path::new(path))
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(matches("*.rs", "lib.rs"));
+        assert!(matches("*.rs", "src/lib.rs"));
+        assert!(!matches("*.rs", "lib.rsx"));
+        assert!(matches("?.txt", "docs/a.txt"));
+        assert!(!matches("?.txt", "ab.txt"));
+        assert!(matches("src/*.rs", "src/lib.rs"));
+        assert!(!matches("src/*.rs", "src/bin/main.rs"));
+        assert!(!matches("src/*.rs", "crates/src/lib.rs"));
+        assert!(matches("/Cargo.lock", "Cargo.lock"));
+        assert!(!matches("/Cargo.lock", "sub/Cargo.lock"));
+    }
+
+    #[test]
+    fn double_star() {
+        assert!(matches("**/target/**", "target/debug"));
+        assert!(matches("**/target/**", "crates/a/target/debug/main"));
+        assert!(!matches("**/target/**", "targets/debug"));
+        assert!(matches("src/**/*.rs", "src/lib.rs"));
+        assert!(matches("src/**/*.rs", "src/a/b/c.rs"));
+        assert!(matches("a**b", "axxb"));
+        assert!(!matches("a**b", "ax/xb"));
+    }
+
+    #[test]
+    fn directories() {
+        assert!(matches("target", "target/debug/main"));
+        assert!(matches("target/", "crates/a/target"));
+        assert!(matches("node_modules", "web/node_modules/react/index.js"));
+        assert!(!matches("target", "targets"));
+    }
+
+    #[test]
+    fn classes_and_escapes() {
+        assert!(matches("[abc].txt", "b.txt"));
+        assert!(!matches("[abc].txt", "d.txt"));
+        assert!(matches("[!abc].txt", "d.txt"));
+        assert!(!matches("[!abc].txt", "a.txt"));
+        assert!(matches("file[0-9]", "file7"));
+        assert!(matches("[]]", "]"));
+        assert!(matches("\\*.txt", "*.txt"));
+        assert!(!matches("\\*.txt", "a.txt"));
+        assert!(matches("a.b", "a.b"));
+        assert!(!matches("a.b", "axb"));
+        assert!(matches("[^&]", "x"));
+        assert!(matches("[a\\-z]", "-"));
+        assert!(!matches("[a\\-z]", "b"));
+        assert!(matches("[\\d]", "d"));
+        assert!(!matches("[\\d]", "1"));
+    }
+
+    #[test]
+    fn invalid() {
+        for glob in ["", "/", "[abc", "abc\\"] {
+            assert!(GlobFilter::new(glob).is_err(), "{glob}");
+        }
+        let err = GlobFilter::new("[abc").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid glob `[abc`: a character class is not closed"
+        );
+    }
+
+    #[test]
+    fn options() {
+        let filter = GlobFilter::new("src/*.rs").unwrap();
+        assert!(!filter.ignore(Path::new("SRC/lib.rs")));
+        let filter = filter.with_options(MatchOptions::new().case_insensitive(true));
+        assert!(filter.ignore(Path::new("SRC/lib.rs")));
+        assert_eq!(filter.as_str(), "src/*.rs");
+    }
+
+    #[test]
+    fn translate() {
+        use crate::glob::{translate, Dialect};
+
+        let matches = |glob: &str, dialect: Dialect, path: &str| {
+            let regex = format!("^{}$", translate(glob, dialect).unwrap());
+            regex::Regex::new(&regex).unwrap().is_match(path)
+        };
+        let plain = Dialect::default();
+        assert!(matches("**/a/**/b", plain, "a/b"));
+        assert!(matches("a**", plain, "a/b/c"));
+        assert!(!matches("a*", plain, "a/b"));
+
+        let braces = Dialect {
+            braces: true,
+            ..Dialect::default()
+        };
+        assert!(matches("{src,lib/{a,b}}/*.rs", braces, "lib/b/x.rs"));
+        assert!(matches("x.{rs,}", braces, "x."));
+        assert!(!matches("{src,lib}/*.rs", braces, "{src,lib}/x.rs"));
+        assert!(matches("{src,lib}/*.rs", plain, "{src,lib}/x.rs"));
+        assert!(matches("\\{a\\}", braces, "{a}"));
+
+        let fnmatch = Dialect {
+            slash_wildcards: true,
+            ..Dialect::default()
+        };
+        assert!(matches("a*c", fnmatch, "a/b/c"));
+        assert!(matches("a?b", fnmatch, "a/b"));
+
+        let rclone = Dialect {
+            braces: true,
+            regex_blocks: true,
+            ..Dialect::default()
+        };
+        assert!(matches("file{{\\d+}}.{jpg,png}", rclone, "file12.png"));
+        assert!(!matches("file{{\\d+}}.jpg", rclone, "filex.jpg"));
+
+        for glob in ["{a,b", "{{a}", "[a", "a\\"] {
+            assert!(translate(glob, rclone).is_err(), "{glob}");
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//! Golden-corpus regression testing for filters.
+//!
+//! A corpus is a plain text file with one `path<TAB>decision` entry per line,
+//! where the decision is either `ignore` or `keep`. Blank lines and lines
+//! starting with `#` are skipped.
+//!
+//! # Examples
+//! ```
+//! use pathfilter::golden::GoldenCorpus;
+//! use pathfilter::ExtensionFilter;
+//!
+//! let corpus = GoldenCorpus::parse("# sources\nsrc/lib.rs\tignore\nREADME.md\tkeep\n").unwrap();
+//! corpus.assert_matches(&ExtensionFilter::new(".rs"));
+//! ```
+
+use crate::IgnorePath;
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A single entry of a [`GoldenCorpus`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GoldenEntry {
+    /// The 1-based line the entry was read from.
+    pub line: usize,
+    /// The path to evaluate.
+    pub path: PathBuf,
+    /// Whether the path is expected to be ignored.
+    pub ignored: bool,
+}
+
+/// A list of paths together with the decision a filter is expected to make for them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GoldenCorpus {
+    entries: Vec<GoldenEntry>,
+}
+
+/// An error that occurred while loading a [`GoldenCorpus`].
+#[derive(Debug)]
+pub enum GoldenError {
+    /// The corpus file could not be read.
+    Io(io::Error),
+    /// A line has no tab separating the path from the decision.
+    MissingDecision {
+        /// The 1-based line number.
+        line: usize,
+    },
+    /// A line has a decision other than `ignore` or `keep`.
+    InvalidDecision {
+        /// The 1-based line number.
+        line: usize,
+        /// The decision as it was written.
+        decision: String,
+    },
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoldenError::Io(err) => write!(f, "failed to read corpus: {err}"),
+            GoldenError::MissingDecision { line } => {
+                write!(f, "line {line}: expected `path<TAB>decision`")
+            }
+            GoldenError::InvalidDecision { line, decision } => write!(
+                f,
+                "line {line}: invalid decision `{decision}`, expected `ignore` or `keep`"
+            ),
+        }
+    }
+}
+
+impl Error for GoldenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GoldenError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GoldenError {
+    fn from(value: io::Error) -> Self {
+        GoldenError::Io(value)
+    }
+}
+
+/// An entry of a [`GoldenCorpus`] that a filter did not reproduce.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Mismatch {
+    /// The entry with the expected decision.
+    pub entry: GoldenEntry,
+    /// Whether the filter ignored the path.
+    pub ignored: bool,
+}
+
+/// Every entry of a [`GoldenCorpus`] that a filter did not reproduce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatches {
+    /// The entries that differ, in corpus order.
+    pub mismatches: Vec<Mismatch>,
+    /// The number of entries in the corpus.
+    pub total: usize,
+}
+
+impl fmt::Display for Mismatches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} paths differ from the corpus:",
+            self.mismatches.len(),
+            self.total
+        )?;
+        for mismatch in &self.mismatches {
+            write!(
+                f,
+                "\n  line {}: {}: expected {}, got {}",
+                mismatch.entry.line,
+                mismatch.entry.path.display(),
+                decision(mismatch.entry.ignored),
+                decision(mismatch.ignored)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for Mismatches {}
+
+fn decision(ignored: bool) -> &'static str {
+    if ignored {
+        "ignore"
+    } else {
+        "keep"
+    }
+}
+
+impl GoldenCorpus {
+    /// Parses a corpus from its text representation.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::golden::GoldenCorpus;
+    ///
+    /// let corpus = GoldenCorpus::parse("src/lib.rs\tignore\nREADME.md\tkeep\n").unwrap();
+    /// assert_eq!(corpus.entries().len(), 2);
+    /// ```
+    /// # Errors
+    /// If a line is not of the form `path<TAB>decision`, an error is returned.
+    pub fn parse(text: &str) -> Result<Self, GoldenError> {
+        let mut entries = Vec::new();
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            if raw.trim().is_empty() || raw.starts_with('#') {
+                continue;
+            }
+
+            let (path, decision) = raw
+                .rsplit_once('\t')
+                .ok_or(GoldenError::MissingDecision { line })?;
+            let ignored = match decision.trim() {
+                "ignore" => true,
+                "keep" => false,
+                other => {
+                    return Err(GoldenError::InvalidDecision {
+                        line,
+                        decision: other.to_string(),
+                    })
+                }
+            };
+
+            entries.push(GoldenEntry {
+                line,
+                path: path.into(),
+                ignored,
+            });
+        }
+
+        Ok(GoldenCorpus { entries })
+    }
+
+    /// Reads and parses a corpus file.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use pathfilter::golden::GoldenCorpus;
+    ///
+    /// let corpus = GoldenCorpus::from_file("tests/ignore.corpus").unwrap();
+    /// ```
+    /// # Errors
+    /// If the file cannot be read or is malformed, an error is returned.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, GoldenError> {
+        GoldenCorpus::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Returns the entries of the corpus.
+    pub fn entries(&self) -> &[GoldenEntry] {
+        &self.entries
+    }
+
+    /// Checks that the filter reproduces the decision of every entry.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::golden::GoldenCorpus;
+    /// use pathfilter::ExtensionFilter;
+    ///
+    /// let corpus = GoldenCorpus::parse("src/lib.rs\tignore\nREADME.md\tkeep\n").unwrap();
+    /// assert!(corpus.check(&ExtensionFilter::new(".rs")).is_ok());
+    /// assert!(corpus.check(&ExtensionFilter::new(".md")).is_err());
+    /// ```
+    /// # Errors
+    /// If any decision differs, all mismatching entries are returned.
+    pub fn check<F: IgnorePath>(&self, filter: &F) -> Result<(), Mismatches> {
+        let mismatches: Vec<Mismatch> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let ignored = filter.ignore(&entry.path);
+                (ignored != entry.ignored).then(|| Mismatch {
+                    entry: entry.clone(),
+                    ignored,
+                })
+            })
+            .collect();
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(Mismatches {
+                mismatches,
+                total: self.entries.len(),
+            })
+        }
+    }
+
+    /// Asserts that the filter reproduces the decision of every entry.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::golden::GoldenCorpus;
+    /// use pathfilter::ExtensionFilter;
+    ///
+    /// let corpus = GoldenCorpus::parse("src/lib.rs\tignore\nREADME.md\tkeep\n").unwrap();
+    /// corpus.assert_matches(&ExtensionFilter::new(".rs"));
+    /// ```
+    /// # Panics
+    /// Panics with a listing of every mismatching entry if any decision differs.
+    #[track_caller]
+    pub fn assert_matches<F: IgnorePath>(&self, filter: &F) {
+        if let Err(mismatches) = self.check(filter) {
+            panic!("{mismatches}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        golden::{GoldenCorpus, GoldenError},
+        PathFilter,
+    };
+    use std::path::Path;
+
+    const CORPUS: &str =
+        "# rust sources\nsrc/lib.rs\tignore\nsrc/main.rs\tignore\n\nREADME.md\tkeep\n";
+
+    #[test]
+    fn parse() {
+        let corpus = GoldenCorpus::parse(CORPUS).unwrap();
+        let entries = corpus.entries();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].line, 2);
+        assert_eq!(entries[0].path, Path::new("src/lib.rs"));
+        assert!(entries[0].ignored);
+        assert_eq!(entries[2].line, 5);
+        assert!(!entries[2].ignored);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(
+            GoldenCorpus::parse("src/lib.rs\n"),
+            Err(GoldenError::MissingDecision { line: 1 })
+        ));
+        assert!(matches!(
+            GoldenCorpus::parse("src/lib.rs\tignore\nsrc/main.rs\tskip\n"),
+            Err(GoldenError::InvalidDecision { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn check() {
+        let corpus = GoldenCorpus::parse(CORPUS).unwrap();
+        assert!(corpus.check(&PathFilter::new_extension(".rs")).is_ok());
+
+        let mismatches = corpus.check(&PathFilter::new_extension(".md")).unwrap_err();
+        assert_eq!(mismatches.mismatches.len(), 3);
+        assert_eq!(
+            mismatches.to_string(),
+            "3 of 3 paths differ from the corpus:\n  line 2: src/lib.rs: expected ignore, got keep\n  line 3: src/main.rs: expected ignore, got keep\n  line 5: README.md: expected keep, got ignore"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "1 of 3 paths differ")]
+    fn assert_matches() {
+        let corpus = GoldenCorpus::parse(CORPUS).unwrap();
+        corpus.assert_matches(&PathFilter::new_extensions([".rs", ".md"]));
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{content::io_error_policy_methods, IgnorePath, IoErrorPolicy};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, File},
+    hash::Hash,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+/// Computes a digest of file contents for [`HashSetContentFilter`].
+pub trait ContentHasher {
+    /// The digest produced by the hasher.
+    type Digest: Eq + Hash;
+
+    /// Reads `reader` to the end and returns the digest of its contents.
+    ///
+    /// # Errors
+    /// If reading fails, an error is returned.
+    fn digest<R: Read>(&self, reader: R) -> io::Result<Self::Digest>;
+}
+
+/// The 64-bit FNV-1a hash.
+///
+/// It is fast and stable across platforms and releases, so digests can be stored,
+/// but it is not collision resistant. Use a cryptographic hash through
+/// [`ContentHasher`] if files can be crafted by others.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Fnv1a;
+
+impl ContentHasher for Fnv1a {
+    type Digest = u64;
+
+    fn digest<R: Read>(&self, mut reader: R) -> io::Result<u64> {
+        let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+        let mut buf = [0; 8192];
+        loop {
+            let read = match reader.read(&mut buf) {
+                Ok(0) => return Ok(hash),
+                Ok(read) => read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            for &byte in &buf[..read] {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+    }
+}
+
+/// A filter that matches files whose contents are already known.
+///
+/// Contents are identified by their size and digest. Files with a size no known
+/// content has are never read.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{Fnv1a, HashSetContentFilter, IgnorePath};
+///
+/// let mut filter = HashSetContentFilter::new(Fnv1a);
+/// filter.insert_file("uploaded/report.pdf").unwrap();
+/// assert!(filter.ignore("inbox/report-copy.pdf"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct HashSetContentFilter<H: ContentHasher> {
+    hasher: H,
+    digests: HashMap<u64, HashSet<H::Digest>>,
+    on_error: IoErrorPolicy,
+}
+
+impl<H: ContentHasher> IgnorePath for HashSetContentFilter<H> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+impl<H: ContentHasher> HashSetContentFilter<H> {
+    /// Creates a new filter without known contents that keeps files it cannot read.
+    pub fn new(hasher: H) -> Self {
+        HashSetContentFilter {
+            hasher,
+            digests: HashMap::new(),
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    io_error_policy_methods!("files that cannot be read");
+
+    /// Adds known contents with the given size in bytes and digest.
+    ///
+    /// Returns `false` if the contents were already known.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{ContentHasher, Fnv1a, HashSetContentFilter};
+    ///
+    /// let mut filter = HashSetContentFilter::new(Fnv1a);
+    /// let digest = Fnv1a.digest("hello".as_bytes()).unwrap();
+    /// assert!(filter.insert(5, digest));
+    /// assert!(filter.contains(5, &digest));
+    /// assert!(!filter.contains(6, &digest));
+    /// ```
+    pub fn insert(&mut self, size: u64, digest: H::Digest) -> bool {
+        self.digests.entry(size).or_default().insert(digest)
+    }
+
+    /// Adds the contents of a file.
+    ///
+    /// # Errors
+    /// If the file cannot be read, an error is returned.
+    pub fn insert_file<P: AsRef<Path>>(&mut self, path: P) -> io::Result<bool> {
+        let path = path.as_ref();
+        let size = fs::metadata(path)?.len();
+        let digest = self.digest_file(path)?;
+        Ok(self.insert(size, digest))
+    }
+
+    /// Returns `true` if contents with the given size and digest are known.
+    pub fn contains(&self, size: u64, digest: &H::Digest) -> bool {
+        self.digests
+            .get(&size)
+            .is_some_and(|digests| digests.contains(digest))
+    }
+
+    /// Returns the number of known contents.
+    pub fn len(&self) -> usize {
+        self.digests.values().map(HashSet::len).sum()
+    }
+
+    /// Returns `true` if no contents are known.
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+
+    /// Returns whether the contents of the file are known. Directories are not.
+    ///
+    /// # Errors
+    /// If the file cannot be read, an error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let path = path.as_ref();
+        let metadata = fs::metadata(path)?;
+        let Some(digests) = self.digests.get(&metadata.len()) else {
+            return Ok(false);
+        };
+        if !metadata.is_file() {
+            return Ok(false);
+        }
+
+        Ok(digests.contains(&self.digest_file(path)?))
+    }
+
+    fn digest_file(&self, path: &Path) -> io::Result<H::Digest> {
+        self.hasher.digest(BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        content::tests::TempDir, ContentHasher, Fnv1a, HashSetContentFilter, IgnorePath,
+        IoErrorPolicy,
+    };
+    use std::io::{self, Read};
+
+    #[test]
+    fn fnv1a() {
+        assert_eq!(Fnv1a.digest("".as_bytes()).unwrap(), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(Fnv1a.digest("a".as_bytes()).unwrap(), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(
+            Fnv1a.digest("foobar".as_bytes()).unwrap(),
+            0x8594_4171_f739_67e8
+        );
+    }
+
+    #[test]
+    fn hash_set_content_filter() {
+        let dir = TempDir::new();
+        let known = dir.write("known.txt", "hello");
+        let copy = dir.write("copy.txt", "hello");
+        let other = dir.write("other.txt", "world");
+        let longer = dir.write("longer.txt", "hello world");
+
+        let mut filter = HashSetContentFilter::new(Fnv1a);
+        assert!(filter.is_empty());
+        assert!(filter.insert_file(&known).unwrap());
+        assert!(!filter.insert_file(&copy).unwrap());
+        assert_eq!(filter.len(), 1);
+
+        assert!(filter.ignore(&copy));
+        assert!(!filter.ignore(&other));
+        assert!(!filter.ignore(&longer));
+        assert!(!filter.ignore(dir.path()));
+
+        let missing = dir.path().join("missing");
+        assert!(!filter.ignore(&missing));
+        let filter = filter.with_io_error_policy(IoErrorPolicy::Ignore);
+        assert!(filter.ignore(&missing));
+    }
+
+    #[test]
+    fn size_pre_check() {
+        struct Panicking;
+
+        impl ContentHasher for Panicking {
+            type Digest = ();
+
+            fn digest<R: Read>(&self, _: R) -> io::Result<()> {
+                panic!("file should not be read");
+            }
+        }
+
+        let dir = TempDir::new();
+        let file = dir.write("file.txt", "hello");
+
+        let mut filter = HashSetContentFilter::new(Panicking);
+        filter.insert(4, ());
+        assert!(!filter.ignore(file));
+    }
+}
@@ -0,0 +1,148 @@
+use crate::IgnorePath;
+#[cfg(feature = "io")]
+use crate::IoErrorPolicy;
+#[cfg(feature = "io")]
+use std::io;
+use std::path::{Component, Path};
+
+/// A filter that matches hidden entries, i.e. names starting with `.`.
+///
+/// Only the file name is checked, which is enough if hidden directories are not
+/// descended into. Use [`with_ancestors`](Self::with_ancestors) to also match
+/// everything inside them. `.` and `..` are not hidden.
+///
+/// With the `io` feature, [`with_attributes`](Self::with_attributes) also matches
+/// entries with the hidden attribute on Windows.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{HiddenFileFilter, IgnorePath};
+/// use std::path::Path;
+///
+/// let filter = HiddenFileFilter::new();
+/// assert!(filter.ignore(Path::new("home/me/.bashrc")));
+/// assert!(!filter.ignore(Path::new(".git/config")));
+/// assert!(filter.with_ancestors(true).ignore(Path::new(".git/config")));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HiddenFileFilter {
+    ancestors: bool,
+    #[cfg(feature = "io")]
+    attributes: bool,
+    #[cfg(feature = "io")]
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for HiddenFileFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        let dotted = if self.ancestors {
+            path.components().any(is_dotted)
+        } else {
+            path.components().next_back().is_some_and(is_dotted)
+        };
+        if dotted {
+            return true;
+        }
+
+        #[cfg(feature = "io")]
+        if self.attributes {
+            return if self.ancestors {
+                path.ancestors()
+                    .filter(|entry| !entry.as_os_str().is_empty())
+                    .any(|entry| self.on_error.resolve(has_hidden_attribute(entry)))
+            } else {
+                self.on_error.resolve(has_hidden_attribute(path))
+            };
+        }
+
+        false
+    }
+}
+
+fn is_dotted(component: Component<'_>) -> bool {
+    match component {
+        Component::Normal(name) => name.as_encoded_bytes().starts_with(b"."),
+        _ => false,
+    }
+}
+
+#[cfg(all(feature = "io", windows))]
+fn has_hidden_attribute(path: &Path) -> io::Result<bool> {
+    use std::os::windows::fs::MetadataExt;
+
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    match path.symlink_metadata() {
+        Ok(metadata) => Ok(metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(all(feature = "io", not(windows)))]
+fn has_hidden_attribute(_path: &Path) -> io::Result<bool> {
+    Ok(false)
+}
+
+impl HiddenFileFilter {
+    /// Creates a new filter for names starting with `.`.
+    pub fn new() -> Self {
+        HiddenFileFilter::default()
+    }
+
+    /// Sets whether paths inside a hidden directory are matched too.
+    ///
+    /// This checks every component of a path, so use it with relative paths.
+    pub fn with_ancestors(mut self, ancestors: bool) -> Self {
+        self.ancestors = ancestors;
+        self
+    }
+
+    /// Sets whether entries with the hidden attribute are matched too.
+    ///
+    /// This reads the metadata of the path and has no effect outside of Windows.
+    /// A path that does not exist is not hidden.
+    #[cfg(feature = "io")]
+    pub fn with_attributes(mut self, attributes: bool) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    #[cfg(feature = "io")]
+    crate::content::io_error_policy_methods!("paths whose attributes cannot be read");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HiddenFileFilter, IgnorePath};
+    use std::path::Path;
+
+    #[test]
+    fn dotfiles() {
+        let filter = HiddenFileFilter::new();
+        assert!(filter.ignore(Path::new(".env")));
+        assert!(filter.ignore(Path::new("src/.hidden/")));
+        assert!(!filter.ignore(Path::new("src/.hidden/lib.rs")));
+        assert!(!filter.ignore(Path::new("src/lib.rs")));
+        assert!(!filter.ignore(Path::new("src/..")));
+        assert!(!filter.ignore(Path::new("./src")));
+        assert!(!filter.ignore(Path::new("")));
+
+        let filter = filter.with_ancestors(true);
+        assert!(filter.ignore(Path::new("src/.hidden/lib.rs")));
+        assert!(!filter.ignore(Path::new("../src/lib.rs")));
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn attributes() {
+        use crate::content::tests::TempDir;
+
+        let dir = TempDir::new();
+        let file = dir.write("plain.txt", "");
+        let filter = HiddenFileFilter::new().with_attributes(true);
+        assert!(!filter.ignore(&file));
+        assert!(!filter.ignore(dir.path().join("missing")));
+        assert!(filter.ignore(dir.path().join(".missing")));
+    }
+}
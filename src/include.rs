@@ -0,0 +1,93 @@
+use crate::{IgnorePath, MatchCandidate, PathFilter};
+use std::path::Path;
+
+/// A filter that ignores every path not explicitly included.
+///
+/// This inverts the usual logic for include-oriented lists, like the files of a
+/// package or a sparse checkout: a path is kept only if one of the
+/// [`include`](Self::include) filters matches it and none of the
+/// [`exclude`](Self::exclude) filters does. Without include filters, every path
+/// is ignored.
+///
+/// Paths are matched as given, so directories leading to included files must be
+/// included as well if they are filtered too.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, IncludeFilter, PathFilter};
+/// use std::path::Path;
+///
+/// let filter = IncludeFilter::new(vec![PathFilter::new_extensions([".rs", ".toml"])])
+///     .with_exclude(PathFilter::new_extension(".toml"));
+/// assert!(!filter.ignore(Path::new("src/lib.rs")));
+/// assert!(filter.ignore(Path::new("Cargo.toml")));
+/// assert!(filter.ignore(Path::new("README.md")));
+/// ```
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IncludeFilter<F = PathFilter> {
+    include: Vec<F>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    exclude: Vec<F>,
+}
+
+impl<F: IgnorePath> IgnorePath for IncludeFilter<F> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        !self.include.ignore_candidate(candidate) || self.exclude.ignore_candidate(candidate)
+    }
+}
+
+impl<F> IncludeFilter<F> {
+    /// Creates a filter that keeps only paths matched by one of the filters.
+    pub fn new(include: Vec<F>) -> Self {
+        IncludeFilter {
+            include,
+            exclude: Vec::new(),
+        }
+    }
+
+    /// Adds a filter for paths to keep.
+    pub fn with_include(mut self, filter: F) -> Self {
+        self.include.push(filter);
+        self
+    }
+
+    /// Adds a filter for paths to ignore even if they are included.
+    pub fn with_exclude(mut self, filter: F) -> Self {
+        self.exclude.push(filter);
+        self
+    }
+
+    /// Returns the filters for paths to keep.
+    pub fn include(&self) -> &[F] {
+        &self.include
+    }
+
+    /// Returns the filters for paths that are ignored even if they are included.
+    pub fn exclude(&self) -> &[F] {
+        &self.exclude
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ExtensionFilter, IgnorePath, IncludeFilter};
+    use std::path::Path;
+
+    #[test]
+    fn include() {
+        let filter = IncludeFilter::new(vec![ExtensionFilter::new(".rs")])
+            .with_include(ExtensionFilter::new(".md"))
+            .with_exclude(ExtensionFilter::new(".md"));
+        assert!(!filter.ignore(Path::new("src/lib.rs")));
+        assert!(filter.ignore(Path::new("README.md")));
+        assert!(filter.ignore(Path::new("Cargo.toml")));
+
+        let empty = IncludeFilter::<ExtensionFilter>::new(Vec::new());
+        assert!(empty.ignore(Path::new("src/lib.rs")));
+    }
+}
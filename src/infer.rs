@@ -0,0 +1,223 @@
+//! Proposing filters from example paths.
+//!
+//! Given paths a user marked to ignore and paths marked to keep, [`infer`]
+//! proposes a small list of filters that ignores the former and keeps the latter,
+//! e.g. to offer "create rule from selection" in an interactive tool.
+//!
+//! Filters are chosen greedily, preferring those that cover the most remaining
+//! paths, from these candidates:
+//! - the extensions of the ignored paths,
+//! - with the `regex` feature, the directories of the ignored paths, as `^dir/`,
+//! - with the `regex` feature, the file names of the ignored paths, as `(^|/)name$`.
+//!
+//! A candidate is only used if it ignores none of the kept paths. The result is
+//! small but not necessarily the smallest possible.
+//!
+//! # Examples
+//! ```
+//! use pathfilter::{infer::infer, IgnorePath};
+//!
+//! let inference = infer(["main.o", "util.o", "libm.a"], ["main.c", "util.c"]);
+//! assert_eq!(inference.filters.len(), 1);
+//! assert!(inference.filters.ignore("lib.o"));
+//! assert!(inference.unresolved.is_empty());
+//! ```
+
+use crate::{
+    ExtensionFilter, ExtensionsFilter, IgnorePath, MatchOptions, MatchOverrides, PathFilter,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+/// The filters proposed by [`infer`].
+#[derive(Clone, Debug, Default)]
+pub struct Inference {
+    /// The proposed filters. They ignore every ignored path except the unresolved
+    /// and conflicting ones, and keep every kept path.
+    pub filters: Vec<PathFilter>,
+    /// Ignored paths that no candidate can separate from the kept paths.
+    pub unresolved: Vec<PathBuf>,
+    /// Paths that were marked both to ignore and to keep.
+    pub conflicts: Vec<PathBuf>,
+}
+
+/// A rule that can be proposed, ordered by preference on ties.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Candidate {
+    Extension(OsString),
+    #[cfg(feature = "regex")]
+    Directory(String),
+    #[cfg(feature = "regex")]
+    FileName(String),
+}
+
+impl Candidate {
+    fn to_filter(&self) -> PathFilter {
+        match self {
+            Candidate::Extension(ext) => ExtensionFilter::from_os_str(ext).into(),
+            #[cfg(feature = "regex")]
+            Candidate::Directory(dir) => regex_filter(&format!("^{}/", regex::escape(dir))),
+            #[cfg(feature = "regex")]
+            Candidate::FileName(name) => regex_filter(&format!("(^|/){}$", regex::escape(name))),
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+fn regex_filter(pattern: &str) -> PathFilter {
+    PathFilter::new_regex(regex::Regex::new(pattern).expect("escaped pattern is valid"))
+}
+
+/// Proposes filters that ignore the paths in `ignore` and keep the paths in `keep`.
+///
+/// See the [module documentation](self) for how filters are chosen.
+pub fn infer<I, K, P, Q>(ignore: I, keep: K) -> Inference
+where
+    I: IntoIterator<Item = P>,
+    K: IntoIterator<Item = Q>,
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let keep: BTreeSet<PathBuf> = keep.into_iter().map(|p| p.as_ref().to_path_buf()).collect();
+    let (conflicts, ignore): (BTreeSet<PathBuf>, BTreeSet<PathBuf>) = ignore
+        .into_iter()
+        .map(|p| p.as_ref().to_path_buf())
+        .partition(|path| keep.contains(path));
+
+    // Every usable candidate with the ignored paths it covers.
+    let mut candidates: BTreeMap<Candidate, BTreeSet<&Path>> = BTreeMap::new();
+    for path in &ignore {
+        for candidate in candidates_for(path) {
+            candidates.entry(candidate).or_default().insert(path);
+        }
+    }
+    candidates.retain(|candidate, _| {
+        let filter = candidate.to_filter();
+        !keep.iter().any(|path| filter.ignore(path))
+    });
+
+    let mut uncovered: BTreeSet<&Path> = ignore.iter().map(PathBuf::as_path).collect();
+    let mut chosen = Vec::new();
+    while let Some((candidate, covered)) = candidates
+        .iter()
+        .map(|(candidate, paths)| (candidate, paths.intersection(&uncovered).count()))
+        .filter(|&(_, covered)| covered > 0)
+        .min_by_key(|&(candidate, covered)| (std::cmp::Reverse(covered), candidate))
+        .map(|(candidate, _)| (candidate.clone(), candidates[candidate].clone()))
+    {
+        uncovered.retain(|path| !covered.contains(path));
+        chosen.push(candidate);
+    }
+
+    Inference {
+        filters: to_filters(chosen),
+        unresolved: uncovered.into_iter().map(Path::to_path_buf).collect(),
+        conflicts: conflicts.into_iter().collect(),
+    }
+}
+
+fn candidates_for(path: &Path) -> Vec<Candidate> {
+    #[cfg_attr(not(feature = "regex"), allow(unused_mut))]
+    let mut candidates: Vec<Candidate> = path
+        .extension()
+        .map(|ext| Candidate::Extension(ext.to_os_string()))
+        .into_iter()
+        .collect();
+
+    #[cfg(feature = "regex")]
+    if let Some(text) = path.to_str() {
+        candidates.extend(
+            text.match_indices('/')
+                .map(|(end, _)| &text[..end])
+                .filter(|dir| !dir.is_empty())
+                .map(|dir| Candidate::Directory(dir.to_string())),
+        );
+        if let Some(name) = text.rsplit('/').next().filter(|name| !name.is_empty()) {
+            candidates.push(Candidate::FileName(name.to_string()));
+        }
+    }
+
+    candidates
+}
+
+/// Merges the chosen extensions into one filter, followed by the other filters.
+fn to_filters(chosen: Vec<Candidate>) -> Vec<PathFilter> {
+    let (extensions, others): (Vec<_>, Vec<_>) = chosen
+        .into_iter()
+        .partition(|candidate| matches!(candidate, Candidate::Extension(_)));
+
+    let mut filters = Vec::with_capacity(others.len() + 1);
+    match extensions.as_slice() {
+        [] => {}
+        [extension] => filters.push(extension.to_filter()),
+        extensions => {
+            let extensions = extensions
+                .iter()
+                .map(|candidate| match candidate {
+                    Candidate::Extension(ext) => ext.clone(),
+                    #[cfg(feature = "regex")]
+                    _ => unreachable!("partitioned by kind"),
+                })
+                .collect();
+            filters.push(
+                ExtensionsFilter::from_set(extensions, MatchOptions::new(), MatchOverrides::new())
+                    .into(),
+            );
+        }
+    }
+    filters.extend(others.iter().map(Candidate::to_filter));
+    filters
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{infer::infer, IgnorePath, PathFilter};
+    use std::path::Path;
+
+    #[test]
+    fn extensions() {
+        let inference = infer(["a.o", "b.o", "c.a", "d.log"], ["a.c", "b.h"]);
+        assert_eq!(inference.filters.len(), 1);
+        assert!(matches!(inference.filters[0], PathFilter::Extensions(_)));
+        for path in ["x.o", "x.a", "x.log"] {
+            assert!(inference.filters.ignore(path));
+        }
+        assert!(!inference.filters.ignore("x.c"));
+    }
+
+    #[test]
+    fn conflicts() {
+        let inference = infer(["a.rs", "b.txt"], ["a.rs", "c.txt"]);
+        assert_eq!(inference.conflicts, [Path::new("a.rs")]);
+        assert!(inference.filters.is_empty() || !inference.filters.ignore("c.txt"));
+        #[cfg(not(feature = "regex"))]
+        assert_eq!(inference.unresolved, [Path::new("b.txt")]);
+        #[cfg(feature = "regex")]
+        assert!(inference.filters.ignore("b.txt"));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn directories_and_names() {
+        let ignore = [
+            "target/debug/main",
+            "target/release/main.d",
+            "src/gen/parser.rs",
+            "src/gen/lexer.rs",
+            "docs/.DS_Store",
+            "src/.DS_Store",
+        ];
+        let keep = ["src/lib.rs", "docs/index.md", "Cargo.toml"];
+        let inference = infer(ignore, keep);
+
+        assert!(inference.unresolved.is_empty());
+        assert_eq!(inference.filters.len(), 3, "{:?}", inference.filters);
+        assert!(ignore.iter().all(|path| inference.filters.ignore(path)));
+        assert!(!keep.iter().any(|path| inference.filters.ignore(path)));
+        assert!(inference.filters.ignore("target/tmp"));
+        assert!(inference.filters.ignore("tests/.DS_Store"));
+    }
+}
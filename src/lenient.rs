@@ -0,0 +1,410 @@
+use crate::{value::Value, FilterRule, FilterSet, PathFilter};
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{fmt, marker::PhantomData};
+
+/// A list of filters that skips invalid entries instead of failing to deserialize.
+///
+/// Every entry that cannot be deserialized into a [`PathFilter`], e.g. because of an
+/// unknown filter kind or an invalid regular expression, is recorded as a
+/// [`LenientWarning`] and left out of [`filters`](Self::filters).
+///
+/// # Examples
+/// ```
+/// use pathfilter::LenientFilters;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     ignore: LenientFilters,
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LenientFilters {
+    /// The filters that could be deserialized.
+    pub filters: Vec<PathFilter>,
+    /// A warning for every entry that was skipped.
+    pub warnings: Vec<LenientWarning>,
+}
+
+/// The rules of a [`FilterSet`], skipping invalid rules instead of failing to
+/// deserialize.
+///
+/// Like [`LenientFilters`], every rule that cannot be deserialized, e.g. because
+/// of an unknown filter kind, is recorded as a [`LenientWarning`] and left out of
+/// [`set`](Self::set), so the usable rules still apply in order. Only the list
+/// form of a set is read, so the set never ignores unmatched paths. Use
+/// [`FiltersSeed::deserialize_set`] to choose the strict mode at runtime.
+///
+/// # Examples
+/// ```
+/// use pathfilter::LenientFilterSet;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     rules: LenientFilterSet,
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LenientFilterSet {
+    /// The set of the rules that could be deserialized.
+    pub set: FilterSet,
+    /// A warning for every rule that was skipped.
+    pub warnings: Vec<LenientWarning>,
+}
+
+/// An entry that was skipped while deserializing [`LenientFilters`] or [`LenientFilterSet`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LenientWarning {
+    /// The position of the entry in the original list.
+    pub index: usize,
+    /// The error that occurred while deserializing the entry.
+    pub message: String,
+}
+
+impl fmt::Display for LenientWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "skipped filter {}: {}", self.index, self.message)
+    }
+}
+
+impl<'de> Deserialize<'de> for LenientFilters {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        FiltersSeed::lenient().deserialize(deserializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for LenientFilterSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        FiltersSeed::lenient().deserialize_set(deserializer)
+    }
+}
+
+/// Deserializes a list of filters either leniently or strictly, chosen at runtime.
+///
+/// The lenient mode is the same as deserializing [`LenientFilters`]. The strict
+/// mode, e.g. for validating configs in CI, fails on the first invalid entry and
+/// also on fields a filter does not know, which are otherwise ignored.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{FiltersSeed, LenientFilters};
+/// use serde::de::{value::{Error, SeqDeserializer}, DeserializeSeed};
+///
+/// let strict = std::env::var_os("CI").is_some();
+/// let seed = if strict { FiltersSeed::strict() } else { FiltersSeed::lenient() };
+///
+/// let empty = SeqDeserializer::<_, Error>::new(std::iter::empty::<()>());
+/// let filters: LenientFilters = seed.deserialize(empty).unwrap();
+/// assert!(filters.filters.is_empty());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FiltersSeed {
+    strict: bool,
+}
+
+impl FiltersSeed {
+    /// Skips invalid entries and records a warning for each of them.
+    pub fn lenient() -> Self {
+        FiltersSeed { strict: false }
+    }
+
+    /// Fails on invalid entries and unknown fields.
+    pub fn strict() -> Self {
+        FiltersSeed { strict: true }
+    }
+
+    /// Returns `true` if this seed deserializes strictly.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Deserializes the rules of a [`FilterSet`] in this mode.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::FiltersSeed;
+    /// use serde::de::value::{Error, SeqDeserializer};
+    ///
+    /// let empty = SeqDeserializer::<_, Error>::new(std::iter::empty::<()>());
+    /// let rules = FiltersSeed::strict().deserialize_set(empty).unwrap();
+    /// assert!(rules.set.is_empty());
+    /// ```
+    /// # Errors
+    /// If the input is not a list, or in the strict mode a rule is invalid, an
+    /// error is returned.
+    pub fn deserialize_set<'de, D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<LenientFilterSet, D::Error> {
+        let (rules, warnings) = deserializer.deserialize_seq(EntriesVisitor::<FilterRule> {
+            strict: self.strict,
+            marker: PhantomData,
+        })?;
+        Ok(LenientFilterSet {
+            set: rules.into_iter().collect(),
+            warnings,
+        })
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for FiltersSeed {
+    type Value = LenientFilters;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let (filters, warnings) = deserializer.deserialize_seq(EntriesVisitor::<PathFilter> {
+            strict: self.strict,
+            marker: PhantomData,
+        })?;
+        Ok(LenientFilters { filters, warnings })
+    }
+}
+
+/// Deserializes a list of filters or rules, collecting a warning for every
+/// invalid entry unless `strict`.
+struct EntriesVisitor<T> {
+    strict: bool,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: DeserializeOwned> Visitor<'de> for EntriesVisitor<T> {
+    type Value = (Vec<T>, Vec<LenientWarning>);
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of filters")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let (mut entries, mut warnings) = (Vec::new(), Vec::new());
+        let mut index = 0;
+        while let Some(value) = seq.next_element::<Value>()? {
+            let entry = if self.strict {
+                check_extensions_fields(&value)
+                    .and_then(|()| T::deserialize(value.into_strict_deserializer()))
+            } else {
+                T::deserialize(IntoDeserializer::<de::value::Error>::into_deserializer(
+                    value,
+                ))
+            };
+            match entry {
+                Ok(entry) => entries.push(entry),
+                Err(err) if self.strict => {
+                    return Err(de::Error::custom(format_args!("filter {index}: {err}")))
+                }
+                Err(err) => warnings.push(LenientWarning {
+                    index,
+                    message: err.to_string(),
+                }),
+            }
+            index += 1;
+        }
+
+        Ok((entries, warnings))
+    }
+}
+
+/// Checks the fields of the map form of every `ExtensionsFilter` in the value.
+///
+/// That form is told apart from the list form by its contents, so the strict
+/// deserializer cannot check its fields when deserializing it.
+fn check_extensions_fields(value: &Value) -> Result<(), de::value::Error> {
+    const FIELDS: &[&str] = &["extensions", "options", "overrides"];
+    match value {
+        Value::Map(entries) => {
+            for (key, value) in entries {
+                if let (Value::Str(kind), Value::Map(fields)) = (key, value) {
+                    if kind == "Extensions" || kind == "extensions" {
+                        for (field, _) in fields {
+                            match field {
+                                Value::Str(field) if !FIELDS.contains(&field.as_str()) => {
+                                    return Err(de::Error::unknown_field(field, FIELDS));
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                check_extensions_fields(value)?;
+            }
+            Ok(())
+        }
+        Value::Seq(values) => values.iter().try_for_each(check_extensions_fields),
+        Value::Some(value) => check_extensions_fields(value),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        value::Value, FiltersSeed, IgnorePath, LenientFilterSet, LenientFilters, PathFilter,
+    };
+    use serde::{
+        de::{value::Error, DeserializeSeed, IntoDeserializer},
+        Deserialize,
+    };
+    use std::path::Path;
+
+    fn str(s: &str) -> Value {
+        Value::Str(s.to_string())
+    }
+
+    fn entry(key: &str, value: Value) -> Value {
+        Value::Map(vec![(str(key), value)])
+    }
+
+    fn os_str(s: &str) -> Value {
+        if cfg!(windows) {
+            entry(
+                "Windows",
+                Value::Seq(s.encode_utf16().map(|c| Value::U64(c.into())).collect()),
+            )
+        } else {
+            entry(
+                "Unix",
+                Value::Seq(s.bytes().map(|b| Value::U64(b.into())).collect()),
+            )
+        }
+    }
+
+    fn deserialize(value: Value) -> LenientFilters {
+        LenientFilters::deserialize(IntoDeserializer::<Error>::into_deserializer(value)).unwrap()
+    }
+
+    #[test]
+    fn skips_unknown_kinds() {
+        let lenient = deserialize(Value::Seq(vec![
+            entry("Extension", entry("extension", os_str("rs"))),
+            entry("Tree", entry("tree", str("src"))),
+            entry("extension", entry("extension", os_str("txt"))),
+        ]));
+
+        assert_eq!(lenient.filters.len(), 2);
+        assert!(matches!(lenient.filters[0], PathFilter::Extension(_)));
+        assert!(lenient.filters.ignore(Path::new("src/lib.rs")));
+        assert!(lenient.filters.ignore(Path::new("notes.txt")));
+
+        assert_eq!(lenient.warnings.len(), 1);
+        assert_eq!(lenient.warnings[0].index, 1);
+        assert!(lenient.warnings[0].message.contains("Tree"));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn skips_invalid_regex() {
+        let lenient = deserialize(Value::Seq(vec![
+            entry("Regex", entry("regex", str("(unclosed"))),
+            entry("Regex", entry("regex", str("^src/"))),
+        ]));
+
+        assert_eq!(lenient.filters.len(), 1);
+        assert!(lenient.filters.ignore(Path::new("src/lib.rs")));
+        assert_eq!(lenient.warnings.len(), 1);
+        assert_eq!(lenient.warnings[0].index, 0);
+        assert!(lenient.warnings[0]
+            .to_string()
+            .starts_with("skipped filter 0: "));
+    }
+
+    #[test]
+    fn strict() {
+        let strict = |value: Value| {
+            FiltersSeed::strict()
+                .deserialize(IntoDeserializer::<Error>::into_deserializer(value))
+                .map(|filters| filters.filters)
+        };
+        let extension = |fields: Vec<(Value, Value)>| {
+            let mut entries = vec![(str("extension"), os_str("rs"))];
+            entries.extend(fields);
+            entry("Extension", Value::Map(entries))
+        };
+
+        assert_eq!(
+            strict(Value::Seq(vec![extension(vec![])])).unwrap().len(),
+            1
+        );
+
+        let unknown_kind = Value::Seq(vec![extension(vec![]), entry("Tree", str("src"))]);
+        let err = strict(unknown_kind.clone()).unwrap_err().to_string();
+        assert!(err.starts_with("filter 1: unknown variant `Tree`"), "{err}");
+        assert_eq!(deserialize(unknown_kind).filters.len(), 1);
+
+        let unknown_field = Value::Seq(vec![extension(vec![(str("case"), Value::Bool(true))])]);
+        let err = strict(unknown_field.clone()).unwrap_err().to_string();
+        assert!(err.starts_with("filter 0: unknown field `case`"), "{err}");
+        assert_eq!(deserialize(unknown_field).filters.len(), 1);
+
+        let nested = Value::Seq(vec![extension(vec![(
+            str("options"),
+            Value::Map(vec![(str("ignore_case"), Value::Bool(true))]),
+        )])]);
+        let err = strict(nested).unwrap_err().to_string();
+        assert!(
+            err.starts_with("filter 0: unknown field `ignore_case`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn strict_extensions_map() {
+        let extensions = |field: &str| {
+            Value::Seq(vec![entry(
+                "Extensions",
+                Value::Map(vec![
+                    (str("extensions"), Value::Seq(vec![str(".rs")])),
+                    (str(field), Value::Bool(true)),
+                ]),
+            )])
+        };
+        let strict = |value: Value| {
+            FiltersSeed::strict()
+                .deserialize(IntoDeserializer::<Error>::into_deserializer(value))
+                .map(|filters| filters.filters)
+        };
+
+        let err = strict(extensions("case")).unwrap_err().to_string();
+        assert!(err.starts_with("filter 0: unknown field `case`"), "{err}");
+        assert_eq!(deserialize(extensions("case")).filters.len(), 1);
+        let Value::Seq(filters) = extensions("case") else {
+            unreachable!()
+        };
+        let nested = Value::Seq(vec![entry("Not", filters[0].clone())]);
+        assert!(strict(nested).is_err());
+    }
+
+    #[test]
+    fn filter_set() {
+        let value = Value::Seq(vec![
+            entry(
+                "Ignore",
+                entry("Extension", entry("extension", os_str("log"))),
+            ),
+            entry("Ignore", entry("Tree", str("src"))),
+            entry(
+                "Allow",
+                entry(
+                    "FileName",
+                    entry("names", Value::Seq(vec![str("keep.log")])),
+                ),
+            ),
+        ]);
+        let lenient = LenientFilterSet::deserialize(IntoDeserializer::<Error>::into_deserializer(
+            value.clone(),
+        ))
+        .unwrap();
+        assert_eq!(lenient.set.len(), 2);
+        assert!(lenient.set.ignore(Path::new("debug.log")));
+        assert!(!lenient.set.ignore(Path::new("keep.log")));
+        assert_eq!(lenient.warnings.len(), 1);
+        assert_eq!(lenient.warnings[0].index, 1);
+
+        let err = FiltersSeed::strict()
+            .deserialize_set(IntoDeserializer::<Error>::into_deserializer(value))
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("filter 1: "), "{err}");
+    }
+}
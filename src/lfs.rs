@@ -0,0 +1,113 @@
+use crate::{
+    content::{io_error_policy_methods, read_prefix, IoErrorPolicy},
+    IgnorePath,
+};
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+const POINTER_HEADER: &[u8] = b"version https://git-lfs.github.com/spec/v1\n";
+/// Pointer files are smaller than this according to the Git LFS specification.
+const MAX_POINTER_SIZE: u64 = 1024;
+
+/// A filter that matches Git LFS pointer files.
+///
+/// A file is a pointer if it is smaller than 1024 bytes and starts with
+/// `version https://git-lfs.github.com/spec/v1`. Only the first line is read.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{IgnorePath, LfsPointerFilter};
+///
+/// let filter = LfsPointerFilter::new();
+/// assert!(filter.ignore("assets/video.mp4"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LfsPointerFilter {
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for LfsPointerFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+impl LfsPointerFilter {
+    /// Creates a new Git LFS pointer filter that keeps files it cannot read.
+    pub fn new() -> Self {
+        LfsPointerFilter {
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    io_error_policy_methods!("files that cannot be read");
+
+    /// Returns whether the path is a Git LFS pointer file. Directories are not.
+    ///
+    /// # Errors
+    /// If the file cannot be read, an error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let path = path.as_ref();
+        let metadata = fs::metadata(path)?;
+        if !metadata.is_file() || metadata.len() >= MAX_POINTER_SIZE {
+            return Ok(false);
+        }
+
+        Ok(read_prefix(path, POINTER_HEADER.len())? == POINTER_HEADER)
+    }
+
+    /// Returns whether the contents of `reader` start like a Git LFS pointer file.
+    ///
+    /// The size is not checked, only the header is read.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::LfsPointerFilter;
+    ///
+    /// let pointer = "version https://git-lfs.github.com/spec/v1\noid sha256:4d7a\nsize 12345\n";
+    /// assert!(LfsPointerFilter::is_pointer(pointer.as_bytes()).unwrap());
+    /// assert!(!LfsPointerFilter::is_pointer("fn main() {}".as_bytes()).unwrap());
+    /// ```
+    /// # Errors
+    /// If reading fails, an error is returned.
+    pub fn is_pointer<R: Read>(reader: R) -> io::Result<bool> {
+        let mut header = Vec::with_capacity(POINTER_HEADER.len());
+        reader
+            .take(POINTER_HEADER.len() as u64)
+            .read_to_end(&mut header)?;
+        Ok(header == POINTER_HEADER)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{content::tests::TempDir, IgnorePath, IoErrorPolicy, LfsPointerFilter};
+
+    #[test]
+    fn lfs_pointer_filter() {
+        let dir = TempDir::new();
+        let pointer = dir.write(
+            "video.mp4",
+            "version https://git-lfs.github.com/spec/v1\noid sha256:4d7a\nsize 12345\n",
+        );
+        let text = dir.write("notes.txt", "version 1\n");
+        let mut large = b"version https://git-lfs.github.com/spec/v1\n".to_vec();
+        large.resize(4096, b'a');
+        let large = dir.write("large.bin", large);
+
+        let filter = LfsPointerFilter::new();
+        assert!(filter.ignore(&pointer));
+        assert!(!filter.ignore(&text));
+        assert!(!filter.ignore(&large));
+        assert!(!filter.ignore(dir.path()));
+
+        let missing = dir.path().join("missing");
+        assert!(!filter.ignore(&missing));
+        assert!(filter
+            .with_io_error_policy(IoErrorPolicy::Ignore)
+            .ignore(&missing));
+    }
+}
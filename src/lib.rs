@@ -5,6 +5,10 @@
 //!
 //! This library provides a simple interface for creating and using filters.
 //! Filters are used to ignore files and directories.
+//!
+//! To combine several filters into the rules of a project, e.g. loaded from a
+//! config, use a [`FilterSet`]: its rules are evaluated in order and the last
+//! matching rule decides whether a path is ignored or kept.
 //! # Examples
 //!
 //! ```
@@ -17,19 +21,239 @@
 //!
 //! ```
 
+pub mod archive;
+#[cfg(feature = "io")]
+mod binary;
+#[cfg(feature = "glob")]
+pub mod borg;
+mod builder;
+mod candidate;
+mod chain;
+#[cfg(feature = "serde")]
+pub mod compact;
+pub mod compat;
+mod component;
+#[cfg(feature = "io")]
+mod content;
+#[cfg(feature = "test-util")]
+pub mod corpus;
+mod depth;
+mod dir_cache;
+#[cfg(feature = "io")]
+mod empty;
 mod extension;
+mod file_name;
+#[cfg(feature = "io")]
+mod file_type;
+mod filter_set;
+#[cfg(feature = "glob")]
+mod gitignore;
+#[cfg(feature = "glob")]
+mod glob;
+pub mod golden;
+#[cfg(feature = "io")]
+mod hash;
+mod hidden;
+mod include;
+pub mod infer;
+#[cfg(feature = "serde")]
+mod lenient;
+#[cfg(feature = "io")]
+mod lfs;
+mod limit;
+#[cfg(feature = "io")]
+mod marker;
+#[cfg(feature = "mime")]
+mod mime;
+#[cfg(feature = "io")]
+mod modified;
+mod names;
+mod no_extension;
+mod normalize;
+mod not;
+mod ops;
+mod options;
+#[cfg(all(feature = "io", unix))]
+mod owner;
+mod path_set;
+pub mod patterns;
+#[cfg(feature = "io")]
+mod permissions;
+mod prefix;
+pub mod presets;
+#[cfg(feature = "glob")]
+pub mod rclone;
 #[cfg(feature = "regex")]
 mod regex;
+#[cfg(feature = "regex")]
+mod regex_set;
+#[cfg(feature = "glob")]
+pub mod restic;
+mod retain;
+mod retention;
+pub mod rsync;
+mod shared;
+#[cfg(feature = "io")]
+mod sidecar;
+#[cfg(feature = "io")]
+mod size;
+#[cfg(feature = "io")]
+mod sorted;
+mod stem;
+#[cfg(feature = "glob")]
+pub mod stignore;
+#[cfg(feature = "substring")]
+mod substring;
+#[cfg(feature = "serde")]
+mod value;
+#[cfg(feature = "glob")]
+pub mod vscode;
+mod wildcard;
+#[cfg(all(feature = "xattr", any(target_os = "linux", target_os = "macos")))]
+mod xattr;
 
 #[cfg(feature = "regex")]
 pub use crate::regex::RegexFilter;
-pub use extension::{ExtensionFilter, ExtensionsFilter};
+#[cfg(feature = "io")]
+pub use binary::BinaryFilter;
+pub use builder::{FilterSetBuilder, PathFilterBuilder};
+pub use candidate::{MatchCandidate, MatchContext};
+pub use chain::{ChainMode, FilterChain};
+pub use component::ComponentFilter;
+#[cfg(feature = "io")]
+pub use content::IoErrorPolicy;
+pub use depth::DepthFilter;
+pub use dir_cache::DirCache;
+#[cfg(feature = "io")]
+pub use empty::{EmptyDirFilter, EmptyFileFilter};
+pub use extension::{ExtensionFilter, ExtensionsFilter, StaticExtensionsFilter};
+pub use file_name::FileNameFilter;
+#[cfg(feature = "io")]
+pub use file_type::FileTypeFilter;
+pub use filter_set::{FilterRule, FilterSet};
+#[cfg(feature = "glob")]
+pub use gitignore::GitignoreFilter;
+#[cfg(feature = "glob")]
+pub use glob::{GlobError, GlobFilter};
+#[cfg(feature = "io")]
+pub use hash::{ContentHasher, Fnv1a, HashSetContentFilter};
+pub use hidden::HiddenFileFilter;
+pub use include::IncludeFilter;
+#[cfg(feature = "serde")]
+pub use lenient::{FiltersSeed, LenientFilterSet, LenientFilters, LenientWarning};
+#[cfg(feature = "io")]
+pub use lfs::LfsPointerFilter;
+pub use limit::LimitFilter;
+#[cfg(feature = "io")]
+pub use marker::MarkerFilter;
+#[cfg(feature = "mime")]
+pub use mime::MimeTypeFilter;
+#[cfg(feature = "io")]
+pub use modified::{AgeFilter, ModifiedSinceFilter};
+pub use no_extension::NoExtensionFilter;
+pub use not::{Not, Only};
+pub use ops::{And, Or};
+pub use options::{MatchOptions, MatchOverrides, MatchTarget};
+#[cfg(all(feature = "io", unix))]
+pub use owner::OwnerFilter;
+pub use path_set::ExactPathSetFilter;
+#[cfg(feature = "io")]
+pub use permissions::PermissionsFilter;
+pub use prefix::PrefixFilter;
+#[cfg(feature = "regex")]
+pub use regex_set::RegexSetFilter;
+pub use retain::{retain_ignored, retain_not_ignored};
+pub use retention::{Retention, RetentionPlan};
+pub use shared::{shared, DynIgnorePath, SharedFilter};
+#[cfg(feature = "io")]
+pub use sidecar::SidecarFilter;
+#[cfg(feature = "io")]
+pub use size::SizeFilter;
+#[cfg(feature = "io")]
+pub use sorted::SortedPathFile;
 use std::path::Path;
+pub use stem::StemFilter;
+#[cfg(feature = "substring")]
+pub use substring::SubstringFilter;
+pub use wildcard::WildcardFilter;
+#[cfg(all(feature = "xattr", any(target_os = "linux", target_os = "macos")))]
+pub use xattr::XattrFilter;
 
 /// Provides an interface ignoring paths.
 pub trait IgnorePath {
     /// Returns `true` if the path should be ignored.
     fn ignore<P: AsRef<Path>>(&self, path: P) -> bool;
+
+    /// Returns `true` if the path of the candidate should be ignored.
+    ///
+    /// Filters that match on the text of a path override this to reuse the
+    /// conversion stored in the candidate, see [`MatchCandidate`].
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        self.ignore(candidate.path())
+    }
+
+    /// Returns `true` if any of the paths should be ignored.
+    ///
+    /// Implementations can override this with a faster batch algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::{ExtensionFilter, IgnorePath};
+    ///
+    /// let filter = ExtensionFilter::new(".rs");
+    /// assert!(filter.ignore_any(["README.md", "src/lib.rs"]));
+    /// assert!(!filter.ignore_any(["README.md", "Cargo.toml"]));
+    /// ```
+    fn ignore_any<I, P>(&self, paths: I) -> bool
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        paths.into_iter().any(|path| self.ignore(path))
+    }
+
+    /// Returns `true` if all of the paths should be ignored.
+    ///
+    /// Implementations can override this with a faster batch algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::{ExtensionFilter, IgnorePath};
+    ///
+    /// let filter = ExtensionFilter::new(".rs");
+    /// assert!(filter.ignore_all(["src/main.rs", "src/lib.rs"]));
+    /// assert!(!filter.ignore_all(["README.md", "src/lib.rs"]));
+    /// ```
+    fn ignore_all<I, P>(&self, paths: I) -> bool
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        paths.into_iter().all(|path| self.ignore(path))
+    }
+
+    /// Returns for each path, in order, whether it should be ignored.
+    ///
+    /// Implementations can override this with a faster batch algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::{ExtensionFilter, IgnorePath};
+    ///
+    /// let filter = ExtensionFilter::new(".rs");
+    /// let ignored: Vec<bool> = filter.ignore_iter(["README.md", "src/lib.rs"]).collect();
+    /// assert_eq!(ignored, [false, true]);
+    /// ```
+    fn ignore_iter<I, P>(&self, paths: I) -> impl Iterator<Item = bool>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        paths.into_iter().map(|path| self.ignore(path))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -37,12 +261,43 @@ pub trait IgnorePath {
 /// An enum that represents different types of filters for ignoring paths.
 pub enum PathFilter {
     /// Filter that matches based on their extension.
+    #[cfg_attr(feature = "serde", serde(alias = "extension"))]
     Extension(ExtensionFilter),
     /// Filter that matches based on multiple extensions.
+    #[cfg_attr(feature = "serde", serde(alias = "extensions"))]
     Extensions(ExtensionsFilter),
+    /// Filter that matches paths containing a component with one of a set of names.
+    Component(ComponentFilter),
+    /// Filter that matches paths whose file name is one of a set of names.
+    FileName(FileNameFilter),
+    /// Filter that matches paths whose file stem is one of a set of stems.
+    Stem(StemFilter),
+    /// Filter that matches paths starting with one of a set of prefixes.
+    Prefix(PrefixFilter),
+    /// Filter that matches based on a simple wildcard pattern.
+    Wildcard(WildcardFilter),
+    /// Filter that ignores exactly the paths the inner filter keeps, see [`PathFilter::negate`].
+    Not(Box<PathFilter>),
+    /// Filter that ignores a path only if all of the inner filters do, see [`PathFilter::new_and`].
+    ///
+    /// Options are set on every inner filter and read from the first one.
+    And(Vec<PathFilter>),
+    /// Filter that ignores a path if any of the inner filters does, see [`PathFilter::new_or`].
+    ///
+    /// Options are set on every inner filter and read from the first one.
+    Or(Vec<PathFilter>),
     #[cfg(feature = "regex")]
     /// Filter that matches based on a regular expression.
+    #[cfg_attr(feature = "serde", serde(alias = "regex"))]
     Regex(RegexFilter),
+    #[cfg(feature = "glob")]
+    /// Filter that matches based on a gitignore-style glob.
+    Glob(GlobFilter),
+    #[cfg(all(feature = "io", unix))]
+    /// Filter that matches entries by their owning user or group.
+    ///
+    /// It reads the metadata of every path and has no match options.
+    Owner(OwnerFilter),
 }
 
 impl From<ExtensionFilter> for PathFilter {
@@ -57,6 +312,42 @@ impl From<ExtensionsFilter> for PathFilter {
     }
 }
 
+impl From<StaticExtensionsFilter> for PathFilter {
+    fn from(value: StaticExtensionsFilter) -> Self {
+        PathFilter::Extensions(value.into())
+    }
+}
+
+impl From<ComponentFilter> for PathFilter {
+    fn from(value: ComponentFilter) -> Self {
+        PathFilter::Component(value)
+    }
+}
+
+impl From<FileNameFilter> for PathFilter {
+    fn from(value: FileNameFilter) -> Self {
+        PathFilter::FileName(value)
+    }
+}
+
+impl From<StemFilter> for PathFilter {
+    fn from(value: StemFilter) -> Self {
+        PathFilter::Stem(value)
+    }
+}
+
+impl From<PrefixFilter> for PathFilter {
+    fn from(value: PrefixFilter) -> Self {
+        PathFilter::Prefix(value)
+    }
+}
+
+impl From<WildcardFilter> for PathFilter {
+    fn from(value: WildcardFilter) -> Self {
+        PathFilter::Wildcard(value)
+    }
+}
+
 #[cfg(feature = "regex")]
 impl From<RegexFilter> for PathFilter {
     fn from(value: RegexFilter) -> Self {
@@ -64,7 +355,34 @@ impl From<RegexFilter> for PathFilter {
     }
 }
 
+#[cfg(feature = "glob")]
+impl From<GlobFilter> for PathFilter {
+    fn from(value: GlobFilter) -> Self {
+        PathFilter::Glob(value)
+    }
+}
+
+#[cfg(all(feature = "io", unix))]
+impl From<OwnerFilter> for PathFilter {
+    fn from(value: OwnerFilter) -> Self {
+        PathFilter::Owner(value)
+    }
+}
+
 impl PathFilter {
+    /// Creates a builder for a list of filters that share the same options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::PathFilter;
+    ///
+    /// let filters = PathFilter::builder().extension(".rs").case_insensitive(true).build();
+    /// ```
+    pub fn builder() -> PathFilterBuilder {
+        PathFilterBuilder::new()
+    }
+
     /// Creates a new `PathFilter` based on a single extension.
     ///
     /// # Examples
@@ -94,6 +412,313 @@ impl PathFilter {
     {
         ExtensionsFilter::new(extensions).into()
     }
+
+    /// Creates a new `PathFilter` that matches paths containing one of the given components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::PathFilter;
+    ///
+    /// let filter = PathFilter::new_components(["node_modules", "target"]);
+    /// ```
+    pub fn new_components<I, S>(components: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ComponentFilter::new(components).into()
+    }
+
+    /// Creates a new `PathFilter` that matches paths with one of the given file names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::PathFilter;
+    ///
+    /// let filter = PathFilter::new_file_names(["Thumbs.db", "desktop.ini"]);
+    /// ```
+    pub fn new_file_names<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        FileNameFilter::new(names).into()
+    }
+
+    /// Creates a new `PathFilter` that matches paths with one of the given file stems.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::PathFilter;
+    ///
+    /// let filter = PathFilter::new_stems(["README", "LICENSE"]);
+    /// ```
+    pub fn new_stems<I, S>(stems: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        StemFilter::new(stems).into()
+    }
+
+    /// Creates a new `PathFilter` that matches paths starting with one of the given prefixes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::PathFilter;
+    ///
+    /// let filter = PathFilter::new_prefixes(["build/", "third_party/vendored"]);
+    /// ```
+    pub fn new_prefixes<I, P>(prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<std::path::PathBuf>,
+    {
+        PrefixFilter::new(prefixes).into()
+    }
+
+    /// Creates a new `PathFilter` that matches file names against a wildcard pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::PathFilter;
+    ///
+    /// let filter = PathFilter::new_wildcard("*.tmp");
+    /// ```
+    pub fn new_wildcard<S: Into<String>>(pattern: S) -> Self {
+        WildcardFilter::new(pattern).into()
+    }
+
+    /// Creates a new `PathFilter` that ignores a path only if all of the filters do.
+    ///
+    /// Without filters, no path is ignored. For filters of other types, use
+    /// [`FilterChain::all`] or combine two of them with `&` into an [`And`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::{IgnorePath, PathFilter};
+    /// use std::path::Path;
+    ///
+    /// let filter = PathFilter::new_and([
+    ///     PathFilter::new_prefixes(["vendor"]),
+    ///     PathFilter::new_extension(".js"),
+    /// ]);
+    /// assert!(filter.ignore(Path::new("vendor/jquery.js")));
+    /// assert!(!filter.ignore(Path::new("src/app.js")));
+    /// ```
+    pub fn new_and<I: IntoIterator<Item = PathFilter>>(filters: I) -> Self {
+        PathFilter::And(filters.into_iter().collect())
+    }
+
+    /// Creates a filter that ignores a path if any of the filters does.
+    ///
+    /// This is what a list of filters does; as a `PathFilter`, the disjunction can
+    /// be nested in other filters, e.g. [`new_and`](Self::new_and). Without filters,
+    /// no path is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::{IgnorePath, PathFilter};
+    /// use std::path::Path;
+    ///
+    /// let filter = PathFilter::new_or([
+    ///     PathFilter::new_extension(".o"),
+    ///     PathFilter::new_extension(".a"),
+    /// ]);
+    /// assert!(filter.ignore(Path::new("main.o")));
+    /// assert!(!filter.ignore(Path::new("main.c")));
+    /// ```
+    pub fn new_or<I: IntoIterator<Item = PathFilter>>(filters: I) -> Self {
+        PathFilter::Or(filters.into_iter().collect())
+    }
+
+    /// Returns a filter that ignores exactly the paths this filter keeps.
+    ///
+    /// Negating a negated filter returns the original filter. For other filter
+    /// types, see [`Not`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::{IgnorePath, PathFilter};
+    /// use std::path::Path;
+    ///
+    /// // Ignore everything except Rust files.
+    /// let filter = PathFilter::new_extension(".rs").negate();
+    /// assert!(!filter.ignore(Path::new("src/lib.rs")));
+    /// assert!(filter.ignore(Path::new("README.md")));
+    /// ```
+    pub fn negate(self) -> Self {
+        match self {
+            PathFilter::Not(inner) => *inner,
+            filter => PathFilter::Not(Box::new(filter)),
+        }
+    }
+
+    /// Sets the options used for matching.
+    ///
+    /// Applying the same options to every filter of a list makes them match consistently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::{IgnorePath, MatchOptions, PathFilter};
+    /// use std::path::Path;
+    ///
+    /// let options = MatchOptions::new().case_insensitive(true);
+    /// let filters: Vec<PathFilter> = [PathFilter::new_extension(".rs"), PathFilter::new_extension(".md")]
+    ///     .into_iter()
+    ///     .map(|filter| filter.with_options(options))
+    ///     .collect();
+    /// assert!(filters.ignore(Path::new("README.MD")));
+    /// ```
+    pub fn with_options(mut self, options: MatchOptions) -> Self {
+        self.set_options(options);
+        self
+    }
+
+    /// Sets the options used for matching.
+    ///
+    /// Settings overridden with [`with_overrides`](Self::with_overrides) keep their value.
+    pub fn set_options(&mut self, options: MatchOptions) {
+        match self {
+            PathFilter::Extension(x) => x.set_options(options),
+            PathFilter::Extensions(x) => x.set_options(options),
+            PathFilter::Component(x) => x.set_options(options),
+            PathFilter::FileName(x) => x.set_options(options),
+            PathFilter::Stem(x) => x.set_options(options),
+            PathFilter::Prefix(x) => x.set_options(options),
+            PathFilter::Wildcard(x) => x.set_options(options),
+            PathFilter::And(x) => x.iter_mut().for_each(|x| x.set_options(options)),
+            PathFilter::Or(x) => x.iter_mut().for_each(|x| x.set_options(options)),
+            PathFilter::Not(x) => x.set_options(options),
+            #[cfg(feature = "regex")]
+            PathFilter::Regex(x) => x.set_options(options),
+            #[cfg(feature = "glob")]
+            PathFilter::Glob(x) => x.set_options(options),
+            #[cfg(all(feature = "io", unix))]
+            PathFilter::Owner(_) => {}
+        }
+    }
+
+    /// Returns the options used for matching, including overrides.
+    pub fn options(&self) -> MatchOptions {
+        match self {
+            PathFilter::Extension(x) => x.options(),
+            PathFilter::Extensions(x) => x.options(),
+            PathFilter::Component(x) => x.options(),
+            PathFilter::FileName(x) => x.options(),
+            PathFilter::Stem(x) => x.options(),
+            PathFilter::Prefix(x) => x.options(),
+            PathFilter::Wildcard(x) => x.options(),
+            PathFilter::And(x) => x.first().map(PathFilter::options).unwrap_or_default(),
+            PathFilter::Or(x) => x.first().map(PathFilter::options).unwrap_or_default(),
+            PathFilter::Not(x) => x.options(),
+            #[cfg(feature = "regex")]
+            PathFilter::Regex(x) => x.options(),
+            #[cfg(feature = "glob")]
+            PathFilter::Glob(x) => x.options(),
+            #[cfg(all(feature = "io", unix))]
+            PathFilter::Owner(_) => MatchOptions::new(),
+        }
+    }
+
+    /// Sets options that take precedence over the ones set with [`with_options`](Self::with_options).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::{IgnorePath, MatchOptions, MatchOverrides, PathFilter};
+    /// use std::path::Path;
+    ///
+    /// let filter = PathFilter::new_extension(".rs")
+    ///     .with_overrides(MatchOverrides::new().case_insensitive(false))
+    ///     .with_options(MatchOptions::new().case_insensitive(true));
+    /// assert!(!filter.ignore(Path::new("src/lib.RS")));
+    /// ```
+    pub fn with_overrides(mut self, overrides: MatchOverrides) -> Self {
+        self.set_overrides(overrides);
+        self
+    }
+
+    /// Sets options that take precedence over the ones set with [`set_options`](Self::set_options).
+    pub fn set_overrides(&mut self, overrides: MatchOverrides) {
+        match self {
+            PathFilter::Extension(x) => x.set_overrides(overrides),
+            PathFilter::Extensions(x) => x.set_overrides(overrides),
+            PathFilter::Component(x) => x.set_overrides(overrides),
+            PathFilter::FileName(x) => x.set_overrides(overrides),
+            PathFilter::Stem(x) => x.set_overrides(overrides),
+            PathFilter::Prefix(x) => x.set_overrides(overrides),
+            PathFilter::Wildcard(x) => x.set_overrides(overrides),
+            PathFilter::And(x) => x.iter_mut().for_each(|x| x.set_overrides(overrides)),
+            PathFilter::Or(x) => x.iter_mut().for_each(|x| x.set_overrides(overrides)),
+            PathFilter::Not(x) => x.set_overrides(overrides),
+            #[cfg(feature = "regex")]
+            PathFilter::Regex(x) => x.set_overrides(overrides),
+            #[cfg(feature = "glob")]
+            PathFilter::Glob(x) => x.set_overrides(overrides),
+            #[cfg(all(feature = "io", unix))]
+            PathFilter::Owner(_) => {}
+        }
+    }
+
+    /// Returns the options this filter overrides.
+    pub fn overrides(&self) -> MatchOverrides {
+        match self {
+            PathFilter::Extension(x) => x.overrides(),
+            PathFilter::Extensions(x) => x.overrides(),
+            PathFilter::Component(x) => x.overrides(),
+            PathFilter::FileName(x) => x.overrides(),
+            PathFilter::Stem(x) => x.overrides(),
+            PathFilter::Prefix(x) => x.overrides(),
+            PathFilter::Wildcard(x) => x.overrides(),
+            PathFilter::And(x) => x.first().map(PathFilter::overrides).unwrap_or_default(),
+            PathFilter::Or(x) => x.first().map(PathFilter::overrides).unwrap_or_default(),
+            PathFilter::Not(x) => x.overrides(),
+            #[cfg(feature = "regex")]
+            PathFilter::Regex(x) => x.overrides(),
+            #[cfg(feature = "glob")]
+            PathFilter::Glob(x) => x.overrides(),
+            #[cfg(all(feature = "io", unix))]
+            PathFilter::Owner(_) => MatchOverrides::new(),
+        }
+    }
+
+    /// Rewrites the filter into an equivalent one that is cheaper to evaluate, if possible.
+    ///
+    /// Regex filters that only check for extensions or a leading directory are
+    /// turned into extension or prefix filters, see [`RegexFilter::simplify`] for the exact conditions. The filters
+    /// inside negated and combined filters are optimized as well, all other
+    /// filters are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::PathFilter;
+    ///
+    /// let filter = PathFilter::new_extension(".rs").optimize();
+    /// assert!(matches!(filter, PathFilter::Extension(_)));
+    /// ```
+    pub fn optimize(self) -> Self {
+        match self {
+            #[cfg(feature = "regex")]
+            PathFilter::Regex(x) => x.simplify().unwrap_or(PathFilter::Regex(x)),
+            PathFilter::Not(x) => x.optimize().negate(),
+            PathFilter::And(x) => {
+                PathFilter::And(x.into_iter().map(PathFilter::optimize).collect())
+            }
+            PathFilter::Or(x) => PathFilter::Or(x.into_iter().map(PathFilter::optimize).collect()),
+            x => x,
+        }
+    }
 }
 
 #[cfg(feature = "regex")]
@@ -114,20 +739,137 @@ impl PathFilter {
     }
 }
 
+#[cfg(feature = "glob")]
+impl PathFilter {
+    /// Creates a new `PathFilter` based on a glob.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pathfilter::PathFilter;
+    ///
+    /// let filter = PathFilter::new_glob("**/target/**").unwrap();
+    /// ```
+    /// # Errors
+    /// If the glob is invalid, an error is returned.
+    pub fn new_glob(glob: &str) -> Result<Self, GlobError> {
+        Ok(GlobFilter::new(glob)?.into())
+    }
+}
+
 impl IgnorePath for PathFilter {
     fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
         match self {
             PathFilter::Extension(x) => x.ignore(path),
             PathFilter::Extensions(x) => x.ignore(path),
+            PathFilter::Component(x) => x.ignore(path),
+            PathFilter::FileName(x) => x.ignore(path),
+            PathFilter::Stem(x) => x.ignore(path),
+            PathFilter::Prefix(x) => x.ignore(path),
+            PathFilter::Wildcard(x) => x.ignore(path),
+            PathFilter::And(x) => chain::all_ignore(x, &MatchCandidate::new(path.as_ref())),
+            PathFilter::Or(x) => x.ignore(path),
+            PathFilter::Not(x) => !x.ignore(path),
             #[cfg(feature = "regex")]
             PathFilter::Regex(x) => x.ignore(path),
+            #[cfg(feature = "glob")]
+            PathFilter::Glob(x) => x.ignore(path),
+            #[cfg(all(feature = "io", unix))]
+            PathFilter::Owner(x) => x.ignore(path),
+        }
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        match self {
+            PathFilter::Extension(x) => x.ignore_candidate(candidate),
+            PathFilter::Extensions(x) => x.ignore_candidate(candidate),
+            PathFilter::Component(x) => x.ignore_candidate(candidate),
+            PathFilter::FileName(x) => x.ignore_candidate(candidate),
+            PathFilter::Stem(x) => x.ignore_candidate(candidate),
+            PathFilter::Prefix(x) => x.ignore_candidate(candidate),
+            PathFilter::Wildcard(x) => x.ignore_candidate(candidate),
+            PathFilter::And(x) => chain::all_ignore(x, candidate),
+            PathFilter::Or(x) => x.ignore_candidate(candidate),
+            PathFilter::Not(x) => !x.ignore_candidate(candidate),
+            #[cfg(feature = "regex")]
+            PathFilter::Regex(x) => x.ignore_candidate(candidate),
+            #[cfg(feature = "glob")]
+            PathFilter::Glob(x) => x.ignore_candidate(candidate),
+            #[cfg(all(feature = "io", unix))]
+            PathFilter::Owner(x) => x.ignore_candidate(candidate),
         }
     }
 }
 
-impl<T: AsRef<[PathFilter]>> IgnorePath for T {
+// Collections of filters ignore a path if any of their filters does.
+//
+// These used to be a single blanket impl for every `T: AsRef<[PathFilter]>`, which
+// prevented downstream crates from implementing `IgnorePath` for their own types
+// that also implement `AsRef<[PathFilter]>`. Code that relied on it for other
+// containers, e.g. `Box<[PathFilter]>` or `Rc<[PathFilter]>`, can call
+// `filters.as_ref().ignore(path)` to use the slice impl instead.
+
+impl<F: IgnorePath> IgnorePath for [F] {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        self.iter().any(|filter| filter.ignore_candidate(candidate))
+    }
+}
+
+impl<F: IgnorePath, const N: usize> IgnorePath for [F; N] {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.as_slice().ignore(path)
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        self.as_slice().ignore_candidate(candidate)
+    }
+}
+
+impl<F: IgnorePath> IgnorePath for Vec<F> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.as_slice().ignore(path)
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        self.as_slice().ignore_candidate(candidate)
+    }
+}
+
+impl<T: IgnorePath + ?Sized> IgnorePath for &T {
     fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
-        self.as_ref().iter().any(|filter| filter.ignore(&path))
+        (**self).ignore(path)
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        (**self).ignore_candidate(candidate)
+    }
+
+    fn ignore_any<I, P>(&self, paths: I) -> bool
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        (**self).ignore_any(paths)
+    }
+
+    fn ignore_all<I, P>(&self, paths: I) -> bool
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        (**self).ignore_all(paths)
+    }
+
+    fn ignore_iter<I, P>(&self, paths: I) -> impl Iterator<Item = bool>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        (**self).ignore_iter(paths)
     }
 }
 
@@ -173,6 +915,116 @@ mod tests {
         assert!(!filter.ignore(Path::new("src/main.png")));
     }
 
+    #[test]
+    fn negate() {
+        use crate::IgnorePath;
+
+        let filter = PathFilter::new_extension(".rs").negate();
+        assert!(matches!(filter, PathFilter::Not(_)));
+        assert!(filter.ignore(Path::new("README.md")));
+        assert!(!filter.ignore(Path::new("src/lib.rs")));
+
+        let filter = filter.negate();
+        assert!(matches!(filter, PathFilter::Extension(_)));
+        assert!(filter.ignore(Path::new("src/lib.rs")));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn optimize_regex() {
+        use regex::Regex;
+
+        let filter = PathFilter::new_regex(Regex::new("^.*\\.rs$").unwrap()).optimize();
+        assert!(matches!(filter, PathFilter::Extension(_)));
+
+        let filter = PathFilter::new_regex(Regex::new("^src/lib.rs$").unwrap()).optimize();
+        assert!(matches!(filter, PathFilter::Regex(_)));
+    }
+
+    #[cfg(all(feature = "regex", feature = "serde"))]
+    #[test]
+    fn deserialize_legacy_variant_names() {
+        use crate::IgnorePath;
+        use serde::{
+            de::value::{Error, MapAccessDeserializer, MapDeserializer},
+            Deserialize,
+        };
+        use std::collections::BTreeMap;
+
+        for tag in ["Regex", "regex"] {
+            let content = BTreeMap::from([("regex", "^src/lib.rs$")]);
+            let map = MapDeserializer::<_, Error>::new([(tag, content)].into_iter());
+            let filter = PathFilter::deserialize(MapAccessDeserializer::new(map)).unwrap();
+            assert!(matches!(filter, PathFilter::Regex(_)));
+            assert!(filter.ignore(Path::new("src/lib.rs")));
+        }
+    }
+
+    #[test]
+    fn collections() {
+        use crate::IgnorePath;
+        use std::rc::Rc;
+
+        let array = [
+            PathFilter::new_extension(".rs"),
+            PathFilter::new_extension(".md"),
+        ];
+        let vec = array.to_vec();
+        let slice: &[PathFilter] = &vec;
+        let rc: Rc<[PathFilter]> = vec.clone().into();
+        fn ignored_by<F: IgnorePath>(filter: F, path: &str) -> bool {
+            filter.ignore(Path::new(path))
+        }
+
+        for path in ["src/lib.rs", "README.md"] {
+            assert!(array.ignore(Path::new(path)));
+            assert!(vec.ignore(Path::new(path)));
+            assert!(slice.ignore(Path::new(path)));
+            assert!(ignored_by(&vec, path));
+            assert!(ignored_by(slice, path));
+            assert!(rc.as_ref().ignore(Path::new(path)));
+        }
+        assert!(!slice.ignore(Path::new("Cargo.toml")));
+        let empty: [PathFilter; 0] = [];
+        assert!(!empty.ignore(Path::new("src/lib.rs")));
+    }
+
+    #[test]
+    fn batch() {
+        use crate::IgnorePath;
+
+        let filters = vec![
+            PathFilter::new_extension(".rs"),
+            PathFilter::new_extension(".md"),
+        ];
+        assert!(filters.ignore_any(["Cargo.toml", "README.md"]));
+        assert!(!filters.ignore_any(["Cargo.toml"]));
+        assert!(!filters.ignore_any(Vec::<&Path>::new()));
+        assert!(filters.ignore_all(["src/lib.rs", "README.md"]));
+        assert!(!filters.ignore_all(["src/lib.rs", "Cargo.toml"]));
+        assert!(filters.ignore_all(Vec::<&Path>::new()));
+
+        let by_ref = &filters;
+        let ignored: Vec<bool> = by_ref
+            .ignore_iter(["src/lib.rs", "Cargo.toml", "README.md"])
+            .collect();
+        assert_eq!(ignored, [true, false, true]);
+    }
+
+    #[test]
+    fn collections_of_any_filter() {
+        use crate::{ExtensionFilter, IgnorePath};
+
+        let filters = vec![ExtensionFilter::new(".rs"), ExtensionFilter::new(".md")];
+        assert!(filters.ignore(Path::new("src/lib.rs")));
+        assert!(filters.as_slice().ignore(Path::new("README.md")));
+        assert!(!filters.ignore(Path::new("Cargo.toml")));
+
+        let nested = [filters.clone(), vec![ExtensionFilter::new(".toml")]];
+        assert!(nested.ignore(Path::new("Cargo.toml")));
+        assert!(!Vec::<ExtensionFilter>::new().ignore(Path::new("src/lib.rs")));
+    }
+
     #[cfg(feature = "regex")]
     #[test]
     fn regex_extension_combined_filter() {
@@ -190,4 +1042,191 @@ mod tests {
         assert!(!filters.ignore(Path::new("src/main.rs")));
         assert!(filters.ignore(Path::new("src/Program.cs")));
     }
+
+    #[cfg(feature = "serde")]
+    fn serde_samples() -> Vec<PathFilter> {
+        use crate::{MatchOptions, MatchOverrides};
+
+        let options = MatchOptions::new().case_insensitive(true);
+        let overrides = MatchOverrides::new().normalize_separators(true);
+        #[cfg_attr(not(feature = "regex"), allow(unused_mut))]
+        let mut samples = vec![
+            PathFilter::new_extension(".rs"),
+            PathFilter::new_extension(".rs").with_options(options),
+            PathFilter::new_extensions([".rs", ".md"]),
+            PathFilter::new_extensions([".rs", ".md"]).with_overrides(overrides),
+            PathFilter::new_components(["target", ".git"]),
+            PathFilter::new_prefixes(["build/"]),
+            PathFilter::new_file_names(["Thumbs.db"]),
+            PathFilter::new_stems(["README"]),
+            PathFilter::new_wildcard("*.tmp"),
+            PathFilter::new_extension(".rs").negate(),
+            PathFilter::new_and([PathFilter::new_extension(".js")]),
+            PathFilter::new_or([PathFilter::new_extension(".js")]),
+        ];
+        #[cfg(feature = "regex")]
+        samples.extend([
+            PathFilter::new_regex(regex::Regex::new("^src/.*\\.rs$").unwrap()),
+            PathFilter::new_regex(regex::Regex::new("^src/").unwrap())
+                .with_options(options)
+                .with_overrides(overrides),
+        ]);
+        #[cfg(feature = "glob")]
+        samples.push(
+            PathFilter::new_glob("src/**/*.rs")
+                .unwrap()
+                .with_options(options),
+        );
+        samples
+    }
+
+    /// The serialized form is an interface, so changes to it have to be deliberate.
+    ///
+    /// The snapshots are of the in-crate `Value` tree rather than JSON, TOML or
+    /// YAML text, as none of those formats are dependencies.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_format() {
+        use crate::value::{to_value, Value};
+
+        let str = |s: &str| Value::Str(s.to_string());
+        let map = |entries: Vec<(&str, Value)>| {
+            Value::Map(entries.into_iter().map(|(k, v)| (str(k), v)).collect())
+        };
+        let options = map(vec![
+            ("case_insensitive", Value::Bool(true)),
+            ("normalize_separators", Value::Bool(false)),
+        ]);
+        let overrides = map(vec![(
+            "normalize_separators",
+            Value::Some(Box::new(Value::Bool(true))),
+        )]);
+        let extensions = Value::Seq(vec![str(".md"), str(".rs")]);
+
+        #[cfg_attr(not(feature = "regex"), allow(unused_mut))]
+        let mut expected = vec![
+            map(vec![(
+                "Extension",
+                map(vec![("extension", Value::os_str("rs"))]),
+            )]),
+            map(vec![(
+                "Extension",
+                map(vec![
+                    ("extension", Value::os_str("rs")),
+                    ("options", options.clone()),
+                ]),
+            )]),
+            map(vec![("Extensions", extensions.clone())]),
+            map(vec![(
+                "Extensions",
+                map(vec![
+                    ("extensions", extensions),
+                    ("overrides", overrides.clone()),
+                ]),
+            )]),
+            map(vec![(
+                "Component",
+                map(vec![(
+                    "components",
+                    Value::Seq(vec![str(".git"), str("target")]),
+                )]),
+            )]),
+            map(vec![(
+                "Prefix",
+                map(vec![("prefixes", Value::Seq(vec![str("build/")]))]),
+            )]),
+            map(vec![(
+                "FileName",
+                map(vec![("names", Value::Seq(vec![str("Thumbs.db")]))]),
+            )]),
+            map(vec![(
+                "Stem",
+                map(vec![("stems", Value::Seq(vec![str("README")]))]),
+            )]),
+            map(vec![("Wildcard", map(vec![("pattern", str("*.tmp"))]))]),
+            map(vec![(
+                "Not",
+                map(vec![(
+                    "Extension",
+                    map(vec![("extension", Value::os_str("rs"))]),
+                )]),
+            )]),
+            map(vec![(
+                "And",
+                Value::Seq(vec![map(vec![(
+                    "Extension",
+                    map(vec![("extension", Value::os_str("js"))]),
+                )])]),
+            )]),
+            map(vec![(
+                "Or",
+                Value::Seq(vec![map(vec![(
+                    "Extension",
+                    map(vec![("extension", Value::os_str("js"))]),
+                )])]),
+            )]),
+        ];
+        #[cfg(feature = "regex")]
+        expected.extend([
+            map(vec![("Regex", map(vec![("regex", str("^src/.*\\.rs$"))]))]),
+            map(vec![(
+                "Regex",
+                map(vec![
+                    ("regex", str("^src/")),
+                    ("options", options.clone()),
+                    ("overrides", overrides),
+                ]),
+            )]),
+        ]);
+        #[cfg(feature = "glob")]
+        expected.push(map(vec![(
+            "Glob",
+            map(vec![("glob", str("src/**/*.rs")), ("options", options)]),
+        )]));
+
+        let serialized: Vec<Value> = serde_samples()
+            .iter()
+            .map(|filter| to_value(filter).unwrap())
+            .collect();
+        assert_eq!(serialized, expected);
+    }
+
+    /// Checks that a round trip keeps the decisions of every sample, over a fixed
+    /// list of paths rather than generated ones.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use crate::{value::to_value, IgnorePath};
+        use serde::{
+            de::{value::Error, IntoDeserializer},
+            Deserialize,
+        };
+
+        let paths = [
+            "src/lib.rs",
+            "src/LIB.RS",
+            "SRC/main.rs",
+            "src\\lib.rs",
+            "README.md",
+            "docs/guide.MD",
+            "Cargo.toml",
+            ".rs",
+            "",
+        ];
+        for filter in serde_samples() {
+            let value = to_value(&filter).unwrap();
+            let deserialized = PathFilter::deserialize(
+                IntoDeserializer::<Error>::into_deserializer(value.clone()),
+            )
+            .unwrap();
+            assert_eq!(to_value(&deserialized).unwrap(), value);
+            for path in paths {
+                assert_eq!(
+                    deserialized.ignore(path),
+                    filter.ignore(path),
+                    "{filter:?} on {path:?}"
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,115 @@
+use crate::IgnorePath;
+use std::{
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A stateful filter that keeps the first `limit` paths it is asked about and ignores the rest.
+///
+/// Every call to [`ignore`](IgnorePath::ignore) counts, so ask it last, after all
+/// other filters kept the path. The filter can be shared between threads.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{ExtensionFilter, IgnorePath, LimitFilter};
+///
+/// let filter = ExtensionFilter::new(".o");
+/// let limit = LimitFilter::new(2);
+/// let preview: Vec<_> = ["a.rs", "a.o", "b.rs", "c.rs"]
+///     .into_iter()
+///     .filter(|path| !filter.ignore(path) && !limit.ignore(path))
+///     .collect();
+/// assert_eq!(preview, ["a.rs", "b.rs"]);
+/// assert!(limit.is_exhausted());
+/// ```
+#[derive(Debug)]
+pub struct LimitFilter {
+    limit: usize,
+    kept: AtomicUsize,
+}
+
+impl Clone for LimitFilter {
+    fn clone(&self) -> Self {
+        LimitFilter {
+            limit: self.limit,
+            kept: AtomicUsize::new(self.kept()),
+        }
+    }
+}
+
+impl IgnorePath for LimitFilter {
+    fn ignore<P: AsRef<Path>>(&self, _path: P) -> bool {
+        self.kept
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |kept| {
+                (kept < self.limit).then_some(kept + 1)
+            })
+            .is_err()
+    }
+}
+
+impl LimitFilter {
+    /// Creates a new filter that keeps the first `limit` paths.
+    pub fn new(limit: usize) -> Self {
+        LimitFilter {
+            limit,
+            kept: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of paths that are kept at most.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the number of paths kept so far.
+    pub fn kept(&self) -> usize {
+        self.kept.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if every further path will be ignored.
+    pub fn is_exhausted(&self) -> bool {
+        self.kept() >= self.limit
+    }
+
+    /// Starts counting from zero again.
+    pub fn reset(&self) {
+        self.kept.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IgnorePath, LimitFilter};
+
+    #[test]
+    fn limit_filter() {
+        let limit = LimitFilter::new(2);
+        assert!(!limit.ignore("a"));
+        assert!(!limit.ignore("b"));
+        assert!(limit.ignore("c"));
+        assert!(limit.ignore("d"));
+        assert_eq!(limit.kept(), 2);
+
+        let clone = limit.clone();
+        limit.reset();
+        assert!(!limit.ignore("a"));
+        assert!(clone.is_exhausted());
+
+        assert!(LimitFilter::new(0).ignore("a"));
+    }
+
+    #[test]
+    fn limit_filter_threads() {
+        let limit = LimitFilter::new(100);
+        let kept = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| (0..50).filter(|_| !limit.ignore("a")).count()))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .sum::<usize>()
+        });
+        assert_eq!(kept, 100);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    content::{io_error_policy_methods, read_prefix},
+    IgnorePath, IoErrorPolicy,
+};
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    path::Path,
+};
+
+const CACHEDIR_TAG: &str = "CACHEDIR.TAG";
+const CACHEDIR_SIGNATURE: &[u8] = b"Signature: 8a477f597d28d172789f06886806bc55";
+
+/// A filter that matches directories containing a marker file, like `.nobackup`.
+///
+/// [`cachedir_tag`](Self::cachedir_tag) also matches cache directories tagged
+/// with a `CACHEDIR.TAG` file as described in the
+/// [Cache Directory Tagging Specification](https://bford.info/cachedir/), which
+/// must start with the signature.
+///
+/// Only the directory itself is matched, which is enough if it is not descended
+/// into. Use [`with_ancestors`](Self::with_ancestors) to also match everything
+/// inside it.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{IgnorePath, MarkerFilter};
+///
+/// let filter = MarkerFilter::cachedir_tag().with_marker(".nobackup");
+/// assert!(filter.ignore("target"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MarkerFilter {
+    markers: Vec<OsString>,
+    cachedir_tag: bool,
+    ancestors: bool,
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for MarkerFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        if self.ancestors {
+            path.ancestors()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .any(|dir| self.on_error.resolve(self.check(dir)))
+        } else {
+            self.on_error.resolve(self.check(path))
+        }
+    }
+}
+
+impl MarkerFilter {
+    /// Creates a new filter for directories containing any of the marker files.
+    pub fn new<S, T>(markers: T) -> Self
+    where
+        S: AsRef<OsStr>,
+        T: IntoIterator<Item = S>,
+    {
+        MarkerFilter {
+            markers: markers
+                .into_iter()
+                .map(|marker| marker.as_ref().to_os_string())
+                .collect(),
+            ..MarkerFilter::default()
+        }
+    }
+
+    /// Creates a new filter for directories tagged with a valid `CACHEDIR.TAG`.
+    pub fn cachedir_tag() -> Self {
+        MarkerFilter {
+            cachedir_tag: true,
+            ..MarkerFilter::default()
+        }
+    }
+
+    /// Adds a marker file.
+    pub fn with_marker<S: AsRef<OsStr>>(mut self, marker: S) -> Self {
+        self.markers.push(marker.as_ref().to_os_string());
+        self
+    }
+
+    /// Sets whether paths inside a marked directory are matched too.
+    ///
+    /// This checks every ancestor of a path, so use it with relative paths.
+    pub fn with_ancestors(mut self, ancestors: bool) -> Self {
+        self.ancestors = ancestors;
+        self
+    }
+
+    io_error_policy_methods!("directories that cannot be checked");
+
+    /// Returns whether the path is a directory containing a marker.
+    ///
+    /// # Errors
+    /// If the path or a marker cannot be checked, an error is returned.
+    /// A path that does not exist is not an error.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let dir = path.as_ref();
+        if !dir.is_dir() {
+            return Ok(false);
+        }
+
+        for marker in &self.markers {
+            if dir.join(marker).try_exists()? {
+                return Ok(true);
+            }
+        }
+
+        if self.cachedir_tag {
+            let tag = dir.join(CACHEDIR_TAG);
+            if tag.is_file() && read_prefix(&tag, CACHEDIR_SIGNATURE.len())? == CACHEDIR_SIGNATURE {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{content::tests::TempDir, IgnorePath, MarkerFilter};
+    use std::fs;
+
+    #[test]
+    fn marker_filter() {
+        let dir = TempDir::new();
+        for name in ["cache", "fake", "media", "plain"] {
+            fs::create_dir(dir.path().join(name)).unwrap();
+        }
+        dir.write(
+            "cache/CACHEDIR.TAG",
+            "Signature: 8a477f597d28d172789f06886806bc55\n# comment\n",
+        );
+        dir.write("fake/CACHEDIR.TAG", "not a cache\n");
+        dir.write("media/.nomedia", "");
+        let file = dir.write("cache/data.bin", "");
+
+        let filter = MarkerFilter::cachedir_tag().with_marker(".nomedia");
+        assert!(filter.ignore(dir.path().join("cache")));
+        assert!(!filter.ignore(dir.path().join("fake")));
+        assert!(filter.ignore(dir.path().join("media")));
+        assert!(!filter.ignore(dir.path().join("plain")));
+        assert!(!filter.ignore(dir.path().join("missing")));
+        assert!(!filter.ignore(&file));
+        assert!(filter.with_ancestors(true).ignore(&file));
+
+        let filter = MarkerFilter::new([".nomedia"]);
+        assert!(!filter.ignore(dir.path().join("cache")));
+        assert!(filter.ignore(dir.path().join("media")));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::IgnorePath;
+use std::path::Path;
+
+/// Common extensions and the MIME types they are guessed as, sorted by extension.
+const TYPES: &[(&str, &str)] = &[
+    ("7z", "application/x-7z-compressed"),
+    ("aac", "audio/aac"),
+    ("avi", "video/x-msvideo"),
+    ("avif", "image/avif"),
+    ("bmp", "image/bmp"),
+    ("bz2", "application/x-bzip2"),
+    ("c", "text/x-c"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("epub", "application/epub+zip"),
+    ("flac", "audio/flac"),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("heic", "image/heic"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("ico", "image/x-icon"),
+    ("jar", "application/java-archive"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("js", "text/javascript"),
+    ("json", "application/json"),
+    ("m4a", "audio/mp4"),
+    ("md", "text/markdown"),
+    ("mkv", "video/x-matroska"),
+    ("mov", "video/quicktime"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("mpeg", "video/mpeg"),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("oga", "audio/ogg"),
+    ("ogg", "audio/ogg"),
+    ("ogv", "video/ogg"),
+    ("opus", "audio/opus"),
+    ("otf", "font/otf"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("ppt", "application/vnd.ms-powerpoint"),
+    (
+        "pptx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+    ("psd", "image/vnd.adobe.photoshop"),
+    ("rar", "application/vnd.rar"),
+    ("rs", "text/x-rust"),
+    ("rtf", "application/rtf"),
+    ("svg", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("toml", "application/toml"),
+    ("ttf", "font/ttf"),
+    ("txt", "text/plain"),
+    ("wasm", "application/wasm"),
+    ("wav", "audio/wav"),
+    ("webm", "video/webm"),
+    ("webp", "image/webp"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("xls", "application/vnd.ms-excel"),
+    (
+        "xlsx",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    ),
+    ("xml", "application/xml"),
+    ("xz", "application/x-xz"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("zip", "application/zip"),
+    ("zst", "application/zstd"),
+];
+
+/// A filter that matches paths whose MIME type, guessed from the extension, matches a pattern.
+///
+/// A pattern is either a full type, like `application/zip`, or a type with any
+/// subtype, like `image/*`; a pattern without a `/` is the same as `type/*`.
+/// Patterns and extensions are compared case-insensitively. The guess uses a
+/// built-in table of common extensions, see [`guess`](Self::guess); the contents
+/// of the file are not read, and paths with an unknown extension never match.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, MimeTypeFilter};
+/// use std::path::Path;
+///
+/// let filter = MimeTypeFilter::new(["image/*", "application/zip"]);
+/// assert!(filter.ignore(Path::new("photos/IMG_0001.JPG")));
+/// assert!(filter.ignore(Path::new("downloads/archive.zip")));
+/// assert!(!filter.ignore(Path::new("notes.txt")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MimeTypeFilter {
+    patterns: Vec<String>,
+}
+
+impl IgnorePath for MimeTypeFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        MimeTypeFilter::guess(path).is_some_and(|mime| self.matches_type(mime))
+    }
+}
+
+impl MimeTypeFilter {
+    /// Creates a new filter for a list of MIME type patterns.
+    pub fn new<I, S>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        MimeTypeFilter {
+            patterns: patterns.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns the patterns of this filter.
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Guesses the MIME type of a path from its extension.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::MimeTypeFilter;
+    ///
+    /// assert_eq!(MimeTypeFilter::guess("logo.PNG"), Some("image/png"));
+    /// assert_eq!(MimeTypeFilter::guess("Makefile"), None);
+    /// ```
+    pub fn guess<P: AsRef<Path>>(path: P) -> Option<&'static str> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        TYPES
+            .binary_search_by_key(&extension.as_str(), |&(ext, _)| ext)
+            .ok()
+            .map(|index| TYPES[index].1)
+    }
+
+    /// Returns whether a MIME type matches one of the patterns.
+    pub fn matches_type(&self, mime: &str) -> bool {
+        let (kind, subtype) = mime.split_once('/').unwrap_or((mime, ""));
+        self.patterns.iter().any(|pattern| {
+            let (pattern_kind, pattern_subtype) = pattern.split_once('/').unwrap_or((pattern, "*"));
+            pattern_kind.eq_ignore_ascii_case(kind)
+                && (pattern_subtype == "*" || pattern_subtype.eq_ignore_ascii_case(subtype))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mime::TYPES, IgnorePath, MimeTypeFilter};
+    use std::path::Path;
+
+    #[test]
+    fn table_is_sorted() {
+        assert!(TYPES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn patterns() {
+        let filter = MimeTypeFilter::new(["IMAGE/*", "video", "application/zip"]);
+        assert!(filter.ignore(Path::new("a.png")));
+        assert!(filter.ignore(Path::new("clip.mkv")));
+        assert!(filter.ignore(Path::new("a.ZIP")));
+        assert!(!filter.ignore(Path::new("a.gz")));
+        assert!(!filter.ignore(Path::new("a.unknown")));
+        assert!(!filter.ignore(Path::new("png")));
+
+        assert!(MimeTypeFilter::new(["text/x-rust"]).ignore(Path::new("src/lib.rs")));
+        assert!(!MimeTypeFilter::new(["text/x"]).ignore(Path::new("src/lib.rs")));
+    }
+}
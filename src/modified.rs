@@ -0,0 +1,227 @@
+use crate::{content::io_error_policy_methods, IgnorePath, IoErrorPolicy};
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+/// A filter that matches files last modified before a point in time.
+///
+/// [`with_newer`](Self::with_newer) matches files modified after it instead. Files
+/// modified exactly at that time are never matched. The modification time is read
+/// with [`fs::metadata`], so symlinks are followed. Directories and other entries
+/// that are not files are never matched, as their modification time says nothing
+/// about the files inside them.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{IgnorePath, ModifiedSinceFilter};
+/// use std::time::{Duration, SystemTime};
+///
+/// let last_build = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+/// let filter = ModifiedSinceFilter::new(last_build);
+/// assert!(filter.ignore("src/unchanged.rs"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ModifiedSinceFilter {
+    time: SystemTime,
+    newer: bool,
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for ModifiedSinceFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+impl ModifiedSinceFilter {
+    /// Creates a new filter for files last modified before `time`.
+    pub fn new(time: SystemTime) -> Self {
+        ModifiedSinceFilter {
+            time,
+            newer: false,
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    /// Sets whether files modified after the time are matched instead of files modified before it.
+    pub fn with_newer(mut self, newer: bool) -> Self {
+        self.newer = newer;
+        self
+    }
+
+    io_error_policy_methods!(
+        "paths whose metadata cannot be read",
+        "e.g. because they do not exist"
+    );
+
+    /// Returns the time modification times are compared to.
+    pub fn time(&self) -> SystemTime {
+        self.time
+    }
+
+    /// Returns whether a file last modified at `modified` is matched.
+    pub fn matches_time(&self, modified: SystemTime) -> bool {
+        if self.newer {
+            modified > self.time
+        } else {
+            modified < self.time
+        }
+    }
+
+    /// Returns whether the path is a file with a matching modification time.
+    ///
+    /// # Errors
+    /// If the metadata or the modification time cannot be read, an error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let metadata = fs::metadata(path)?;
+        Ok(metadata.is_file() && self.matches_time(metadata.modified()?))
+    }
+}
+
+/// A filter that matches files by the time since they were last modified.
+///
+/// Ages are measured from a reference time, which is captured when the filter is
+/// created and can be replaced with [`with_now`](Self::with_now), e.g. for tests.
+/// Files modified after the reference time have an age of zero. Like
+/// [`ModifiedSinceFilter`], only files are matched.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{AgeFilter, IgnorePath};
+/// use std::time::Duration;
+///
+/// const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+///
+/// let filter = AgeFilter::newer_than(7 * DAY);
+/// assert!(filter.ignore("downloads/today.zip"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AgeFilter {
+    age: Duration,
+    newer: bool,
+    now: SystemTime,
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for AgeFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+impl AgeFilter {
+    /// Creates a new filter for files last modified more than `age` ago.
+    pub fn older_than(age: Duration) -> Self {
+        AgeFilter {
+            age,
+            newer: false,
+            now: SystemTime::now(),
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    /// Creates a new filter for files last modified less than `age` ago.
+    pub fn newer_than(age: Duration) -> Self {
+        AgeFilter {
+            newer: true,
+            ..AgeFilter::older_than(age)
+        }
+    }
+
+    /// Sets the time ages are measured from.
+    pub fn with_now(mut self, now: SystemTime) -> Self {
+        self.now = now;
+        self
+    }
+
+    io_error_policy_methods!(
+        "paths whose metadata cannot be read",
+        "e.g. because they do not exist"
+    );
+
+    /// Returns the time ages are measured from.
+    pub fn now(&self) -> SystemTime {
+        self.now
+    }
+
+    /// Returns whether a file last modified at `modified` is matched.
+    pub fn matches_time(&self, modified: SystemTime) -> bool {
+        let age = self.now.duration_since(modified).unwrap_or_default();
+        if self.newer {
+            age < self.age
+        } else {
+            age > self.age
+        }
+    }
+
+    /// Returns whether the path is a file with a matching age.
+    ///
+    /// # Errors
+    /// If the metadata or the modification time cannot be read, an error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let metadata = fs::metadata(path)?;
+        Ok(metadata.is_file() && self.matches_time(metadata.modified()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        content::tests::TempDir, AgeFilter, IgnorePath, IoErrorPolicy, ModifiedSinceFilter,
+    };
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn modified_since_filter() {
+        let dir = TempDir::new();
+        let file = dir.write("file", "");
+        let modified = file.metadata().unwrap().modified().unwrap();
+        let before = modified - Duration::from_secs(60);
+        let after = modified + Duration::from_secs(60);
+
+        assert!(ModifiedSinceFilter::new(after).ignore(&file));
+        assert!(!ModifiedSinceFilter::new(before).ignore(&file));
+        assert!(!ModifiedSinceFilter::new(modified).ignore(&file));
+        assert!(!ModifiedSinceFilter::new(after).ignore(dir.path()));
+
+        let filter = ModifiedSinceFilter::new(before).with_newer(true);
+        assert!(filter.ignore(&file));
+        assert!(!filter.with_newer(false).ignore(&file));
+        assert!(!ModifiedSinceFilter::new(after)
+            .with_newer(true)
+            .ignore(&file));
+
+        let missing = dir.path().join("missing");
+        let filter = ModifiedSinceFilter::new(SystemTime::now());
+        assert!(!filter.ignore(&missing));
+        assert!(filter
+            .with_io_error_policy(IoErrorPolicy::Ignore)
+            .ignore(&missing));
+    }
+
+    #[test]
+    fn age_filter() {
+        const HOUR: Duration = Duration::from_secs(60 * 60);
+
+        let now = SystemTime::UNIX_EPOCH + 1000 * HOUR;
+        let filter = AgeFilter::older_than(24 * HOUR).with_now(now);
+        assert_eq!(filter.now(), now);
+        assert!(filter.matches_time(now - 25 * HOUR));
+        assert!(!filter.matches_time(now - 24 * HOUR));
+        assert!(!filter.matches_time(now - HOUR));
+        assert!(!filter.matches_time(now + HOUR));
+
+        let filter = AgeFilter::newer_than(24 * HOUR).with_now(now);
+        assert!(!filter.matches_time(now - 25 * HOUR));
+        assert!(filter.matches_time(now - HOUR));
+        assert!(filter.matches_time(now + HOUR));
+
+        let dir = TempDir::new();
+        let file = dir.write("file", "");
+        assert!(AgeFilter::newer_than(HOUR).ignore(&file));
+        assert!(!AgeFilter::older_than(HOUR).ignore(&file));
+        assert!(!AgeFilter::newer_than(HOUR).ignore(dir.path()));
+    }
+}
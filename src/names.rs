@@ -0,0 +1,82 @@
+use crate::{extension::unicode_fold, MatchOptions};
+use std::collections::HashSet;
+
+/// A set of names that can be looked up with the case sensitivity of [`MatchOptions`].
+///
+/// The folded forms are computed once, so options can change freely afterwards.
+/// With the `serde` feature the set is serialized as a sorted list.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct NameSet {
+    names: HashSet<String>,
+    ascii: HashSet<String>,
+    unicode: HashSet<String>,
+}
+
+impl NameSet {
+    pub(crate) fn insert(&mut self, name: String) {
+        self.ascii.insert(name.to_ascii_lowercase());
+        self.unicode.insert(unicode_fold(&name).collect());
+        self.names.insert(name);
+    }
+
+    /// Returns `true` if the set contains the name, folding it into `buffer` if needed.
+    pub(crate) fn contains(&self, name: &str, options: MatchOptions, buffer: &mut String) -> bool {
+        if !options.is_case_insensitive() {
+            self.names.contains(name)
+        } else if options.is_unicode_case() {
+            buffer.clear();
+            buffer.extend(unicode_fold(name));
+            self.unicode.contains(buffer.as_str())
+        } else if name.bytes().any(|b| b.is_ascii_uppercase()) {
+            buffer.clear();
+            buffer.push_str(name);
+            buffer.make_ascii_lowercase();
+            self.ascii.contains(buffer.as_str())
+        } else {
+            self.ascii.contains(name)
+        }
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+}
+
+/// Returns the part of a file name after the last `\` if the options normalize separators.
+///
+/// On platforms where `\` is not a separator, a Windows path like `a\b.txt` is a
+/// single file name, of which only `b.txt` is the name meant.
+pub(crate) fn file_name(name: &str, options: MatchOptions) -> &str {
+    match name.rsplit_once('\\') {
+        Some((_, name)) if options.normalizes_separators() => name,
+        _ => name,
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for NameSet {
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        let mut set = NameSet::default();
+        for name in iter {
+            set.insert(name.into());
+        }
+        set
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for NameSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut names: Vec<_> = self.iter().collect();
+        names.sort_unstable();
+        names.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for NameSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
@@ -0,0 +1,88 @@
+use crate::IgnorePath;
+use std::path::Path;
+
+/// A filter that matches paths whose file name has no extension, like `Makefile`
+/// or `target/release/app`.
+///
+/// Names with an empty extension, like `notes.`, and dotfiles, like `.bashrc`,
+/// have no extension either, see [`Path::extension`]. Paths without a file name,
+/// like `..`, are never matched. [`inverted`](Self::inverted) matches paths with
+/// an extension instead, to keep only those without one.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, NoExtensionFilter};
+/// use std::path::Path;
+///
+/// let filter = NoExtensionFilter::new();
+/// assert!(filter.ignore(Path::new("target/release/app")));
+/// assert!(!filter.ignore(Path::new("target/release/app.d")));
+///
+/// let filter = NoExtensionFilter::inverted();
+/// assert!(!filter.ignore(Path::new("target/release/app")));
+/// assert!(filter.ignore(Path::new("target/release/app.d")));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NoExtensionFilter {
+    inverted: bool,
+}
+
+impl IgnorePath for NoExtensionFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        if path.file_name().is_none() {
+            return false;
+        }
+        let has_extension = path.extension().is_some_and(|ext| !ext.is_empty());
+        has_extension == self.inverted
+    }
+}
+
+impl NoExtensionFilter {
+    /// Creates a new filter for paths without an extension.
+    pub fn new() -> Self {
+        NoExtensionFilter { inverted: false }
+    }
+
+    /// Creates a new filter for paths with an extension.
+    pub fn inverted() -> Self {
+        NoExtensionFilter { inverted: true }
+    }
+
+    /// Returns `true` if the filter matches paths with an extension.
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IgnorePath, NoExtensionFilter};
+    use std::path::Path;
+
+    #[test]
+    fn no_extension() {
+        let filter = NoExtensionFilter::new();
+        for path in ["Makefile", "bin/app", ".bashrc", "notes.", "dir.d/LICENSE"] {
+            assert!(filter.ignore(Path::new(path)), "{path}");
+            assert!(
+                !NoExtensionFilter::inverted().ignore(Path::new(path)),
+                "{path}"
+            );
+        }
+        for path in ["a.rs", "archive.tar.gz", ".config.toml"] {
+            assert!(!filter.ignore(Path::new(path)), "{path}");
+            assert!(
+                NoExtensionFilter::inverted().ignore(Path::new(path)),
+                "{path}"
+            );
+        }
+        for path in ["", "/", ".."] {
+            assert!(!filter.ignore(Path::new(path)), "{path}");
+            assert!(
+                !NoExtensionFilter::inverted().ignore(Path::new(path)),
+                "{path}"
+            );
+        }
+    }
+}
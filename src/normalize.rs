@@ -0,0 +1,86 @@
+use crate::MatchOptions;
+use std::borrow::Cow;
+
+/// Returns whether extended-length prefixes are stripped before matching.
+///
+/// They are only meaningful on Windows, or for Windows paths matched elsewhere
+/// with normalized separators; on other platforms a path starting with `\\?\`
+/// is matched as it is.
+pub(crate) fn strips_verbatim(options: MatchOptions) -> bool {
+    cfg!(windows) || options.normalizes_separators()
+}
+
+/// Strips Windows extended-length (`\\?\`) prefixes from a path.
+///
+/// `\\?\C:\dir` becomes `C:\dir` and `\\?\UNC\server\share` becomes
+/// `\\server\share`, so rules written against the regular forms also match paths
+/// handed out by APIs like `std::fs::canonicalize`. Other verbatim paths, such as
+/// `\\?\Volume{..}\`, have no regular form and are returned unchanged.
+pub(crate) fn strip_verbatim_prefix(path: &str) -> Cow<'_, str> {
+    match split_verbatim_prefix(path) {
+        ("", rest) => Cow::Borrowed(rest),
+        (prefix, rest) => Cow::Owned(format!("{prefix}{rest}")),
+    }
+}
+
+/// Splits a path into the prefix that replaces its verbatim prefix and the rest.
+///
+/// The regular form of the path is the concatenation of both; the prefix is only
+/// non-empty for UNC paths, so callers can avoid copying the path otherwise.
+pub(crate) fn split_verbatim_prefix(path: &str) -> (&'static str, &str) {
+    let Some(rest) = path.strip_prefix(r"\\?\") else {
+        return ("", path);
+    };
+
+    if let Some(unc) = rest
+        .get(..4)
+        .filter(|prefix| prefix.eq_ignore_ascii_case(r"UNC\"))
+        .and(rest.get(4..))
+    {
+        return (r"\\", unc);
+    }
+
+    match rest.as_bytes() {
+        [drive, b':', ..] if drive.is_ascii_alphabetic() => ("", rest),
+        _ => ("", path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::normalize::strip_verbatim_prefix;
+
+    #[test]
+    fn verbatim_disk() {
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\C:\Users\a.txt"),
+            r"C:\Users\a.txt"
+        );
+        assert_eq!(strip_verbatim_prefix(r"\\?\d:"), r"d:");
+    }
+
+    #[test]
+    fn verbatim_unc() {
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\UNC\server\share\a.txt"),
+            r"\\server\share\a.txt"
+        );
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\unc\server\share"),
+            r"\\server\share"
+        );
+    }
+
+    #[test]
+    fn unchanged() {
+        for path in [
+            r"C:\Users\a.txt",
+            r"\\server\share\a.txt",
+            r"\\?\Volume{b75e2c83-0000-0000-0000-602f00000000}\a.txt",
+            "src/lib.rs",
+            r"\\?\",
+        ] {
+            assert_eq!(strip_verbatim_prefix(path), path);
+        }
+    }
+}
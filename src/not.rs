@@ -0,0 +1,106 @@
+use crate::{IgnorePath, MatchCandidate, PathFilter};
+use std::path::Path;
+
+/// A filter that inverts another filter: it ignores exactly the paths the other one keeps.
+///
+/// See [`PathFilter::negate`](crate::PathFilter::negate) for a negated filter
+/// that is itself a `PathFilter`.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{ExtensionFilter, IgnorePath, Not};
+/// use std::path::Path;
+///
+/// let filter = Not::new(ExtensionFilter::new(".rs"));
+/// assert!(!filter.ignore(Path::new("src/lib.rs")));
+/// assert!(filter.ignore(Path::new("README.md")));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Not<F> {
+    filter: F,
+}
+
+/// An allowlist: keeps only the paths its inner filter matches and ignores everything else.
+///
+/// This is another name for [`Not`], for tools that are include-driven, e.g. that
+/// process only Markdown files. To take exclusions back out of the allowed paths,
+/// use [`IncludeFilter`](crate::IncludeFilter).
+///
+/// Directories are paths too: when walking a tree, directories have to be
+/// matched by the inner filter or exempted from filtering to descend into them.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, Only, PathFilter};
+/// use std::path::Path;
+///
+/// let filter = Only::new(PathFilter::new_extensions([".md", ".rst"]));
+/// assert!(!filter.ignore(Path::new("docs/index.md")));
+/// assert!(filter.ignore(Path::new("src/lib.rs")));
+/// ```
+pub type Only<F> = Not<F>;
+
+impl<F: IgnorePath> IgnorePath for Not<F> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        !self.filter.ignore(path)
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        !self.filter.ignore_candidate(candidate)
+    }
+}
+
+impl<F> Not<F> {
+    /// Creates a filter that ignores the paths `filter` keeps.
+    pub fn new(filter: F) -> Self {
+        Not { filter }
+    }
+
+    /// Returns the filter that is inverted.
+    pub fn inner(&self) -> &F {
+        &self.filter
+    }
+
+    /// Returns the filter that is inverted, consuming the wrapper.
+    pub fn into_inner(self) -> F {
+        self.filter
+    }
+}
+
+/// Converts the wrapper into the equivalent [`PathFilter::negate`].
+impl From<Not<PathFilter>> for PathFilter {
+    fn from(not: Not<PathFilter>) -> Self {
+        not.filter.negate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ExtensionFilter, IgnorePath, MatchContext, Not, Only, PathFilter};
+    use std::path::Path;
+
+    #[test]
+    fn not() {
+        let filter = Not::new(vec![
+            ExtensionFilter::new(".rs"),
+            ExtensionFilter::new(".md"),
+        ]);
+        assert!(!filter.ignore(Path::new("src/lib.rs")));
+        assert!(!filter.ignore(Path::new("README.md")));
+        assert!(filter.ignore(Path::new("Cargo.toml")));
+        assert!(!MatchContext::new().ignore(&filter, Path::new("a.md")));
+
+        let twice = Not::new(filter);
+        assert!(twice.ignore(Path::new("src/lib.rs")));
+        assert_eq!(twice.into_inner().inner().len(), 2);
+
+        let filter: PathFilter = Only::new(PathFilter::new_extension(".md")).into();
+        assert!(matches!(filter, PathFilter::Not(_)));
+        assert!(filter.ignore(Path::new("Cargo.toml")));
+    }
+}
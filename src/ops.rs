@@ -0,0 +1,391 @@
+use crate::{IgnorePath, MatchCandidate, Not, PathFilter, SharedFilter};
+use std::{
+    ops::{BitAnd, BitOr},
+    path::Path,
+};
+
+/// A filter that ignores a path if both of its filters do, usually built with `a & b`.
+///
+/// The filters of this crate support `&`, `|` and `!`, which build [`And`], [`Or`]
+/// and [`Not`]. For [`PathFilter`] the operators build [`PathFilter::And`],
+/// [`PathFilter::Or`] and [`PathFilter::negate`] instead, so the result is again a
+/// `PathFilter`. A [`SharedFilter`] can be combined with the filters of this crate
+/// on its right-hand side; other filters can be combined by wrapping them with
+/// [`And::new`], [`Or::new`] or [`Not::new`].
+///
+/// # Examples
+/// ```
+/// use pathfilter::{ExtensionFilter, IgnorePath, PrefixFilter};
+/// use std::path::Path;
+///
+/// let filter = ExtensionFilter::new(".js") & PrefixFilter::new(["vendor"]);
+/// assert!(filter.ignore(Path::new("vendor/jquery.js")));
+/// assert!(!filter.ignore(Path::new("src/app.js")));
+/// ```
+///
+/// Only filters can be combined:
+/// ```compile_fail
+/// let filter = pathfilter::ExtensionFilter::new(".js") & 5;
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct And<A, B> {
+    left: A,
+    right: B,
+}
+
+/// A filter that ignores a path if either of its filters does, usually built with `a | b`.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{ExtensionFilter, IgnorePath, PrefixFilter};
+/// use std::path::Path;
+///
+/// let filter = ExtensionFilter::new(".o") | PrefixFilter::new(["target"]);
+/// assert!(filter.ignore(Path::new("main.o")));
+/// assert!(filter.ignore(Path::new("target/debug/app")));
+/// assert!(!filter.ignore(Path::new("src/main.c")));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Or<A, B> {
+    left: A,
+    right: B,
+}
+
+impl<A: IgnorePath, B: IgnorePath> IgnorePath for And<A, B> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        self.left.ignore_candidate(candidate) && self.right.ignore_candidate(candidate)
+    }
+}
+
+impl<A: IgnorePath, B: IgnorePath> IgnorePath for Or<A, B> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        self.left.ignore_candidate(candidate) || self.right.ignore_candidate(candidate)
+    }
+}
+
+impl<A, B> And<A, B> {
+    /// Creates a filter that ignores paths both filters ignore.
+    pub fn new(left: A, right: B) -> Self {
+        And { left, right }
+    }
+
+    /// Returns the left-hand filter.
+    pub fn left(&self) -> &A {
+        &self.left
+    }
+
+    /// Returns the right-hand filter.
+    pub fn right(&self) -> &B {
+        &self.right
+    }
+
+    /// Returns both filters, consuming the wrapper.
+    pub fn into_inner(self) -> (A, B) {
+        (self.left, self.right)
+    }
+}
+
+impl<A, B> Or<A, B> {
+    /// Creates a filter that ignores paths either filter ignores.
+    pub fn new(left: A, right: B) -> Self {
+        Or { left, right }
+    }
+
+    /// Returns the left-hand filter.
+    pub fn left(&self) -> &A {
+        &self.left
+    }
+
+    /// Returns the right-hand filter.
+    pub fn right(&self) -> &B {
+        &self.right
+    }
+
+    /// Returns both filters, consuming the wrapper.
+    pub fn into_inner(self) -> (A, B) {
+        (self.left, self.right)
+    }
+}
+
+/// Joins two `PathFilter`s into a [`PathFilter::And`], flattening nested ones.
+impl BitAnd for PathFilter {
+    type Output = PathFilter;
+
+    fn bitand(self, rhs: PathFilter) -> PathFilter {
+        let mut filters = match self {
+            PathFilter::And(filters) => filters,
+            filter => vec![filter],
+        };
+        match rhs {
+            PathFilter::And(rhs) => filters.extend(rhs),
+            rhs => filters.push(rhs),
+        }
+        PathFilter::And(filters)
+    }
+}
+
+/// Joins two `PathFilter`s into a [`PathFilter::Or`], flattening nested ones.
+impl BitOr for PathFilter {
+    type Output = PathFilter;
+
+    fn bitor(self, rhs: PathFilter) -> PathFilter {
+        let mut filters = match self {
+            PathFilter::Or(filters) => filters,
+            filter => vec![filter],
+        };
+        match rhs {
+            PathFilter::Or(rhs) => filters.extend(rhs),
+            rhs => filters.push(rhs),
+        }
+        PathFilter::Or(filters)
+    }
+}
+
+/// Negates the filter with [`PathFilter::negate`].
+impl std::ops::Not for PathFilter {
+    type Output = PathFilter;
+
+    fn not(self) -> PathFilter {
+        self.negate()
+    }
+}
+
+/// Negating a negated filter returns the original filter.
+impl<F> std::ops::Not for Not<F> {
+    type Output = F;
+
+    fn not(self) -> F {
+        self.into_inner()
+    }
+}
+
+impl<F, R: IgnorePath> BitAnd<R> for Not<F> {
+    type Output = And<Self, R>;
+
+    fn bitand(self, rhs: R) -> Self::Output {
+        And::new(self, rhs)
+    }
+}
+
+impl<F, R: IgnorePath> BitOr<R> for Not<F> {
+    type Output = Or<Self, R>;
+
+    fn bitor(self, rhs: R) -> Self::Output {
+        Or::new(self, rhs)
+    }
+}
+
+/// Implements `shared & filter` and `shared | filter` for a [`SharedFilter`].
+///
+/// The coherence rules only allow this for filters of this crate on the right-hand
+/// side, and rule out `!shared` entirely.
+macro_rules! impl_shared_ops {
+    ($(#[$attr:meta])* [$($param:ident $(: $bound:path)?),*] $ty:ty) => {
+        $(#[$attr])*
+        impl<$($param $(: $bound)?),*> BitAnd<$ty> for SharedFilter {
+            type Output = And<Self, $ty>;
+
+            fn bitand(self, rhs: $ty) -> Self::Output {
+                And::new(self, rhs)
+            }
+        }
+
+        $(#[$attr])*
+        impl<$($param $(: $bound)?),*> BitOr<$ty> for SharedFilter {
+            type Output = Or<Self, $ty>;
+
+            fn bitor(self, rhs: $ty) -> Self::Output {
+                Or::new(self, rhs)
+            }
+        }
+    };
+}
+
+impl_shared_ops!([] PathFilter);
+impl_shared_ops!([F] Not<F>);
+
+/// Implements `&`, `|` and `!` with the generic wrappers.
+macro_rules! impl_ops {
+    ($($(#[$attr:meta])* [$($param:ident $(: $bound:path)?),*] $ty:ty;)*) => {$(
+        $(#[$attr])*
+        impl<$($param $(: $bound)?,)* R: IgnorePath> BitAnd<R> for $ty {
+            type Output = And<Self, R>;
+
+            fn bitand(self, rhs: R) -> Self::Output {
+                And::new(self, rhs)
+            }
+        }
+
+        $(#[$attr])*
+        impl<$($param $(: $bound)?,)* R: IgnorePath> BitOr<R> for $ty {
+            type Output = Or<Self, R>;
+
+            fn bitor(self, rhs: R) -> Self::Output {
+                Or::new(self, rhs)
+            }
+        }
+
+        $(#[$attr])*
+        impl<$($param $(: $bound)?),*> std::ops::Not for $ty {
+            type Output = Not<Self>;
+
+            fn not(self) -> Self::Output {
+                Not::new(self)
+            }
+        }
+
+        impl_shared_ops!($(#[$attr])* [$($param $(: $bound)?),*] $ty);
+    )*};
+}
+
+impl_ops! {
+    [A, B] And<A, B>;
+    [A, B] Or<A, B>;
+    [F] crate::FilterChain<F>;
+    [F] crate::FilterSet<F>;
+    [F] crate::IncludeFilter<F>;
+    [F] crate::archive::ArchiveEntryFilter<F>;
+    [] crate::ComponentFilter;
+    [] crate::DepthFilter;
+    [] crate::ExactPathSetFilter;
+    [] crate::ExtensionFilter;
+    [] crate::ExtensionsFilter;
+    [] crate::StaticExtensionsFilter;
+    [] crate::FileNameFilter;
+    [] crate::HiddenFileFilter;
+    [] crate::LimitFilter;
+    [] crate::NoExtensionFilter;
+    [] crate::PrefixFilter;
+    [] crate::RetentionPlan;
+    [] crate::StemFilter;
+    [] crate::WildcardFilter;
+    [] crate::patterns::PatternFile;
+    [] crate::presets::StaticSiteFilter;
+    #[cfg(feature = "regex")]
+    [] crate::RegexFilter;
+    #[cfg(feature = "regex")]
+    [] crate::RegexSetFilter;
+    #[cfg(feature = "glob")]
+    [] crate::GlobFilter;
+    #[cfg(feature = "glob")]
+    [] crate::GitignoreFilter;
+    #[cfg(feature = "substring")]
+    [] crate::SubstringFilter;
+    #[cfg(feature = "mime")]
+    [] crate::MimeTypeFilter;
+    #[cfg(feature = "io")]
+    [H: crate::ContentHasher] crate::HashSetContentFilter<H>;
+    #[cfg(feature = "io")]
+    [F] crate::SidecarFilter<F>;
+    #[cfg(feature = "io")]
+    [] crate::presets::CodeSearchFilter;
+    #[cfg(feature = "io")]
+    [] crate::BinaryFilter;
+    #[cfg(feature = "io")]
+    [] crate::EmptyDirFilter;
+    #[cfg(feature = "io")]
+    [] crate::EmptyFileFilter;
+    #[cfg(feature = "io")]
+    [] crate::FileTypeFilter;
+    #[cfg(feature = "io")]
+    [] crate::LfsPointerFilter;
+    #[cfg(feature = "io")]
+    [] crate::MarkerFilter;
+    #[cfg(feature = "io")]
+    [] crate::PermissionsFilter;
+    #[cfg(feature = "io")]
+    [] crate::SizeFilter;
+    #[cfg(feature = "io")]
+    [] crate::SortedPathFile;
+    #[cfg(feature = "io")]
+    [] crate::AgeFilter;
+    #[cfg(feature = "io")]
+    [] crate::ModifiedSinceFilter;
+    #[cfg(all(feature = "io", unix))]
+    [] crate::OwnerFilter;
+    #[cfg(all(feature = "xattr", any(target_os = "linux", target_os = "macos")))]
+    [] crate::XattrFilter;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{And, ExtensionFilter, IgnorePath, Not, Or, PathFilter, PrefixFilter};
+    use std::path::Path;
+
+    #[test]
+    fn path_filter_operators() {
+        let filter = PathFilter::new_prefixes(["vendor"]) & !PathFilter::new_extension(".md")
+            | PathFilter::new_extension(".o")
+            | PathFilter::new_extension(".a");
+        let PathFilter::Or(filters) = &filter else {
+            panic!("expected a flattened disjunction, got {filter:?}");
+        };
+        assert_eq!(filters.len(), 3);
+        assert!(matches!(&filters[0], PathFilter::And(inner) if inner.len() == 2));
+        assert!(filter.ignore(Path::new("vendor/lib.js")));
+        assert!(!filter.ignore(Path::new("vendor/README.md")));
+        assert!(filter.ignore(Path::new("src/main.o")));
+        assert!(!filter.ignore(Path::new("src/main.c")));
+
+        let twice = !!PathFilter::new_extension(".rs");
+        assert!(matches!(twice, PathFilter::Extension(_)));
+    }
+
+    #[test]
+    fn generic_operators() {
+        let filter: And<Not<ExtensionFilter>, PrefixFilter> =
+            !ExtensionFilter::new(".md") & PrefixFilter::new(["docs"]);
+        assert!(filter.ignore(Path::new("docs/logo.png")));
+        assert!(!filter.ignore(Path::new("docs/index.md")));
+        assert!(!filter.ignore(Path::new("src/lib.rs")));
+
+        let filter: Or<_, ExtensionFilter> = filter | ExtensionFilter::new(".tmp");
+        assert!(filter.ignore(Path::new("a.tmp")));
+        let (left, right) = filter.into_inner();
+        assert_eq!(right.extension(), "tmp");
+        assert!(left.left().inner().ignore(Path::new("a.md")));
+
+        let unwrapped: ExtensionFilter = !Not::new(ExtensionFilter::new(".rs"));
+        assert!(unwrapped.ignore(Path::new("lib.rs")));
+    }
+
+    #[test]
+    fn shared_operators() {
+        use crate::{shared, LimitFilter, SharedFilter};
+
+        let shared: SharedFilter = shared(ExtensionFilter::new(".o"));
+        let filter: Or<SharedFilter, _> = shared.clone() | PathFilter::new_extension(".a");
+        assert!(filter.ignore(Path::new("lib.a")));
+        assert!(filter.ignore(Path::new("main.o")));
+
+        let filter = shared & !PrefixFilter::new(["vendor"]);
+        assert!(filter.ignore(Path::new("main.o")));
+        assert!(!filter.ignore(Path::new("vendor/main.o")));
+
+        let filter = LimitFilter::new(1) | ExtensionFilter::new(".o");
+        assert!(filter.ignore(Path::new("main.o")));
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn io_operators() {
+        use crate::MarkerFilter;
+
+        let either_order = (
+            ExtensionFilter::new(".o") & MarkerFilter::cachedir_tag(),
+            MarkerFilter::cachedir_tag() & ExtensionFilter::new(".o"),
+        );
+        assert!(!either_order.0.ignore(Path::new("main.c")));
+        assert!(!either_order.1.ignore(Path::new("main.c")));
+    }
+}
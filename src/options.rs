@@ -0,0 +1,338 @@
+/// Options that control how filters match paths.
+///
+/// The same options can be applied to every filter, so a whole list of filters
+/// can be made case-insensitive at once, see [`PathFilter::with_options`](crate::PathFilter::with_options).
+/// Filters ignore options that do not apply to them, e.g. extension filters do not
+/// look at path separators.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{ExtensionFilter, IgnorePath, MatchOptions};
+/// use std::path::Path;
+///
+/// let options = MatchOptions::new().case_insensitive(true);
+/// let filter = ExtensionFilter::new(".rs").with_options(options);
+/// assert!(filter.ignore(Path::new("src/LIB.RS")));
+/// ```
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct MatchOptions {
+    case_insensitive: bool,
+    normalize_separators: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "std::ops::Not::not"))]
+    unicode_case: bool,
+}
+
+impl MatchOptions {
+    /// Creates the default options: case-sensitive, with ASCII case folding when made
+    /// case-insensitive, and without separator normalization.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::MatchOptions;
+    ///
+    /// assert_eq!(MatchOptions::new(), MatchOptions::default());
+    /// ```
+    pub const fn new() -> Self {
+        MatchOptions {
+            case_insensitive: false,
+            normalize_separators: false,
+            unicode_case: false,
+        }
+    }
+
+    /// Sets whether paths are compared case-insensitively.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::MatchOptions;
+    ///
+    /// let options = MatchOptions::new().case_insensitive(true);
+    /// assert!(options.is_case_insensitive());
+    /// ```
+    pub const fn case_insensitive(mut self, yes: bool) -> Self {
+        self.case_insensitive = yes;
+        self
+    }
+
+    /// Sets whether `\` is treated as a path separator and matched as `/`.
+    ///
+    /// This lets rules written with `/` match Windows paths. It is off by default
+    /// because `\` is a regular character in file names on Unix.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::MatchOptions;
+    ///
+    /// let options = MatchOptions::new().normalize_separators(true);
+    /// assert!(options.normalizes_separators());
+    /// ```
+    pub const fn normalize_separators(mut self, yes: bool) -> Self {
+        self.normalize_separators = yes;
+        self
+    }
+
+    /// Sets whether case-insensitive comparisons fold all of Unicode instead of only
+    /// ASCII letters.
+    ///
+    /// Text is folded by converting it to upper and then to lower case, so `ß`
+    /// matches `SS` and `É` matches `é`. Locale-specific rules, like the Turkish
+    /// dotted and dotless `i`, are not applied. This only has an effect together
+    /// with [`case_insensitive`](Self::case_insensitive). Regexes always use the
+    /// Unicode-aware simple case folding of the `regex` crate.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{ExtensionFilter, IgnorePath, MatchOptions};
+    /// use std::path::Path;
+    ///
+    /// let options = MatchOptions::new().case_insensitive(true);
+    /// let filter = ExtensionFilter::new(".übung").with_options(options);
+    /// assert!(!filter.ignore(Path::new("notes.ÜBUNG")));
+    /// let filter = filter.with_options(options.unicode_case(true));
+    /// assert!(filter.ignore(Path::new("notes.ÜBUNG")));
+    /// ```
+    pub const fn unicode_case(mut self, yes: bool) -> Self {
+        self.unicode_case = yes;
+        self
+    }
+
+    /// Returns `true` if paths are compared case-insensitively.
+    pub const fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Returns `true` if `\` is matched as `/`.
+    pub const fn normalizes_separators(&self) -> bool {
+        self.normalize_separators
+    }
+
+    /// Returns `true` if case-insensitive comparisons fold all of Unicode.
+    pub const fn is_unicode_case(&self) -> bool {
+        self.unicode_case
+    }
+
+    pub(crate) fn is_default(&self) -> bool {
+        *self == MatchOptions::new()
+    }
+}
+
+/// Options set on a single filter that take precedence over the options applied to it.
+///
+/// Options applied through `with_options`, e.g. to a whole list of filters, only
+/// change the settings that a filter does not override.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, MatchOptions, MatchOverrides, PathFilter};
+/// use std::path::Path;
+///
+/// let filters: Vec<PathFilter> = [
+///     PathFilter::new_extension(".rs"),
+///     PathFilter::new_extension(".md").with_overrides(MatchOverrides::new().case_insensitive(false)),
+/// ]
+/// .into_iter()
+/// .map(|filter| filter.with_options(MatchOptions::new().case_insensitive(true)))
+/// .collect();
+///
+/// assert!(filters.ignore(Path::new("src/LIB.RS")));
+/// assert!(!filters.ignore(Path::new("README.MD")));
+/// ```
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct MatchOverrides {
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    case_insensitive: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    normalize_separators: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    unicode_case: Option<bool>,
+}
+
+impl MatchOverrides {
+    /// Creates overrides that do not override anything.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{MatchOptions, MatchOverrides};
+    ///
+    /// let options = MatchOptions::new().case_insensitive(true);
+    /// assert_eq!(MatchOverrides::new().apply(options), options);
+    /// ```
+    pub const fn new() -> Self {
+        MatchOverrides {
+            case_insensitive: None,
+            normalize_separators: None,
+            unicode_case: None,
+        }
+    }
+
+    /// Overrides whether paths are compared case-insensitively.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{MatchOptions, MatchOverrides};
+    ///
+    /// let overrides = MatchOverrides::new().case_insensitive(true);
+    /// assert!(overrides.apply(MatchOptions::new()).is_case_insensitive());
+    /// ```
+    pub const fn case_insensitive(mut self, yes: bool) -> Self {
+        self.case_insensitive = Some(yes);
+        self
+    }
+
+    /// Overrides whether `\` is matched as `/`.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{MatchOptions, MatchOverrides};
+    ///
+    /// let overrides = MatchOverrides::new().normalize_separators(true);
+    /// assert!(overrides.apply(MatchOptions::new()).normalizes_separators());
+    /// ```
+    pub const fn normalize_separators(mut self, yes: bool) -> Self {
+        self.normalize_separators = Some(yes);
+        self
+    }
+
+    /// Overrides whether case-insensitive comparisons fold all of Unicode.
+    pub const fn unicode_case(mut self, yes: bool) -> Self {
+        self.unicode_case = Some(yes);
+        self
+    }
+
+    /// Returns `true` if no option is overridden.
+    pub const fn is_empty(&self) -> bool {
+        self.case_insensitive.is_none()
+            && self.normalize_separators.is_none()
+            && self.unicode_case.is_none()
+    }
+
+    /// Returns the options with every overridden setting replaced.
+    pub const fn apply(&self, options: MatchOptions) -> MatchOptions {
+        MatchOptions {
+            case_insensitive: match self.case_insensitive {
+                Some(yes) => yes,
+                None => options.case_insensitive,
+            },
+            normalize_separators: match self.normalize_separators {
+                Some(yes) => yes,
+                None => options.normalize_separators,
+            },
+            unicode_case: match self.unicode_case {
+                Some(yes) => yes,
+                None => options.unicode_case,
+            },
+        }
+    }
+}
+
+/// The part of a path a filter matches against, e.g. for `RegexFilter` and [`WildcardFilter`](crate::WildcardFilter).
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MatchTarget {
+    /// The whole path.
+    #[default]
+    Path,
+    /// Only the file name, the last component of the path.
+    ///
+    /// Paths without a file name, like `..`, are never matched.
+    FileName,
+}
+
+impl MatchTarget {
+    pub(crate) fn is_path(&self) -> bool {
+        *self == MatchTarget::Path
+    }
+}
+
+/// Implements `with_options`, `set_options`, `options`, `with_overrides`,
+/// `set_overrides` and `overrides` for a filter with `options` and `overrides`
+/// fields.
+///
+/// Attributes before the arguments are added to `with_options`, e.g. examples,
+/// and attributes after `overrides:` to `with_overrides`. With
+/// `on_change = method`, the setters call `self.method()` after storing the new
+/// value, e.g. to recompile a regex.
+macro_rules! options_methods {
+    (
+        $(#[$attr:meta])*
+        $(on_change = $on_change:ident)?
+        $(, overrides: $(#[$overrides_attr:meta])+)?
+    ) => {
+        /// Sets the options used for matching.
+        $(#[$attr])*
+        pub fn with_options(mut self, options: $crate::MatchOptions) -> Self {
+            self.set_options(options);
+            self
+        }
+
+        /// Sets the options used for matching.
+        ///
+        /// Settings overridden with [`with_overrides`](Self::with_overrides) keep their value.
+        pub fn set_options(&mut self, options: $crate::MatchOptions) {
+            self.options = options;
+            $(self.$on_change();)?
+        }
+
+        /// Returns the options used for matching, including overrides.
+        pub fn options(&self) -> $crate::MatchOptions {
+            self.overrides.apply(self.options)
+        }
+
+        /// Sets options that take precedence over the ones set with [`with_options`](Self::with_options).
+        $($(#[$overrides_attr])+)?
+        pub fn with_overrides(mut self, overrides: $crate::MatchOverrides) -> Self {
+            self.set_overrides(overrides);
+            self
+        }
+
+        /// Sets options that take precedence over the ones set with [`set_options`](Self::set_options).
+        pub fn set_overrides(&mut self, overrides: $crate::MatchOverrides) {
+            self.overrides = overrides;
+            $(self.$on_change();)?
+        }
+
+        /// Returns the options this filter overrides.
+        pub fn overrides(&self) -> $crate::MatchOverrides {
+            self.overrides
+        }
+    };
+}
+pub(crate) use options_methods;
+
+#[cfg(test)]
+mod tests {
+    use crate::{MatchOptions, MatchOverrides};
+
+    #[test]
+    fn apply_overrides() {
+        let options = MatchOptions::new().case_insensitive(true);
+
+        let overrides = MatchOverrides::new();
+        assert!(overrides.is_empty());
+        assert_eq!(overrides.apply(options), options);
+
+        let overrides = overrides.case_insensitive(false);
+        assert!(!overrides.is_empty());
+        assert_eq!(overrides.apply(options), MatchOptions::new());
+
+        let overrides = overrides.normalize_separators(true);
+        assert_eq!(
+            overrides.apply(options),
+            MatchOptions::new().normalize_separators(true)
+        );
+
+        let overrides = MatchOverrides::new().unicode_case(true);
+        assert!(overrides.apply(options).is_unicode_case());
+        assert!(overrides.apply(options).is_case_insensitive());
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{content::io_error_policy_methods, IgnorePath, IoErrorPolicy};
+use std::{fs, io, os::unix::fs::MetadataExt, path::Path};
+
+/// A filter that matches entries by their owning user or group.
+///
+/// Available on Unix with the `io` feature. An entry is matched if it is owned by
+/// one of the given users or groups, or, with
+/// [`not_owned_by_current_user`](Self::not_owned_by_current_user), by anyone but
+/// the effective user of the process. The owner is read with
+/// [`fs::symlink_metadata`], so a symlink is matched by its own owner, like
+/// `find -user` does.
+///
+/// It converts into a [`PathFilter`](crate::PathFilter), so it can be combined
+/// with the other filters. With the `serde` feature, a filter for entries not
+/// owned by the current user stores just that, and is bound to the effective user
+/// of the process that deserializes it.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{IgnorePath, OwnerFilter};
+///
+/// let filter = OwnerFilter::uids([0]).with_gid(0);
+/// assert!(filter.ignore("/tmp/root-owned"));
+///
+/// let filter = OwnerFilter::not_owned_by_current_user();
+/// assert!(filter.ignore("/tmp/someone-else"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "OwnerFilterDef", into = "OwnerFilterDef")
+)]
+pub struct OwnerFilter {
+    uids: Vec<u32>,
+    gids: Vec<u32>,
+    not_owned_by: Option<u32>,
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for OwnerFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+extern "C" {
+    fn geteuid() -> u32;
+}
+
+/// The serialized form of an [`OwnerFilter`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct OwnerFilterDef {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    uids: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    gids: Vec<u32>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    not_owned_by_current_user: bool,
+    #[serde(default)]
+    on_error: IoErrorPolicy,
+}
+
+#[cfg(feature = "serde")]
+impl From<OwnerFilterDef> for OwnerFilter {
+    fn from(value: OwnerFilterDef) -> Self {
+        let filter = if value.not_owned_by_current_user {
+            OwnerFilter::not_owned_by_current_user()
+        } else {
+            OwnerFilter::default()
+        };
+        OwnerFilter {
+            uids: value.uids,
+            gids: value.gids,
+            on_error: value.on_error,
+            ..filter
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<OwnerFilter> for OwnerFilterDef {
+    fn from(value: OwnerFilter) -> Self {
+        OwnerFilterDef {
+            uids: value.uids,
+            gids: value.gids,
+            not_owned_by_current_user: value.not_owned_by.is_some(),
+            on_error: value.on_error,
+        }
+    }
+}
+
+impl OwnerFilter {
+    /// Creates a new filter for entries owned by one of the users.
+    pub fn uids<I: IntoIterator<Item = u32>>(uids: I) -> Self {
+        OwnerFilter {
+            uids: uids.into_iter().collect(),
+            ..OwnerFilter::default()
+        }
+    }
+
+    /// Creates a new filter for entries owned by one of the groups.
+    pub fn gids<I: IntoIterator<Item = u32>>(gids: I) -> Self {
+        OwnerFilter {
+            gids: gids.into_iter().collect(),
+            ..OwnerFilter::default()
+        }
+    }
+
+    /// Creates a new filter for entries not owned by the effective user of the process.
+    pub fn not_owned_by_current_user() -> Self {
+        // SAFETY: `geteuid` has no preconditions and always succeeds.
+        let uid = unsafe { geteuid() };
+        OwnerFilter {
+            not_owned_by: Some(uid),
+            ..OwnerFilter::default()
+        }
+    }
+
+    /// Also matches entries owned by the user.
+    pub fn with_uid(mut self, uid: u32) -> Self {
+        self.uids.push(uid);
+        self
+    }
+
+    /// Also matches entries owned by the group.
+    pub fn with_gid(mut self, gid: u32) -> Self {
+        self.gids.push(gid);
+        self
+    }
+
+    io_error_policy_methods!(
+        "paths whose metadata cannot be read",
+        "e.g. because they do not exist"
+    );
+
+    /// Returns whether an entry owned by `uid` and `gid` is matched.
+    pub fn matches_owner(&self, uid: u32, gid: u32) -> bool {
+        self.uids.contains(&uid)
+            || self.gids.contains(&gid)
+            || self.not_owned_by.is_some_and(|current| current != uid)
+    }
+
+    /// Returns whether the path is owned by a matching user or group.
+    ///
+    /// # Errors
+    /// If the metadata cannot be read, an error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let metadata = fs::symlink_metadata(path)?;
+        Ok(self.matches_owner(metadata.uid(), metadata.gid()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{content::tests::TempDir, IgnorePath, IoErrorPolicy, OwnerFilter};
+    use std::os::unix::fs::MetadataExt;
+
+    #[test]
+    fn owner_filter() {
+        let dir = TempDir::new();
+        let file = dir.write("file", "");
+        let metadata = file.metadata().unwrap();
+        let (uid, gid) = (metadata.uid(), metadata.gid());
+
+        assert!(OwnerFilter::uids([uid]).ignore(&file));
+        assert!(!OwnerFilter::uids([uid.wrapping_add(1)]).ignore(&file));
+        assert!(OwnerFilter::gids([gid]).ignore(&file));
+        assert!(OwnerFilter::uids([])
+            .with_gid(gid.wrapping_add(1))
+            .with_gid(gid)
+            .ignore(&file));
+        assert!(!OwnerFilter::not_owned_by_current_user().ignore(&file));
+
+        let filter = OwnerFilter::not_owned_by_current_user();
+        assert!(!filter.matches_owner(uid, gid));
+        assert!(filter.matches_owner(uid.wrapping_add(1), gid));
+
+        let missing = dir.path().join("missing");
+        assert!(!OwnerFilter::uids([uid]).ignore(&missing));
+        assert!(OwnerFilter::uids([uid])
+            .with_io_error_policy(IoErrorPolicy::Ignore)
+            .ignore(&missing));
+    }
+
+    #[test]
+    fn path_filters() {
+        use crate::PathFilter;
+
+        let dir = TempDir::new();
+        let file = dir.write("file.txt", "");
+        let uid = file.metadata().unwrap().uid();
+
+        let filters = vec![
+            PathFilter::new_extension(".log"),
+            OwnerFilter::uids([uid]).into(),
+        ];
+        assert!(filters.ignore(&file));
+        assert!(filters.ignore("missing.log"));
+        let filters = vec![
+            PathFilter::new_extension(".log"),
+            OwnerFilter::uids([uid.wrapping_add(1)]).into(),
+        ];
+        assert!(!filters.ignore(&file));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use crate::{
+            value::{to_value, Value},
+            PathFilter,
+        };
+        use serde::{
+            de::{value::Error, IntoDeserializer},
+            Deserialize,
+        };
+
+        let str = |s: &str| Value::Str(s.to_string());
+        let filter = OwnerFilter::not_owned_by_current_user().with_gid(7);
+        let value = to_value(&filter).unwrap();
+        assert_eq!(
+            value,
+            Value::Map(vec![
+                (str("gids"), Value::Seq(vec![Value::U64(7)])),
+                (str("not_owned_by_current_user"), Value::Bool(true)),
+                (str("on_error"), str("Keep")),
+            ])
+        );
+        let restored =
+            OwnerFilter::deserialize(IntoDeserializer::<Error>::into_deserializer(value)).unwrap();
+        assert_eq!(restored, filter);
+
+        let value = Value::Map(vec![(
+            str("Owner"),
+            Value::Map(vec![(str("uids"), Value::Seq(vec![Value::U64(0)]))]),
+        )]);
+        let filter =
+            PathFilter::deserialize(IntoDeserializer::<Error>::into_deserializer(value)).unwrap();
+        assert!(matches!(filter, PathFilter::Owner(_)));
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{normalize::strip_verbatim_prefix, IgnorePath};
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A filter for a set of exact paths, looked up in constant time.
+///
+/// Paths must match exactly, component by component like [`Path::eq`], so
+/// `a/b` also matches `a//b` and `a/b/`, but not `./a/b`. For lists too large to
+/// keep in memory, see `SortedPathFile`.
+///
+/// With the `serde` feature the filter is serialized as `{ "paths": [...] }`, with
+/// the paths sorted.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{ExactPathSetFilter, IgnorePath};
+/// use std::path::Path;
+///
+/// let filter = ExactPathSetFilter::new(["src/generated.rs", "assets/large.bin"]);
+/// assert!(filter.ignore(Path::new("src/generated.rs")));
+/// assert!(!filter.ignore(Path::new("src/lib.rs")));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExactPathSetFilter {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
+    paths: HashSet<PathBuf>,
+}
+
+#[cfg(feature = "serde")]
+fn serialize_sorted<S: serde::Serializer>(
+    paths: &HashSet<PathBuf>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut paths: Vec<_> = paths.iter().collect();
+    paths.sort();
+    serializer.collect_seq(paths)
+}
+
+impl IgnorePath for ExactPathSetFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.contains(path)
+    }
+}
+
+impl ExactPathSetFilter {
+    /// Creates a new filter for a list of paths.
+    pub fn new<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        paths.into_iter().collect()
+    }
+
+    /// Reads a file with one path per line.
+    ///
+    /// Empty lines are skipped and a trailing `\r` is removed from every line.
+    ///
+    /// # Errors
+    /// If the file cannot be read or is not valid UTF-8, an error is returned.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Ok(text
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect())
+    }
+
+    /// Adds a path to the filter.
+    pub fn with_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.paths.insert(path.into());
+        self
+    }
+
+    /// Returns whether the path is in the set.
+    ///
+    /// On Windows, a path with an extended-length prefix is also looked up
+    /// without it, so `\\?\C:\data\a.txt` matches `C:\data\a.txt`.
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        self.paths.contains(path)
+            || (cfg!(windows)
+                && path
+                    .to_str()
+                    .map(strip_verbatim_prefix)
+                    .is_some_and(|text| self.paths.contains(Path::new(text.as_ref()))))
+    }
+
+    /// Returns the paths of the set, in unspecified order.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.paths.iter().map(PathBuf::as_path)
+    }
+
+    /// Returns the number of paths in the set.
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Returns `true` if the set contains no paths.
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+impl<P: Into<PathBuf>> FromIterator<P> for ExactPathSetFilter {
+    fn from_iter<T: IntoIterator<Item = P>>(iter: T) -> Self {
+        ExactPathSetFilter {
+            paths: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl<P: Into<PathBuf>> Extend<P> for ExactPathSetFilter {
+    fn extend<T: IntoIterator<Item = P>>(&mut self, iter: T) {
+        self.paths.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ExactPathSetFilter, IgnorePath};
+    use std::path::Path;
+
+    #[test]
+    fn exact_paths() {
+        let mut filter = ExactPathSetFilter::new(["a/b"]).with_path("c");
+        filter.extend(["d/e/f"]);
+        assert_eq!(filter.len(), 3);
+        assert!(filter.ignore(Path::new("a/b")));
+        assert!(filter.ignore(Path::new("a//b/")));
+        assert!(filter.ignore(Path::new("d/e/f")));
+        assert!(!filter.ignore(Path::new("a")));
+        assert!(!filter.ignore(Path::new("a/b/c")));
+        assert!(!filter.ignore(Path::new("./c")));
+        assert!(!ExactPathSetFilter::default().ignore(Path::new("")));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn verbatim_paths() {
+        let filter = ExactPathSetFilter::new([r"C:\data\a.txt", r"\\server\share\b"]);
+        assert!(filter.ignore(Path::new(r"\\?\C:\data\a.txt")));
+        assert!(filter.ignore(Path::new(r"\\?\UNC\server\share\b")));
+        assert!(!filter.ignore(Path::new(r"\\?\C:\data")));
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn from_file() {
+        let dir = crate::content::tests::TempDir::new();
+        let list = dir.write("list.txt", "a/b\r\n\nc\n");
+        let filter = ExactPathSetFilter::from_file(list).unwrap();
+        assert_eq!(filter.len(), 2);
+        assert!(filter.ignore(Path::new("a/b")));
+        assert!(filter.ignore(Path::new("c")));
+        assert!(ExactPathSetFilter::from_file(dir.path().join("missing")).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use crate::value::{to_value, Value};
+        use serde::de::IntoDeserializer;
+
+        let filter = ExactPathSetFilter::new(["b", "a"]);
+        let value = to_value(&filter).unwrap();
+        let paths = Value::Seq(vec![Value::Str("a".into()), Value::Str("b".into())]);
+        assert_eq!(value, Value::Map(vec![(Value::Str("paths".into()), paths)]));
+
+        let deserialized = serde::Deserialize::deserialize(IntoDeserializer::<
+            serde::de::value::Error,
+        >::into_deserializer(value));
+        assert_eq!(deserialized, Ok(filter));
+    }
+}
@@ -0,0 +1,1131 @@
+//! Loading filters from pattern files.
+//!
+//! A pattern file contains one filter per line, written as `kind:value`.
+//! Blank lines and lines starting with `#` are skipped.
+//!
+//! | Kind             | Value                                  | Filter               |
+//! |------------------|----------------------------------------|----------------------|
+//! | `ext`            | one or more comma separated extensions | [`ExtensionFilter`] or [`ExtensionsFilter`] |
+//! | `regex` or `re`  | a regular expression (`regex` feature) | `RegexFilter`        |
+//! | `glob`           | a glob (`glob` feature)                | `GlobFilter`         |
+//!
+//! # Examples
+//! ```
+//! use pathfilter::patterns;
+//! use pathfilter::IgnorePath;
+//! use std::path::Path;
+//!
+//! let filters = patterns::parse("# build output\next:.o,.a\n").unwrap();
+//! assert!(filters.ignore(Path::new("target/main.o")));
+//! assert!(!filters.ignore(Path::new("src/main.c")));
+//! ```
+//!
+//! To edit a pattern file without losing its comments, use [`PatternFile`].
+//!
+//! Like in a `.gitignore`, lines starting with `!` can re-include paths an
+//! earlier line ignores; [`parse_set`] reads such files into a [`FilterSet`] and
+//! [`PatternFile`] applies them too.
+//!
+//! Where a file is inconvenient, e.g. in CI, the same rules can be given on a
+//! single line separated by `;`, see [`parse_rules`], [`parse_rule_set`] and
+//! [`from_env`].
+
+use crate::{
+    ExtensionFilter, ExtensionsFilter, FilterRule, FilterSet, IgnorePath, MatchCandidate,
+    PathFilter,
+};
+use std::{
+    env,
+    error::Error,
+    ffi::OsString,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The reason a line of a pattern file could not be parsed.
+#[derive(Debug)]
+pub enum PatternErrorKind {
+    /// The line has no `kind:` prefix.
+    MissingKind,
+    /// The kind is not known.
+    UnknownKind(String),
+    /// The kind is not followed by a value.
+    MissingValue,
+    #[cfg(feature = "regex")]
+    /// The value is not a valid regular expression.
+    Regex(regex::Error),
+    #[cfg(feature = "glob")]
+    /// The value is not a valid glob.
+    Glob(crate::GlobError),
+}
+
+impl fmt::Display for PatternErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternErrorKind::MissingKind => f.write_str("expected `kind:value`"),
+            PatternErrorKind::UnknownKind(kind) => write!(f, "unknown filter kind `{kind}`"),
+            PatternErrorKind::MissingValue => f.write_str("missing value"),
+            #[cfg(feature = "regex")]
+            PatternErrorKind::Regex(err) => write!(f, "invalid regex: {err}"),
+            #[cfg(feature = "glob")]
+            PatternErrorKind::Glob(err) => write!(f, "invalid glob: {}", err.reason),
+        }
+    }
+}
+
+/// An error with the location of the offending text in a pattern file.
+#[derive(Debug)]
+pub struct PatternError {
+    /// The file the pattern was read from, if any.
+    pub file: Option<PathBuf>,
+    /// The 1-based line number.
+    pub line: usize,
+    /// The 1-based column, counted in characters, where the offending text starts.
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+    /// The reason the line could not be parsed.
+    pub kind: PatternErrorKind,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(
+            f,
+            "{}:{}: {} at `{}`",
+            self.line, self.column, self.kind, self.text
+        )
+    }
+}
+
+impl Error for PatternError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            #[cfg(feature = "regex")]
+            PatternErrorKind::Regex(err) => Some(err),
+            #[cfg(feature = "glob")]
+            PatternErrorKind::Glob(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// An error that occurred while loading a pattern file.
+#[derive(Debug)]
+pub enum PatternFileError {
+    /// The file could not be read.
+    Io {
+        /// The file that was read.
+        file: PathBuf,
+        /// The underlying error.
+        source: io::Error,
+    },
+    /// A line of the file could not be parsed.
+    Pattern(PatternError),
+}
+
+impl fmt::Display for PatternFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternFileError::Io { file, source } => {
+                write!(f, "failed to read {}: {source}", file.display())
+            }
+            PatternFileError::Pattern(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for PatternFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PatternFileError::Io { source, .. } => Some(source),
+            PatternFileError::Pattern(err) => Some(err),
+        }
+    }
+}
+
+impl From<PatternError> for PatternFileError {
+    fn from(value: PatternError) -> Self {
+        PatternFileError::Pattern(value)
+    }
+}
+
+/// Parses the contents of a pattern file into a list of filters.
+///
+/// # Examples
+/// ```
+/// use pathfilter::patterns;
+///
+/// let err = patterns::parse("ext:.rs\npath:*.txt\n").unwrap_err();
+/// assert_eq!(err.line, 2);
+/// assert_eq!(err.column, 1);
+/// assert_eq!(err.text, "path");
+/// ```
+/// # Errors
+/// If a line cannot be parsed, an error with its location is returned.
+pub fn parse(text: &str) -> Result<Vec<PathFilter>, PatternError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !is_skipped(line))
+        .map(|(index, line)| parse_line(line, index + 1))
+        .collect()
+}
+
+/// Parses the contents of a pattern file into ordered rules, with `!` lines re-including paths.
+///
+/// A line `!kind:value` becomes an [`Allow`](FilterRule::Allow) rule, all other
+/// lines become [`Ignore`](FilterRule::Ignore) rules. As in a `.gitignore`, the
+/// last line matching a path decides whether it is ignored.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{patterns, IgnorePath};
+/// use std::path::Path;
+///
+/// let set = patterns::parse_set("ext:.log,.tmp\n!ext:.tmp\n").unwrap();
+/// assert!(set.ignore(Path::new("debug.log")));
+/// assert!(!set.ignore(Path::new("scratch.tmp")));
+/// ```
+/// # Errors
+/// If a line cannot be parsed, an error with its location is returned.
+pub fn parse_set(text: &str) -> Result<FilterSet, PatternError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !is_skipped(line))
+        .map(|(index, line)| parse_set_line(line, index + 1))
+        .collect()
+}
+
+/// Parses a line of a set, where a leading `!` makes it an allow rule.
+pub(crate) fn parse_set_line(line: &str, number: usize) -> Result<FilterRule, PatternError> {
+    if line.trim_start().starts_with('!') {
+        // Blanking the `!` keeps the columns of errors in the original line.
+        parse_line(&line.replacen('!', " ", 1), number).map(FilterRule::Allow)
+    } else {
+        parse_line(line, number).map(FilterRule::Ignore)
+    }
+}
+
+/// Parses the contents of a pattern file, reporting every invalid line.
+///
+/// Unlike [`parse`], this does not stop at the first invalid line, so all
+/// mistakes in a file can be reported at once. With the `parallel` feature, large
+/// files are compiled on multiple threads. The filters and errors are in the order
+/// of their lines either way.
+///
+/// # Examples
+/// ```
+/// use pathfilter::patterns;
+///
+/// let errors = patterns::parse_all("ext:.rs\npath:*.txt\next:\n").unwrap_err();
+/// let lines: Vec<_> = errors.iter().map(|err| err.line).collect();
+/// assert_eq!(lines, [2, 3]);
+/// ```
+/// # Errors
+/// If any line cannot be parsed, the errors of all such lines are returned.
+pub fn parse_all(text: &str) -> Result<Vec<PathFilter>, Vec<PatternError>> {
+    let lines: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !is_skipped(line))
+        .map(|(index, line)| (index + 1, line))
+        .collect();
+
+    let mut filters = Vec::with_capacity(lines.len());
+    let mut errors = Vec::new();
+    for result in compile(&lines, parse_line) {
+        match result {
+            Ok(filter) => filters.push(filter),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(filters)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parses the numbered lines with `parse`, in order.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn compile<T>(
+    lines: &[(usize, &str)],
+    parse: fn(&str, usize) -> Result<T, PatternError>,
+) -> Vec<Result<T, PatternError>> {
+    lines
+        .iter()
+        .map(|&(number, line)| parse(line, number))
+        .collect()
+}
+
+/// Parses the numbered lines with `parse`, on multiple threads if there are many.
+#[cfg(feature = "parallel")]
+pub(crate) fn compile<T: Send>(
+    lines: &[(usize, &str)],
+    parse: fn(&str, usize) -> Result<T, PatternError>,
+) -> Vec<Result<T, PatternError>> {
+    use std::{num::NonZeroUsize, panic, thread};
+
+    /// Files with fewer lines than this are not worth spawning threads for.
+    const MIN_LINES: usize = 64;
+
+    let compile_chunk = |chunk: &[(usize, &str)]| -> Vec<_> {
+        chunk
+            .iter()
+            .map(|&(number, line)| parse(line, number))
+            .collect()
+    };
+
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    if threads == 1 || lines.len() < MIN_LINES {
+        return compile_chunk(lines);
+    }
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = lines
+            .chunks(lines.len().div_ceil(threads))
+            .map(|chunk| scope.spawn(move || compile_chunk(chunk)))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|err| panic::resume_unwind(err))
+            })
+            .collect()
+    })
+}
+
+/// Reads and parses a pattern file.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::patterns;
+///
+/// let filters = patterns::parse_file(".pathfilter").unwrap();
+/// ```
+/// # Errors
+/// If the file cannot be read or a line cannot be parsed, an error is returned.
+/// Parse errors carry the name of the file.
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Vec<PathFilter>, PatternFileError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path).map_err(|source| PatternFileError::Io {
+        file: path.to_path_buf(),
+        source,
+    })?;
+
+    parse(&text).map_err(|mut err| {
+        err.file = Some(path.to_path_buf());
+        err.into()
+    })
+}
+
+/// An error that occurred while loading rules from an environment variable.
+#[derive(Debug)]
+pub enum PatternEnvError {
+    /// The variable is not valid unicode.
+    NotUnicode {
+        /// The name of the variable.
+        name: String,
+        /// The value of the variable.
+        value: OsString,
+    },
+    /// A rule could not be parsed.
+    Pattern(PatternError),
+}
+
+impl fmt::Display for PatternEnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternEnvError::NotUnicode { name, .. } => {
+                write!(f, "environment variable {name} is not valid unicode")
+            }
+            PatternEnvError::Pattern(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for PatternEnvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PatternEnvError::NotUnicode { .. } => None,
+            PatternEnvError::Pattern(err) => Some(err),
+        }
+    }
+}
+
+impl From<PatternError> for PatternEnvError {
+    fn from(value: PatternError) -> Self {
+        PatternEnvError::Pattern(value)
+    }
+}
+
+/// Parses rules written on a single line, separated by `;`.
+///
+/// Every rule is written like a line of a pattern file. Empty rules and
+/// surrounding whitespace are skipped. A literal `;` is written as `\;`, any other
+/// `\` is kept as it is, so regular expressions need no further escaping.
+///
+/// Errors report the 1-based number of the rule as [`line`](PatternError::line).
+///
+/// # Examples
+/// ```
+/// use pathfilter::patterns;
+/// use pathfilter::IgnorePath;
+/// use std::path::Path;
+///
+/// let filters = patterns::parse_rules("ext:.o,.a; ext:.log").unwrap();
+/// assert!(filters.ignore(Path::new("build/main.o")));
+/// assert!(filters.ignore(Path::new("build.log")));
+///
+/// let err = patterns::parse_rules("ext:.o;bad").unwrap_err();
+/// assert_eq!(err.line, 2);
+/// ```
+/// # Errors
+/// If a rule cannot be parsed, an error with its location is returned.
+pub fn parse_rules(rules: &str) -> Result<Vec<PathFilter>, PatternError> {
+    split_rules(rules)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, rule)| !rule.trim().is_empty())
+        .map(|(index, rule)| parse_line(&rule, index + 1))
+        .collect()
+}
+
+/// Parses rules written on a single line, separated by `;`, into a [`FilterSet`].
+///
+/// The rules are written like for [`parse_rules`], and like in [`parse_set`], a
+/// rule starting with `!` re-includes paths an earlier rule ignores.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{patterns, IgnorePath};
+/// use std::path::Path;
+///
+/// let set = patterns::parse_rule_set("ext:.log,.tmp; !ext:.tmp").unwrap();
+/// assert!(set.ignore(Path::new("debug.log")));
+/// assert!(!set.ignore(Path::new("scratch.tmp")));
+/// ```
+/// # Errors
+/// If a rule cannot be parsed, an error with its location is returned.
+pub fn parse_rule_set(rules: &str) -> Result<FilterSet, PatternError> {
+    split_rules(rules)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, rule)| !rule.trim().is_empty())
+        .map(|(index, rule)| parse_set_line(&rule, index + 1))
+        .collect()
+}
+
+/// Parses the rules in the environment variable `name` with [`parse_rule_set`].
+///
+/// Rules are separated by `;` and a literal `;`, e.g. in a regular expression,
+/// is written as `\;`. Rules starting with `!` re-include paths. An unset
+/// variable yields an empty set.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::patterns;
+///
+/// // PATHFILTER_RULES='ext:.o;regex:(^|/)tmp/;!regex:^tmp/keep\;me$'
+/// let set = patterns::from_env("PATHFILTER_RULES").unwrap();
+/// ```
+/// # Errors
+/// If the variable is not valid unicode or a rule cannot be parsed, an error is returned.
+pub fn from_env(name: &str) -> Result<FilterSet, PatternEnvError> {
+    match env::var(name) {
+        Ok(rules) => Ok(parse_rule_set(&rules)?),
+        Err(env::VarError::NotPresent) => Ok(FilterSet::new()),
+        Err(env::VarError::NotUnicode(value)) => Err(PatternEnvError::NotUnicode {
+            name: name.to_string(),
+            value,
+        }),
+    }
+}
+
+fn split_rules(rules: &str) -> Vec<String> {
+    let mut split = vec![String::new()];
+    let mut chars = rules.chars().peekable();
+    while let Some(c) = chars.next() {
+        let rule = split.last_mut().expect("split is never empty");
+        match c {
+            '\\' if chars.peek() == Some(&';') => rule.push(chars.next().expect("peeked")),
+            ';' => split.push(String::new()),
+            c => rule.push(c),
+        }
+    }
+    split
+}
+
+/// A line of a [`PatternFile`].
+#[derive(Clone, Debug)]
+pub struct PatternLine {
+    text: String,
+    rule: Option<FilterRule>,
+}
+
+impl PatternLine {
+    /// Returns the line as it is written in the file, without the line ending.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the filter of the line, or `None` if it is blank or a comment.
+    pub fn filter(&self) -> Option<&PathFilter> {
+        self.rule.as_ref().map(FilterRule::filter)
+    }
+
+    /// Returns the rule of the line, an allow rule if it starts with `!`, or
+    /// `None` if it is blank or a comment.
+    pub fn rule(&self) -> Option<&FilterRule> {
+        self.rule.as_ref()
+    }
+
+    fn parse(line: &str, number: usize) -> Result<Self, PatternError> {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let rule = if is_skipped(line) {
+            None
+        } else {
+            Some(parse_set_line(line, number)?)
+        };
+
+        Ok(PatternLine {
+            text: line.to_string(),
+            rule,
+        })
+    }
+}
+
+/// A pattern file that keeps its comments, blank lines and formatting.
+///
+/// Lines are numbered from 1. Writing the file back with [`Display`](fmt::Display)
+/// reproduces unchanged lines exactly, including the line ending style and whether
+/// the file ends with a newline.
+///
+/// Like for [`parse_set`], a line starting with `!` re-includes paths an earlier
+/// line ignores, and the last line matching a path decides.
+///
+/// # Examples
+/// ```
+/// use pathfilter::patterns::PatternFile;
+/// use pathfilter::IgnorePath;
+/// use std::path::Path;
+///
+/// let mut file: PatternFile = "# build output\next:.o\n".parse().unwrap();
+/// file.push_comment("added by the installer");
+/// file.push("ext: .log").unwrap();
+///
+/// assert!(file.ignore(Path::new("install.log")));
+/// assert_eq!(
+///     file.to_string(),
+///     "# build output\next:.o\n# added by the installer\next: .log\n"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct PatternFile {
+    lines: Vec<PatternLine>,
+    line_ending: &'static str,
+    trailing_newline: bool,
+}
+
+impl Default for PatternFile {
+    fn default() -> Self {
+        PatternFile::new()
+    }
+}
+
+impl PatternFile {
+    /// Creates an empty pattern file.
+    pub fn new() -> Self {
+        PatternFile {
+            lines: Vec::new(),
+            line_ending: "\n",
+            trailing_newline: true,
+        }
+    }
+
+    /// Parses the contents of a pattern file.
+    ///
+    /// # Errors
+    /// If a line cannot be parsed, an error with its location is returned.
+    pub fn parse(text: &str) -> Result<Self, PatternError> {
+        let lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| PatternLine::parse(line, index + 1))
+            .collect::<Result<_, _>>()?;
+
+        Ok(PatternFile {
+            lines,
+            line_ending: if text.contains("\r\n") { "\r\n" } else { "\n" },
+            trailing_newline: text.is_empty() || text.ends_with('\n'),
+        })
+    }
+
+    /// Reads and parses a pattern file.
+    ///
+    /// # Errors
+    /// If the file cannot be read or a line cannot be parsed, an error is returned.
+    /// Parse errors carry the name of the file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, PatternFileError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| PatternFileError::Io {
+            file: path.to_path_buf(),
+            source,
+        })?;
+
+        PatternFile::parse(&text).map_err(|mut err| {
+            err.file = Some(path.to_path_buf());
+            err.into()
+        })
+    }
+
+    /// Writes the pattern file to `path`.
+    ///
+    /// # Errors
+    /// If the file cannot be written, an error is returned.
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Returns all lines, including blank lines and comments.
+    pub fn lines(&self) -> &[PatternLine] {
+        &self.lines
+    }
+
+    /// Returns the line with the given 1-based number.
+    pub fn line(&self, number: usize) -> Option<&PatternLine> {
+        number
+            .checked_sub(1)
+            .and_then(|index| self.lines.get(index))
+    }
+
+    /// Returns the filters of the file together with their line numbers.
+    ///
+    /// This includes the filters of `!` lines; use [`rules`](Self::rules) to tell
+    /// them apart.
+    pub fn filters(&self) -> impl Iterator<Item = (usize, &PathFilter)> {
+        self.rules().map(|(number, rule)| (number, rule.filter()))
+    }
+
+    /// Returns the rules of the file together with their line numbers.
+    pub fn rules(&self) -> impl Iterator<Item = (usize, &FilterRule)> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter_map(|(index, line)| line.rule().map(|rule| (index + 1, rule)))
+    }
+
+    /// Returns a copy of the filters of the file.
+    ///
+    /// A list of filters ignores every path one of them matches, so for a file
+    /// with `!` lines use [`to_filter_set`](Self::to_filter_set) instead.
+    pub fn to_filters(&self) -> Vec<PathFilter> {
+        self.filters().map(|(_, filter)| filter.clone()).collect()
+    }
+
+    /// Returns a copy of the rules of the file as a [`FilterSet`].
+    pub fn to_filter_set(&self) -> FilterSet {
+        self.rules().map(|(_, rule)| rule.clone()).collect()
+    }
+
+    /// Appends a pattern line.
+    ///
+    /// # Errors
+    /// If the line cannot be parsed, an error is returned and the file is unchanged.
+    pub fn push(&mut self, line: &str) -> Result<(), PatternError> {
+        self.insert(self.lines.len() + 1, line)
+    }
+
+    /// Appends a comment. A `# ` is added in front of every line of `comment`.
+    pub fn push_comment(&mut self, comment: &str) {
+        for line in comment.lines() {
+            self.lines.push(PatternLine {
+                text: format!("# {line}"),
+                rule: None,
+            });
+        }
+    }
+
+    /// Inserts a pattern line so that it gets the given 1-based line number.
+    ///
+    /// # Errors
+    /// If the line cannot be parsed, an error is returned and the file is unchanged.
+    ///
+    /// # Panics
+    /// Panics if `number` is 0 or more than one past the last line.
+    pub fn insert(&mut self, number: usize, line: &str) -> Result<(), PatternError> {
+        assert!(
+            (1..=self.lines.len() + 1).contains(&number),
+            "line number {number} out of range"
+        );
+        let line = PatternLine::parse(line, number)?;
+        self.lines.insert(number - 1, line);
+        Ok(())
+    }
+
+    /// Removes the line with the given 1-based number.
+    pub fn remove(&mut self, number: usize) -> Option<PatternLine> {
+        let index = number.checked_sub(1)?;
+        (index < self.lines.len()).then(|| self.lines.remove(index))
+    }
+
+    /// Removes every pattern line whose text, ignoring surrounding whitespace, is `pattern`.
+    ///
+    /// Returns the number of removed lines. Comments and blank lines are kept.
+    pub fn remove_pattern(&mut self, pattern: &str) -> usize {
+        let pattern = pattern.trim();
+        let before = self.lines.len();
+        self.lines
+            .retain(|line| line.rule.is_none() || line.text.trim() != pattern);
+        before - self.lines.len()
+    }
+}
+
+impl FromStr for PatternFile {
+    type Err = PatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PatternFile::parse(s)
+    }
+}
+
+impl fmt::Display for PatternFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, line) in self.lines.iter().enumerate() {
+            if index > 0 {
+                f.write_str(self.line_ending)?;
+            }
+            f.write_str(&line.text)?;
+        }
+        if self.trailing_newline && !self.lines.is_empty() {
+            f.write_str(self.line_ending)?;
+        }
+        Ok(())
+    }
+}
+
+impl IgnorePath for PatternFile {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        self.lines
+            .iter()
+            .rev()
+            .filter_map(PatternLine::rule)
+            .find(|rule| rule.filter().ignore_candidate(candidate))
+            .is_some_and(FilterRule::is_ignore)
+    }
+}
+
+/// Writes the filter as a line of a pattern file, if that preserves it exactly.
+///
+/// Filters with options, extensions that are not valid UTF-8 or contain `,` and
+/// regexes that match file names, span lines or start or end with whitespace
+/// have no such line.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{patterns, MatchOptions, PathFilter};
+///
+/// let filter = PathFilter::new_extensions([".md", ".txt"]);
+/// assert_eq!(patterns::to_rule(&filter).as_deref(), Some("ext:.md,.txt"));
+///
+/// let filter = filter.with_options(MatchOptions::new().case_insensitive(true));
+/// assert_eq!(patterns::to_rule(&filter), None);
+/// ```
+pub fn to_rule(filter: &PathFilter) -> Option<String> {
+    if !filter.options().is_default() || !filter.overrides().is_empty() {
+        return None;
+    }
+
+    let extension = |ext: &std::ffi::OsStr| {
+        ext.to_str()
+            .filter(|ext| !ext.is_empty() && !ext.starts_with('.') && !ext.contains(','))
+            .filter(|ext| ext.trim() == *ext)
+            .map(|ext| format!(".{ext}"))
+    };
+
+    match filter {
+        PathFilter::Extension(filter) => Some(format!("ext:{}", extension(filter.extension())?)),
+        PathFilter::Extensions(filter) => {
+            let mut extensions = filter
+                .extensions()
+                .map(extension)
+                .collect::<Option<Vec<_>>>()?;
+            if extensions.len() < 2 {
+                return None;
+            }
+            extensions.sort();
+            Some(format!("ext:{}", extensions.join(",")))
+        }
+        PathFilter::Component(_)
+        | PathFilter::FileName(_)
+        | PathFilter::Stem(_)
+        | PathFilter::Prefix(_)
+        | PathFilter::Wildcard(_)
+        | PathFilter::Not(_)
+        | PathFilter::And(_)
+        | PathFilter::Or(_) => None,
+        #[cfg(feature = "regex")]
+        PathFilter::Regex(filter) => {
+            let pattern = filter.as_str();
+            (filter.target().is_path()
+                && !pattern.is_empty()
+                && pattern.trim() == pattern
+                && !pattern.contains(['\n', '\r']))
+            .then(|| format!("regex:{pattern}"))
+        }
+        #[cfg(feature = "glob")]
+        PathFilter::Glob(filter) => {
+            let glob = filter.as_str();
+            (glob.trim() == glob && !glob.contains(['\n', '\r'])).then(|| format!("glob:{glob}"))
+        }
+        #[cfg(all(feature = "io", unix))]
+        PathFilter::Owner(_) => None,
+    }
+}
+
+pub(crate) fn is_skipped(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with('#')
+}
+
+pub(crate) fn parse_line(line: &str, number: usize) -> Result<PathFilter, PatternError> {
+    let indent = line.len() - line.trim_start().len();
+    let rule = line.trim();
+    let error = |offset: usize, text: &str, kind| PatternError {
+        file: None,
+        line: number,
+        column: line[..indent + offset].chars().count() + 1,
+        text: text.to_string(),
+        kind,
+    };
+
+    let (kind, value) = rule
+        .split_once(':')
+        .ok_or_else(|| error(0, rule, PatternErrorKind::MissingKind))?;
+    let value_offset = kind.len() + 1;
+    if value.is_empty() {
+        return Err(error(value_offset, rule, PatternErrorKind::MissingValue));
+    }
+
+    match kind {
+        "ext" => {
+            let extensions: Vec<&str> = value.split(',').map(str::trim).collect();
+            if extensions.iter().any(|ext| ext.is_empty()) {
+                return Err(error(value_offset, value, PatternErrorKind::MissingValue));
+            }
+            Ok(match extensions.as_slice() {
+                [extension] => ExtensionFilter::new(extension).into(),
+                extensions => ExtensionsFilter::new(extensions).into(),
+            })
+        }
+        #[cfg(feature = "regex")]
+        "regex" | "re" => {
+            let pattern = value.trim();
+            let offset = value_offset + value.len() - value.trim_start().len();
+            if pattern.is_empty() {
+                return Err(error(value_offset, rule, PatternErrorKind::MissingValue));
+            }
+            match regex::Regex::new(pattern) {
+                Ok(regex) => Ok(PathFilter::new_regex(regex)),
+                Err(err) => Err(error(offset, pattern, PatternErrorKind::Regex(err))),
+            }
+        }
+        #[cfg(feature = "glob")]
+        "glob" => {
+            let glob = value.trim();
+            let offset = value_offset + value.len() - value.trim_start().len();
+            match crate::GlobFilter::new(glob) {
+                Ok(glob) => Ok(glob.into()),
+                Err(err) => Err(error(offset, glob, PatternErrorKind::Glob(err))),
+            }
+        }
+        _ => Err(error(
+            0,
+            kind,
+            PatternErrorKind::UnknownKind(kind.to_string()),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        patterns::{
+            parse, parse_all, parse_rule_set, parse_rules, parse_set, split_rules, to_rule,
+            PatternErrorKind, PatternFile,
+        },
+        FilterRule, IgnorePath, PathFilter,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn parse_extensions() {
+        let filters = parse("# comment\n\next:.rs\n  ext: .txt, .md\n").unwrap();
+        assert_eq!(filters.len(), 2);
+        assert!(matches!(filters[0], PathFilter::Extension(_)));
+        assert!(matches!(filters[1], PathFilter::Extensions(_)));
+        assert!(filters.ignore(Path::new("src/lib.rs")));
+        assert!(filters.ignore(Path::new("README.md")));
+        assert!(!filters.ignore(Path::new("Cargo.toml")));
+    }
+
+    #[test]
+    fn re_includes() {
+        let set = parse_set("ext:.log\n  !ext:.log\next:.o\n").unwrap();
+        assert!(matches!(
+            set.rules(),
+            [
+                FilterRule::Ignore(_),
+                FilterRule::Allow(_),
+                FilterRule::Ignore(_)
+            ]
+        ));
+        assert!(!set.ignore(Path::new("debug.log")));
+        assert!(set.ignore(Path::new("main.o")));
+
+        let err = parse_set("ext:.o\n !path:*.log\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "path");
+    }
+
+    #[test]
+    fn all_errors() {
+        let mut text = String::new();
+        for i in 0..200 {
+            match i % 50 {
+                7 => text.push_str("path:*.rs\n"),
+                13 => text.push_str("ext:\n"),
+                _ => text.push_str(&format!("ext:.e{i}\n")),
+            }
+        }
+
+        let errors = parse_all(&text).unwrap_err();
+        let lines: Vec<_> = errors.iter().map(|err| err.line).collect();
+        assert_eq!(lines, [8, 14, 58, 64, 108, 114, 158, 164]);
+        assert!(matches!(errors[0].kind, PatternErrorKind::UnknownKind(_)));
+        assert!(matches!(errors[1].kind, PatternErrorKind::MissingValue));
+
+        let text: String = (0..200).map(|i| format!("ext:.e{i}\n")).collect();
+        let filters = parse_all(&text).unwrap();
+        assert_eq!(filters.len(), 200);
+        assert!(filters[0].ignore(Path::new("a.e0")));
+        assert!(filters[199].ignore(Path::new("a.e199")));
+        assert!(!filters[199].ignore(Path::new("a.e0")));
+    }
+
+    #[test]
+    fn error_locations() {
+        let err = parse("ext:.rs\n\n  .txt\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::MissingKind));
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(err.text, ".txt");
+        assert_eq!(err.to_string(), "3:3: expected `kind:value` at `.txt`");
+
+        let err = parse("ext:\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::MissingValue));
+        assert_eq!((err.line, err.column), (1, 5));
+
+        let err = parse("ext:.rs,,.txt\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::MissingValue));
+        assert_eq!((err.line, err.column), (1, 5));
+
+        let err = parse("ext:.rs\nfoo:bar\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::UnknownKind(ref kind) if kind == "foo"));
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn file_name_in_error() {
+        let mut err = parse("bad\n").unwrap_err();
+        err.file = Some("rules/.ignore".into());
+        assert_eq!(
+            err.to_string(),
+            "rules/.ignore:1:1: expected `kind:value` at `bad`"
+        );
+    }
+
+    #[test]
+    fn pattern_file_round_trip() {
+        for text in [
+            "",
+            "ext:.rs",
+            "# comment\n\n  ext: .txt, .md   \n",
+            "# windows\r\next:.o\r\n\r\n",
+        ] {
+            assert_eq!(PatternFile::parse(text).unwrap().to_string(), text);
+        }
+    }
+
+    #[test]
+    fn pattern_file_edits() {
+        let mut file = PatternFile::parse("# objects\r\next:.o\r\n").unwrap();
+        assert_eq!(
+            file.filters().map(|(line, _)| line).collect::<Vec<_>>(),
+            [2]
+        );
+        assert!(file.line(1).unwrap().filter().is_none());
+        assert!(file.line(0).is_none());
+
+        file.insert(2, "ext:.a").unwrap();
+        file.push_comment("logs\nand traces");
+        file.push("ext:.log").unwrap();
+        assert!(file.ignore(Path::new("lib.a")));
+        assert_eq!(
+            file.to_string(),
+            "# objects\r\next:.a\r\next:.o\r\n# logs\r\n# and traces\r\next:.log\r\n"
+        );
+
+        let err = file.push("bad").unwrap_err();
+        assert_eq!(err.line, 7);
+        assert_eq!(file.lines().len(), 6);
+
+        assert_eq!(file.remove_pattern(" ext:.o"), 1);
+        assert_eq!(file.remove(1).unwrap().text(), "# objects");
+        assert!(file.remove(6).is_none());
+        assert_eq!(file.to_filters().len(), 2);
+        assert!(!file.ignore(Path::new("main.o")));
+    }
+
+    #[test]
+    fn pattern_file_allow_lines() {
+        let mut file = PatternFile::parse("# logs\next:.log,.tmp\n  !ext:.tmp\n").unwrap();
+        assert!(file.ignore(Path::new("debug.log")));
+        assert!(!file.ignore(Path::new("scratch.tmp")));
+        assert!(matches!(
+            file.line(3).unwrap().rule(),
+            Some(FilterRule::Allow(_))
+        ));
+        assert_eq!(
+            file.rules().map(|(line, _)| line).collect::<Vec<_>>(),
+            [2, 3]
+        );
+        assert_eq!(file.to_string(), "# logs\next:.log,.tmp\n  !ext:.tmp\n");
+
+        let set = file.to_filter_set();
+        assert_eq!(set.len(), 2);
+        assert!(!set.ignore(Path::new("scratch.tmp")));
+
+        // Later lines can ignore paths again.
+        file.push("ext:.tmp").unwrap();
+        assert!(file.ignore(Path::new("scratch.tmp")));
+        let err = file.push("!ext:").unwrap_err();
+        assert_eq!((err.line, err.column), (5, 6));
+    }
+
+    #[test]
+    fn rules() {
+        assert_eq!(split_rules(r"a;b\;c;;d\e"), ["a", "b;c", "", "d\\e"]);
+        assert!(parse_rules("").unwrap().is_empty());
+
+        let filters = parse_rules(" ext:.o ;;ext:.a;").unwrap();
+        assert_eq!(filters.len(), 2);
+        assert!(filters.ignore(Path::new("lib.a")));
+
+        let err = parse_rules("ext:.o;;ext:").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::MissingValue));
+        assert_eq!((err.line, err.column), (3, 5));
+
+        let set = parse_rule_set("ext:.log;; !ext:.tmp").unwrap();
+        assert_eq!(set.len(), 2);
+        assert!(matches!(set.rules()[1], FilterRule::Allow(_)));
+        let err = parse_rule_set("ext:.o; !ext:").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 7));
+    }
+
+    #[test]
+    fn from_env() {
+        use super::from_env;
+
+        let name = "PATHFILTER_TEST_FROM_ENV";
+        assert!(from_env(name).unwrap().is_empty());
+        std::env::set_var(name, "ext:.log,.tmp;!ext:.tmp");
+        let set = from_env(name).unwrap();
+        std::env::remove_var(name);
+        assert!(set.ignore(Path::new("debug.log")));
+        assert!(!set.ignore(Path::new("scratch.tmp")));
+    }
+
+    #[test]
+    fn rule_round_trip() {
+        use crate::{ExtensionFilter, ExtensionsFilter, MatchOverrides};
+
+        for rule in ["ext:.rs", "ext:.md,.txt"] {
+            assert_eq!(to_rule(&parse(rule).unwrap()[0]).as_deref(), Some(rule));
+        }
+
+        assert_eq!(to_rule(&ExtensionsFilter::new([".rs"]).into()), None);
+        assert_eq!(to_rule(&ExtensionFilter::new("a,b").into()), None);
+        let filter = PathFilter::new_extension(".rs")
+            .with_overrides(MatchOverrides::new().case_insensitive(false));
+        assert_eq!(to_rule(&filter), None);
+        #[cfg(feature = "regex")]
+        {
+            let rule = r"regex:(^|/)tmp/.*\.log$";
+            assert_eq!(to_rule(&parse(rule).unwrap()[0]).as_deref(), Some(rule));
+            assert_eq!(
+                to_rule(&PathFilter::new_regex(regex::Regex::new(" a").unwrap())),
+                None
+            );
+        }
+        #[cfg(feature = "glob")]
+        {
+            let rule = "glob:**/target/**";
+            assert_eq!(to_rule(&parse(rule).unwrap()[0]).as_deref(), Some(rule));
+        }
+    }
+
+    #[cfg(feature = "glob")]
+    #[test]
+    fn parse_glob() {
+        let filters = parse("glob:*.min.js\nglob: /dist/**\n").unwrap();
+        assert!(matches!(filters[0], PathFilter::Glob(_)));
+        assert!(filters.ignore(Path::new("web/app.min.js")));
+        assert!(filters.ignore(Path::new("dist/index.html")));
+        assert!(!filters.ignore(Path::new("web/dist/index.html")));
+
+        let err = parse("glob:  [a-\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::Glob(_)));
+        assert_eq!((err.line, err.column), (1, 8));
+        assert_eq!(err.text, "[a-");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn parse_regex() {
+        let filters = parse("regex:^src/\nre:\\.log$\n").unwrap();
+        assert!(filters.ignore(Path::new("src/lib.rs")));
+        assert!(filters.ignore(Path::new("build.log")));
+        assert!(!filters.ignore(Path::new("README.md")));
+
+        let err = parse("ext:.rs\n  regex:(unclosed\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::Regex(_)));
+        assert_eq!((err.line, err.column), (2, 9));
+        assert_eq!(err.text, "(unclosed");
+
+        let filters = parse("regex: ^src/ \nre:\t\\.log$\n").unwrap();
+        assert!(filters.ignore(Path::new("src/lib.rs")));
+        assert!(filters.ignore(Path::new("build.log")));
+        assert!(matches!(
+            parse("regex:  \n").unwrap_err().kind,
+            PatternErrorKind::MissingValue
+        ));
+
+        let filters = parse_rules("ext:.o; regex: ^tmp/").unwrap();
+        assert!(filters.ignore(Path::new("tmp/cache")));
+
+        let err = parse("re:  (unclosed\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+        assert_eq!(err.text, "(unclosed");
+    }
+}
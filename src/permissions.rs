@@ -0,0 +1,147 @@
+use crate::{content::io_error_policy_methods, IgnorePath, IoErrorPolicy};
+use std::{fs, io, path::Path};
+
+/// A filter that matches files by their permissions.
+///
+/// The permissions are read with [`fs::metadata`], so symlinks are followed.
+/// Directories and other entries that are not files are never matched.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{IgnorePath, PermissionsFilter};
+///
+/// let filter = PermissionsFilter::executables();
+/// assert!(filter.ignore("target/release/app"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PermissionsFilter {
+    check: Check,
+    on_error: IoErrorPolicy,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Check {
+    Executable,
+    ReadOnly,
+    #[cfg(unix)]
+    ModeMask(u32),
+}
+
+impl IgnorePath for PermissionsFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+impl PermissionsFilter {
+    const fn with_check(check: Check) -> Self {
+        PermissionsFilter {
+            check,
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    /// Creates a new filter for executable files.
+    ///
+    /// On Unix, a file is executable if any of its execute bits is set. Elsewhere
+    /// there is no such permission and no file is matched.
+    pub const fn executables() -> Self {
+        PermissionsFilter::with_check(Check::Executable)
+    }
+
+    /// Creates a new filter for read-only files, see [`fs::Permissions::readonly`].
+    pub const fn read_only() -> Self {
+        PermissionsFilter::with_check(Check::ReadOnly)
+    }
+
+    /// Creates a new filter for files with any of the bits of `mask` set in their Unix mode.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use pathfilter::{IgnorePath, PermissionsFilter};
+    ///
+    /// let world_writable = PermissionsFilter::mode_mask(0o002);
+    /// assert!(world_writable.ignore("shared/notes.txt"));
+    /// ```
+    #[cfg(unix)]
+    pub const fn mode_mask(mask: u32) -> Self {
+        PermissionsFilter::with_check(Check::ModeMask(mask))
+    }
+
+    io_error_policy_methods!(const "paths whose metadata cannot be read", "e.g. because they do not exist");
+
+    /// Returns whether a file with these permissions is matched.
+    pub fn matches_permissions(&self, permissions: &fs::Permissions) -> bool {
+        match self.check {
+            #[cfg(unix)]
+            Check::Executable => mode(permissions) & 0o111 != 0,
+            #[cfg(not(unix))]
+            Check::Executable => false,
+            Check::ReadOnly => permissions.readonly(),
+            #[cfg(unix)]
+            Check::ModeMask(mask) => mode(permissions) & mask != 0,
+        }
+    }
+
+    /// Returns whether the path is a file with matching permissions.
+    ///
+    /// # Errors
+    /// If the metadata cannot be read, an error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let metadata = fs::metadata(path)?;
+        Ok(metadata.is_file() && self.matches_permissions(&metadata.permissions()))
+    }
+}
+
+#[cfg(unix)]
+fn mode(permissions: &fs::Permissions) -> u32 {
+    std::os::unix::fs::PermissionsExt::mode(permissions)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{content::tests::TempDir, IgnorePath, IoErrorPolicy, PermissionsFilter};
+    use std::fs;
+
+    #[test]
+    fn read_only() {
+        let dir = TempDir::new();
+        let file = dir.write("file", "");
+        let filter = PermissionsFilter::read_only();
+        assert!(!filter.ignore(&file));
+
+        let mut permissions = fs::metadata(&file).unwrap().permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&file, permissions.clone()).unwrap();
+        assert!(filter.ignore(&file));
+        assert!(!filter.ignore(dir.path()));
+
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        fs::set_permissions(&file, permissions).unwrap();
+
+        let missing = dir.path().join("missing");
+        assert!(!filter.ignore(&missing));
+        assert!(filter
+            .with_io_error_policy(IoErrorPolicy::Ignore)
+            .ignore(&missing));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn modes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new();
+        let script = dir.write("script.sh", "#!/bin/sh\n");
+        let data = dir.write("data.txt", "");
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o750)).unwrap();
+        fs::set_permissions(&data, fs::Permissions::from_mode(0o646)).unwrap();
+
+        assert!(PermissionsFilter::executables().ignore(&script));
+        assert!(!PermissionsFilter::executables().ignore(&data));
+        assert!(!PermissionsFilter::executables().ignore(dir.path()));
+        assert!(PermissionsFilter::mode_mask(0o002).ignore(&data));
+        assert!(!PermissionsFilter::mode_mask(0o002).ignore(&script));
+    }
+}
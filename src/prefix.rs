@@ -0,0 +1,229 @@
+use crate::{
+    extension::name_eq,
+    normalize::{strip_verbatim_prefix, strips_verbatim},
+    options::options_methods,
+    IgnorePath, MatchOptions, MatchOverrides,
+};
+use std::{
+    borrow::Cow,
+    ffi::OsStr,
+    path::{Component, Path, PathBuf, Prefix},
+};
+
+/// A filter that matches paths starting with one of a set of prefixes.
+///
+/// Prefixes are compared component by component, so `build/` matches `build` and
+/// `build/out/main.o` but not `build2/main.o`. Leading `./` components are
+/// ignored on both sides, and a prefix without any components matches nothing.
+/// On Windows, or with [`MatchOptions::normalize_separators`], extended-length
+/// prefixes are stripped, so `\\?\C:\src` starts with `C:\src`. Drive letters
+/// and UNC servers and shares are compared without regard to case.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, PrefixFilter};
+/// use std::path::Path;
+///
+/// let filter = PrefixFilter::new(["build/", "third_party/vendored"]);
+/// assert!(filter.ignore(Path::new("build/out/main.o")));
+/// assert!(filter.ignore(Path::new("./third_party/vendored/zlib/zlib.h")));
+/// assert!(!filter.ignore(Path::new("build2/main.o")));
+/// assert!(!filter.ignore(Path::new("third_party/vendored2")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrefixFilter {
+    prefixes: Vec<PathBuf>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOptions::is_default")
+    )]
+    options: MatchOptions,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOverrides::is_empty")
+    )]
+    overrides: MatchOverrides,
+}
+
+impl IgnorePath for PrefixFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        let options = self.options();
+        let path = normalized(path.as_ref(), options);
+        self.prefixes
+            .iter()
+            .any(|prefix| starts_with(&path, &normalized(prefix, options), options))
+    }
+}
+
+fn normalized(path: &Path, options: MatchOptions) -> Cow<'_, Path> {
+    let Some(text) = path.to_str() else {
+        return Cow::Borrowed(path);
+    };
+    let text = if strips_verbatim(options) {
+        strip_verbatim_prefix(text)
+    } else {
+        Cow::Borrowed(text)
+    };
+
+    match text {
+        text if options.normalizes_separators() && text.contains('\\') => {
+            Cow::Owned(PathBuf::from(text.replace('\\', "/")))
+        }
+        Cow::Borrowed(text) => Cow::Borrowed(Path::new(text)),
+        Cow::Owned(text) => Cow::Owned(PathBuf::from(text)),
+    }
+}
+
+fn starts_with(path: &Path, prefix: &Path, options: MatchOptions) -> bool {
+    let relevant = |component: &Component<'_>| *component != Component::CurDir;
+    let mut path = path.components().filter(relevant);
+    let mut prefix = prefix.components().filter(relevant).peekable();
+    prefix.peek().is_some()
+        && prefix.all(|expected| {
+            path.next()
+                .is_some_and(|actual| component_eq(actual, expected, options))
+        })
+}
+
+fn component_eq(actual: Component<'_>, expected: Component<'_>, options: MatchOptions) -> bool {
+    match (actual, expected) {
+        (Component::Prefix(actual), Component::Prefix(expected)) => {
+            prefix_eq(actual.kind(), expected.kind())
+        }
+        // Windows paths on other platforms start with the drive as a normal component.
+        (Component::Normal(actual), Component::Normal(expected))
+            if options.normalizes_separators() && is_drive(actual) && is_drive(expected) =>
+        {
+            actual.eq_ignore_ascii_case(expected)
+        }
+        _ => name_eq(actual.as_os_str(), expected.as_os_str(), options),
+    }
+}
+
+/// Returns whether two prefixes name the same drive or share, which Windows
+/// compares without regard to case or to whether the prefix is verbatim.
+fn prefix_eq(actual: Prefix<'_>, expected: Prefix<'_>) -> bool {
+    match (actual, expected) {
+        (
+            Prefix::Disk(actual) | Prefix::VerbatimDisk(actual),
+            Prefix::Disk(expected) | Prefix::VerbatimDisk(expected),
+        ) => actual.eq_ignore_ascii_case(&expected),
+        (
+            Prefix::UNC(server, share) | Prefix::VerbatimUNC(server, share),
+            Prefix::UNC(expected_server, expected_share)
+            | Prefix::VerbatimUNC(expected_server, expected_share),
+        ) => {
+            server.eq_ignore_ascii_case(expected_server)
+                && share.eq_ignore_ascii_case(expected_share)
+        }
+        _ => actual == expected,
+    }
+}
+
+fn is_drive(name: &OsStr) -> bool {
+    matches!(name.as_encoded_bytes(), [drive, b':'] if drive.is_ascii_alphabetic())
+}
+
+impl PrefixFilter {
+    /// Creates a new filter for a list of prefixes.
+    pub fn new<I, P>(prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        PrefixFilter {
+            prefixes: prefixes.into_iter().map(Into::into).collect(),
+            options: MatchOptions::new(),
+            overrides: MatchOverrides::new(),
+        }
+    }
+
+    /// Adds a prefix to the filter.
+    pub fn with_prefix<P: Into<PathBuf>>(mut self, prefix: P) -> Self {
+        self.prefixes.push(prefix.into());
+        self
+    }
+
+    /// Returns the prefixes this filter matches, in the order they were added.
+    pub fn prefixes(&self) -> &[PathBuf] {
+        &self.prefixes
+    }
+
+    /// Returns `true` if a prefix lies inside of `dir`, so paths inside of it can
+    /// match even if `dir` does not.
+    pub(crate) fn has_prefix_inside(&self, dir: &Path) -> bool {
+        let options = self.options();
+        let dir = normalized(dir, options);
+        self.prefixes
+            .iter()
+            .any(|prefix| starts_with(&normalized(prefix, options), &dir, options))
+    }
+
+    options_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IgnorePath, MatchOptions, PrefixFilter};
+    use std::path::Path;
+
+    #[test]
+    fn component_boundaries() {
+        let filter = PrefixFilter::new(["build/"]).with_prefix("a/b");
+        assert!(filter.ignore(Path::new("build")));
+        assert!(filter.ignore(Path::new("build/")));
+        assert!(filter.ignore(Path::new("a/b/c")));
+        assert!(!filter.ignore(Path::new("build2")));
+        assert!(!filter.ignore(Path::new("a/bc")));
+        assert!(!filter.ignore(Path::new("a")));
+        assert!(!filter.ignore(Path::new("src/build/x")));
+        assert!(!filter.ignore(Path::new("Build/x")));
+
+        assert!(!PrefixFilter::new([""]).ignore(Path::new("src")));
+        assert!(!PrefixFilter::new(["./"]).ignore(Path::new("src")));
+    }
+
+    #[test]
+    fn options() {
+        let options = MatchOptions::new()
+            .case_insensitive(true)
+            .normalize_separators(true);
+        let filter = PrefixFilter::new(["third_party/vendored"]).with_options(options);
+        assert!(filter.ignore(Path::new("Third_Party/Vendored/zlib")));
+        assert!(filter.ignore(Path::new("third_party\\vendored\\zlib")));
+        assert!(!filter.ignore(Path::new("third_party\\vendored2")));
+
+        let filter = PrefixFilter::new([r"C:\src"]).with_options(options);
+        assert!(filter.ignore(Path::new(r"\\?\C:\src\lib.rs")));
+        let filter = PrefixFilter::new([r"\\server\share"]).with_options(options);
+        assert!(filter.ignore(Path::new(r"\\?\UNC\server\share\a.txt")));
+
+        let options = MatchOptions::new().normalize_separators(true);
+        let filter = PrefixFilter::new([r"C:\src"]).with_options(options);
+        assert!(filter.ignore(Path::new(r"c:\src\lib.rs")));
+        assert!(filter.ignore(Path::new(r"\\?\c:\src\lib.rs")));
+        assert!(!filter.ignore(Path::new(r"c:\Src\lib.rs")));
+        assert!(!filter.ignore(Path::new(r"D:\src\lib.rs")));
+    }
+
+    #[test]
+    fn prefix_eq() {
+        use super::prefix_eq;
+        use std::{ffi::OsStr, path::Prefix};
+
+        assert!(prefix_eq(Prefix::Disk(b'C'), Prefix::Disk(b'c')));
+        assert!(prefix_eq(Prefix::VerbatimDisk(b'c'), Prefix::Disk(b'C')));
+        assert!(!prefix_eq(Prefix::Disk(b'C'), Prefix::Disk(b'D')));
+
+        let (server, share) = (OsStr::new("Server"), OsStr::new("Share"));
+        let unc = Prefix::UNC(OsStr::new("server"), OsStr::new("share"));
+        assert!(prefix_eq(Prefix::VerbatimUNC(server, share), unc));
+        assert!(!prefix_eq(Prefix::UNC(server, OsStr::new("other")), unc));
+        assert!(!prefix_eq(Prefix::Disk(b'C'), unc));
+        assert!(prefix_eq(
+            Prefix::DeviceNS(OsStr::new("COM1")),
+            Prefix::DeviceNS(OsStr::new("COM1"))
+        ));
+    }
+}
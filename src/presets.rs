@@ -0,0 +1,311 @@
+//! Ready-made filters for common kinds of files.
+//!
+//! The presets are constants or `const fn`s, so they can be used in `static`s and `const`s.
+//!
+//! # Examples
+//! ```
+//! use pathfilter::{presets, IgnorePath};
+//! use std::path::Path;
+//!
+//! let filters = [presets::ARCHIVES, presets::IMAGES];
+//! assert!(filters.ignore(Path::new("backup.tar.gz")));
+//! assert!(filters.ignore(Path::new("logo.png")));
+//! assert!(!filters.ignore(Path::new("src/lib.rs")));
+//! ```
+
+#[cfg(feature = "io")]
+use crate::IoErrorPolicy;
+use crate::{IgnorePath, StaticExtensionsFilter};
+use std::path::Path;
+
+/// Object files, libraries and other compiler output.
+pub const BUILD_ARTIFACTS: StaticExtensionsFilter = StaticExtensionsFilter::new(&[
+    "o", "obj", "a", "lib", "so", "dll", "dylib", "exe", "pdb", "rlib", "class", "pyc",
+]);
+
+/// Compressed files and archives.
+pub const ARCHIVES: StaticExtensionsFilter =
+    StaticExtensionsFilter::new(&["zip", "tar", "gz", "tgz", "bz2", "xz", "zst", "7z", "rar"]);
+
+/// Raster and vector images.
+pub const IMAGES: StaticExtensionsFilter = StaticExtensionsFilter::new(&[
+    "png", "jpg", "jpeg", "gif", "bmp", "webp", "tif", "tiff", "ico", "svg",
+]);
+
+/// Drafts and partials of static site generators, see [`StaticSiteFilter`].
+pub const STATIC_SITE: StaticSiteFilter = StaticSiteFilter::new();
+
+/// A filter for files a static site generator does not publish.
+///
+/// Ignores paths with a component starting with `_`, like `_includes/header.html`,
+/// and files marked as draft in their name, like `post.draft.md`. Use relative
+/// paths, as every component is checked.
+///
+/// With the `io` feature, [`with_front_matter`](Self::with_front_matter) also
+/// ignores files whose YAML (`---`) or TOML (`+++`) front matter contains
+/// `draft: true` or `draft = true`.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{presets, IgnorePath};
+///
+/// assert!(presets::STATIC_SITE.ignore("_layouts/default.html"));
+/// assert!(presets::STATIC_SITE.ignore("posts/_wip.md"));
+/// assert!(presets::STATIC_SITE.ignore("posts/hello.draft.md"));
+/// assert!(!presets::STATIC_SITE.ignore("posts/hello.md"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StaticSiteFilter {
+    #[cfg(feature = "io")]
+    front_matter: bool,
+    #[cfg(feature = "io")]
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for StaticSiteFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        let ignored = path
+            .iter()
+            .any(|component| component.to_string_lossy().starts_with('_'))
+            || path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().contains(".draft."));
+
+        #[cfg(feature = "io")]
+        if !ignored && self.front_matter {
+            return self.on_error.resolve(front_matter::is_draft(path));
+        }
+
+        ignored
+    }
+}
+
+impl StaticSiteFilter {
+    /// Creates a new filter that only looks at paths.
+    pub const fn new() -> Self {
+        StaticSiteFilter {
+            #[cfg(feature = "io")]
+            front_matter: false,
+            #[cfg(feature = "io")]
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    /// Sets whether files marked as draft in their front matter are ignored.
+    ///
+    /// This reads the start of every file that is not ignored by its path.
+    #[cfg(feature = "io")]
+    pub const fn with_front_matter(mut self, front_matter: bool) -> Self {
+        self.front_matter = front_matter;
+        self
+    }
+
+    #[cfg(feature = "io")]
+    crate::content::io_error_policy_methods!(
+        "files that cannot be read when checking front matter"
+    );
+}
+
+/// A filter for files a code search tool should skip, see [`CodeSearchFilter`].
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{presets, IgnorePath};
+///
+/// let filter = presets::code_search().with_max_size(2 * 1024 * 1024);
+/// assert!(filter.ignore(".git/config"));
+/// assert!(filter.ignore("dist/app.min.js"));
+/// assert!(!filter.ignore("src/lib.rs"));
+/// ```
+#[cfg(feature = "io")]
+pub const fn code_search() -> CodeSearchFilter {
+    CodeSearchFilter::new()
+}
+
+/// A filter for files that are not worth searching as source code.
+///
+/// Ignores, in this order:
+/// - anything inside a `.git`, `.hg`, `.svn` or `.bzr` directory,
+/// - [`BUILD_ARTIFACTS`], [`ARCHIVES`] and [`IMAGES`],
+/// - minified files named `*.min.js` or `*.min.css`,
+/// - files larger than [`max_size`](Self::with_max_size), 1 MiB by default,
+/// - files whose first 8 KiB contain a NUL byte or a line longer than 1000 bytes.
+///
+/// Directories are only ignored by name.
+#[cfg(feature = "io")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CodeSearchFilter {
+    max_size: u64,
+    on_error: IoErrorPolicy,
+}
+
+#[cfg(feature = "io")]
+impl Default for CodeSearchFilter {
+    fn default() -> Self {
+        CodeSearchFilter::new()
+    }
+}
+
+#[cfg(feature = "io")]
+impl IgnorePath for CodeSearchFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        let by_name = path
+            .iter()
+            .any(|component| matches!(component.to_str(), Some(".git" | ".hg" | ".svn" | ".bzr")))
+            || [BUILD_ARTIFACTS, ARCHIVES, IMAGES].ignore(path)
+            || path.file_name().is_some_and(|name| {
+                let name = name.to_string_lossy();
+                name.ends_with(".min.js") || name.ends_with(".min.css")
+            });
+
+        by_name || self.on_error.resolve(self.check_contents(path))
+    }
+}
+
+#[cfg(feature = "io")]
+impl CodeSearchFilter {
+    /// Files are sniffed up to this many bytes.
+    const SNIFF_LEN: usize = 8 * 1024;
+    /// Lines longer than this are considered minified.
+    const MAX_LINE_LEN: usize = 1000;
+
+    /// Creates a new filter with a size cap of 1 MiB that keeps files it cannot read.
+    pub const fn new() -> Self {
+        CodeSearchFilter {
+            max_size: 1024 * 1024,
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    /// Sets the size in bytes above which files are ignored.
+    pub const fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Returns the size in bytes above which files are ignored.
+    pub const fn max_size(&self) -> u64 {
+        self.max_size
+    }
+
+    crate::content::io_error_policy_methods!(const "files that cannot be read");
+
+    fn check_contents(&self, path: &Path) -> std::io::Result<bool> {
+        let metadata = std::fs::metadata(path)?;
+        if !metadata.is_file() {
+            return Ok(false);
+        }
+        if metadata.len() > self.max_size {
+            return Ok(true);
+        }
+
+        let prefix = crate::content::read_prefix(path, Self::SNIFF_LEN)?;
+        Ok(crate::content::looks_binary(&prefix)
+            || prefix
+                .split(|&byte| byte == b'\n')
+                .any(|line| line.len() > Self::MAX_LINE_LEN))
+    }
+}
+
+#[cfg(feature = "io")]
+mod front_matter {
+    use crate::content::read_prefix;
+    use std::{fs, io, path::Path};
+
+    /// Front matter longer than this is only checked up to this length.
+    const MAX_LEN: usize = 4096;
+
+    pub(super) fn is_draft(path: &Path) -> io::Result<bool> {
+        if !fs::metadata(path)?.is_file() {
+            return Ok(false);
+        }
+        let prefix = read_prefix(path, MAX_LEN)?;
+        Ok(has_draft_flag(&String::from_utf8_lossy(&prefix)))
+    }
+
+    pub(super) fn has_draft_flag(text: &str) -> bool {
+        let mut lines = text.lines();
+        let (delimiter, separator) = match lines.next().map(str::trim_end) {
+            Some("---") => ("---", ':'),
+            Some("+++") => ("+++", '='),
+            _ => return false,
+        };
+
+        lines
+            .take_while(|line| line.trim_end() != delimiter)
+            .filter_map(|line| line.split_once(separator))
+            .any(|(key, value)| key.trim() == "draft" && value.trim() == "true")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{presets, IgnorePath};
+    use std::path::Path;
+
+    #[test]
+    fn presets() {
+        assert!(presets::BUILD_ARTIFACTS.ignore(Path::new("target/debug/main.o")));
+        assert!(presets::ARCHIVES.ignore(Path::new("release.zip")));
+        assert!(presets::IMAGES.ignore(Path::new("docs/logo.svg")));
+        assert!(!presets::IMAGES.ignore(Path::new("docs/index.md")));
+    }
+
+    #[test]
+    fn static_site() {
+        let filter = presets::STATIC_SITE;
+        assert!(filter.ignore(Path::new("_site/index.html")));
+        assert!(filter.ignore(Path::new("content/_drafts/post.md")));
+        assert!(filter.ignore(Path::new("content/post.draft.md")));
+        assert!(!filter.ignore(Path::new("content/post.md")));
+        assert!(!filter.ignore(Path::new("content/draft.md")));
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn static_site_front_matter() {
+        use crate::{content::tests::TempDir, presets::front_matter::has_draft_flag};
+
+        assert!(has_draft_flag("---\ntitle: Hi\ndraft: true\n---\n"));
+        assert!(has_draft_flag("+++\r\ndraft = true\r\n+++\r\n"));
+        assert!(!has_draft_flag("---\ndraft: false\n---\n"));
+        assert!(!has_draft_flag("---\ntitle: Hi\n---\ndraft: true\n"));
+        assert!(!has_draft_flag("draft: true\n"));
+
+        let dir = TempDir::new();
+        let draft = dir.write("draft.md", "---\ndraft: true\n---\n# Draft\n");
+        let post = dir.write("post.md", "---\ntitle: Post\n---\n");
+
+        assert!(!presets::STATIC_SITE.ignore(&draft));
+        let filter = presets::STATIC_SITE.with_front_matter(true);
+        assert!(filter.ignore(&draft));
+        assert!(!filter.ignore(&post));
+        assert!(!filter.ignore(dir.path()));
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn code_search() {
+        use crate::content::tests::TempDir;
+
+        let dir = TempDir::new();
+        let source = dir.write("lib.rs", "fn main() {}\n");
+        let binary = dir.write("data", b"\x7fELF\0\0");
+        let minified = dir.write("bundle.js", "x".repeat(2000));
+        let large = dir.write("large.txt", "a\n".repeat(1024));
+
+        let filter = presets::code_search();
+        assert!(filter.ignore(Path::new("repo/.git/HEAD")));
+        assert!(filter.ignore(Path::new("target/main.o")));
+        assert!(filter.ignore(Path::new("dist/app.min.css")));
+        assert!(!filter.ignore(&source));
+        assert!(filter.ignore(binary));
+        assert!(filter.ignore(minified));
+        assert!(!filter.ignore(&large));
+        assert!(filter.with_max_size(1024).ignore(&large));
+        assert!(!filter.ignore(dir.path()));
+        assert!(!filter.ignore(dir.path().join("missing.rs")));
+    }
+}
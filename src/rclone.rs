@@ -0,0 +1,164 @@
+//! Loading rclone filter files.
+//!
+//! A filter file, as passed to `--filter-from`, has a rule on every line:
+//! - surrounding whitespace is removed, and blank lines and lines starting with
+//!   `#` or `;` are skipped,
+//! - `+ pattern` includes and `- pattern` excludes the paths the pattern matches,
+//! - a line with just `!` clears all rules before it,
+//! - the first matching rule decides.
+//!
+//! Patterns are globs, matched against paths relative to the root of the remote:
+//! - `*` and `?` do not match `/`, `**` matches across directories, `{a,b}` matches
+//!   either alternative and `{{regex}}` embeds a regular expression,
+//! - a pattern starting with `/` matches from the root, others match the end of
+//!   the path, so `dir/file.txt` matches `a/dir/file.txt`,
+//! - a pattern ending with `/` is a directory rule that matches the directory,
+//!   written with a trailing `/`, and everything inside of it; other patterns
+//!   only match the path itself, so use `dir/**` to match the contents of `dir`.
+//!
+//! The rules are loaded into a [`FilterSet`], whose last matching rule decides,
+//! so its rules are in the reverse order of the lines. For `--ignore-case`, set
+//! the options of the set with [`FilterSet::with_options`]. Size and age limits
+//! like `--max-size` are flags rather than rules; combine the set with a
+//! `SizeFilter` or `AgeFilter` for those.
+//!
+//! # Examples
+//! ```
+//! use pathfilter::{rclone, IgnorePath};
+//! use std::path::Path;
+//!
+//! let set = rclone::parse("# photos\n+ *.{jpg,png}\n- /cache/\n- *\n").unwrap();
+//! assert!(!set.ignore(Path::new("2024/beach.jpg")));
+//! assert!(set.ignore(Path::new("notes.txt")));
+//! assert!(set.ignore(Path::new("cache/")));
+//! ```
+
+use crate::{
+    glob::{translate, Dialect},
+    patterns::{PatternError, PatternErrorKind},
+    FilterRule, FilterSet, GlobError, PathFilter, RegexFilter,
+};
+
+/// Parses the contents of a filter file.
+///
+/// # Errors
+/// If a line is not a rule or its pattern is invalid, an error with its location
+/// is returned.
+pub fn parse(text: &str) -> Result<FilterSet, PatternError> {
+    let mut rules = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let rule = line.trim();
+        if rule.is_empty() || rule.starts_with(['#', ';']) {
+            continue;
+        }
+        if rule == "!" {
+            rules.clear();
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        let error = |offset: usize, kind| PatternError {
+            file: None,
+            line: index + 1,
+            column: line[..indent + offset].chars().count() + 1,
+            text: rule.to_string(),
+            kind,
+        };
+        let (include, pattern) = match rule.split_at_checked(2) {
+            Some(("+ ", pattern)) => (true, pattern),
+            Some(("- ", pattern)) => (false, pattern),
+            _ if rule == "+" || rule == "-" => {
+                return Err(error(1, PatternErrorKind::MissingValue))
+            }
+            _ => return Err(error(0, PatternErrorKind::MissingKind)),
+        };
+        let offset = 2 + pattern.len() - pattern.trim_start().len();
+        let pattern = pattern.trim_start();
+        if pattern.is_empty() {
+            return Err(error(offset, PatternErrorKind::MissingValue));
+        }
+
+        let filter = pattern_filter(pattern).map_err(|kind| error(offset, kind))?;
+        rules.push(if include {
+            FilterRule::Allow(filter)
+        } else {
+            FilterRule::Ignore(filter)
+        });
+    }
+    Ok(rules.into_iter().rev().collect())
+}
+
+fn pattern_filter(pattern: &str) -> Result<PathFilter, PatternErrorKind> {
+    let (anchor, glob) = match pattern.strip_prefix('/') {
+        Some(glob) => ("", glob),
+        None => ("(?:.*/)?", pattern),
+    };
+    let (glob, end) = match glob.strip_suffix('/') {
+        Some(dir) => (dir, "/.*$"),
+        None => (glob, "$"),
+    };
+
+    let dialect = Dialect {
+        braces: true,
+        regex_blocks: true,
+        ..Dialect::default()
+    };
+    let body = translate(glob, dialect).map_err(|reason| {
+        PatternErrorKind::Glob(GlobError {
+            glob: pattern.to_string(),
+            reason,
+        })
+    })?;
+    RegexFilter::new_str(&format!("^{anchor}{body}{end}"))
+        .map(PathFilter::from)
+        .map_err(PatternErrorKind::Regex)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{patterns::PatternErrorKind, rclone::parse, IgnorePath};
+    use std::path::Path;
+
+    #[test]
+    fn rules() {
+        let set =
+            parse("- secret/*.txt\n+ *.txt\n  - /logs/  \n- file{{\\d+}}.bin\n- *.tmp\n- **\n")
+                .unwrap();
+        assert_eq!(set.len(), 6);
+        assert!(set.ignore(Path::new("a/secret/x.txt")));
+        assert!(!set.ignore(Path::new("a/secret/b/x.txt")));
+        assert!(!set.ignore(Path::new("notes.txt")));
+        assert!(set.ignore(Path::new("logs/")));
+        assert!(set.ignore(Path::new("logs/today.log")));
+        assert!(set.ignore(Path::new("data/file42.bin")));
+        assert!(set.ignore(Path::new("anything/else")));
+
+        let set = parse("- *.tmp\n").unwrap();
+        assert!(set.ignore(Path::new("a/b.tmp")));
+        assert!(!set.ignore(Path::new("b.tmp/c")));
+        assert!(!set.ignore(Path::new("a.txt")));
+    }
+
+    #[test]
+    fn clear() {
+        let set = parse("- *.jpg\n; comment\n!\n- *.png\n").unwrap();
+        assert_eq!(set.len(), 1);
+        assert!(!set.ignore(Path::new("a.jpg")));
+        assert!(set.ignore(Path::new("a.png")));
+    }
+
+    #[test]
+    fn errors() {
+        let err = parse("+ ok\n*.jpg\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::MissingKind));
+        assert_eq!((err.line, err.column), (2, 1));
+
+        let err = parse("  - {a,b\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::Glob(_)));
+        assert_eq!(err.column, 5);
+        let err = parse("- {{(}}\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::Regex(_)));
+        let err = parse("-\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::MissingValue));
+    }
+}
@@ -1,19 +1,80 @@
-use crate::IgnorePath;
+use crate::{
+    options::options_methods, ExtensionFilter, ExtensionsFilter, IgnorePath, MatchCandidate,
+    MatchOptions, MatchOverrides, MatchTarget, PathFilter, PrefixFilter,
+};
 use std::{path::Path, str::FromStr};
 
 /// A filter that matches files based on a regex
+///
+/// On Windows, or with [`MatchOptions::normalize_separators`], extended-length
+/// prefixes (`\\?\`) are stripped from paths before matching, so `\\?\C:\dir` is
+/// matched as `C:\dir`.
+///
+/// With the `serde` feature the filter is serialized as its pattern together with
+/// its options, overrides and [`MatchTarget`], so all of them are restored on
+/// deserialization. Settings made through `regex::RegexBuilder` are not kept.
 #[derive(Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "RegexFilterDef")
+)]
 pub struct RegexFilter {
     #[cfg_attr(feature = "serde", serde(with = "serde_regex"))]
     regex: regex::Regex,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "MatchOptions::is_default")
+    )]
+    options: MatchOptions,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "MatchOverrides::is_empty")
+    )]
+    overrides: MatchOverrides,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "MatchTarget::is_path"))]
+    target: MatchTarget,
+}
+
+/// The serialized form of a [`RegexFilter`], compiled with its options on deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RegexFilterDef {
+    #[serde(with = "serde_regex")]
+    regex: regex::Regex,
+    #[serde(default)]
+    options: MatchOptions,
+    #[serde(default)]
+    overrides: MatchOverrides,
+    #[serde(default)]
+    target: MatchTarget,
+}
+
+#[cfg(feature = "serde")]
+impl From<RegexFilterDef> for RegexFilter {
+    fn from(value: RegexFilterDef) -> Self {
+        RegexFilter::new(value.regex)
+            .with_options(value.options)
+            .with_overrides(value.overrides)
+            .with_target(value.target)
+    }
 }
 
 impl IgnorePath for RegexFilter {
     fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
-        match path.as_ref().to_str() {
-            Some(s) => self.regex.is_match(s),
-            None => false,
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        match self.target {
+            MatchTarget::Path => candidate
+                .text(&self.options())
+                .is_some_and(|text| self.regex.is_match(text)),
+            MatchTarget::FileName => candidate
+                .path()
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| self.regex.is_match(name)),
         }
     }
 }
@@ -45,7 +106,7 @@ impl RegexFilter {
     /// If the regex is invalid, an error is returned.
     pub fn new_str(pattern: &str) -> Result<Self, regex::Error> {
         let regex = regex::Regex::new(pattern)?;
-        Ok(RegexFilter { regex })
+        Ok(RegexFilter::new(regex))
     }
 
     /// Creates a new regex filter for a regex.
@@ -63,8 +124,194 @@ impl RegexFilter {
     ///
     /// ```
     pub fn new(regex: regex::Regex) -> Self {
-        RegexFilter { regex }
+        RegexFilter {
+            regex,
+            options: MatchOptions::new(),
+            overrides: MatchOverrides::new(),
+            target: MatchTarget::Path,
+        }
+    }
+
+    options_methods! {
+        ///
+        /// The regex is recompiled from its pattern, so settings made through
+        /// `regex::RegexBuilder` are replaced by the defaults and these options.
+        ///
+        /// # Examples
+        /// ```
+        /// use pathfilter::{IgnorePath, MatchOptions, RegexFilter};
+        /// use std::path::Path;
+        ///
+        /// let options = MatchOptions::new().case_insensitive(true).normalize_separators(true);
+        /// let filter = RegexFilter::new_str("^src/.*\\.rs$").unwrap().with_options(options);
+        /// assert!(filter.ignore(Path::new("SRC\\lib.rs")));
+        /// ```
+        on_change = recompile,
+        overrides:
+        ///
+        /// # Examples
+        /// ```
+        /// use pathfilter::{IgnorePath, MatchOptions, MatchOverrides, RegexFilter};
+        /// use std::path::Path;
+        ///
+        /// let filter = RegexFilter::new_str("^src/").unwrap()
+        ///     .with_overrides(MatchOverrides::new().case_insensitive(true))
+        ///     .with_options(MatchOptions::new());
+        /// assert!(filter.ignore(Path::new("SRC/lib.rs")));
+        /// ```
+    }
+
+    /// Sets the part of a path the regex is matched against.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{IgnorePath, MatchTarget, RegexFilter};
+    /// use std::path::Path;
+    ///
+    /// let filter = RegexFilter::new_str("^test_").unwrap().with_target(MatchTarget::FileName);
+    /// assert!(filter.ignore(Path::new("src/test_parse.rs")));
+    /// assert!(!filter.ignore(Path::new("test_data/input.txt")));
+    /// ```
+    pub fn with_target(mut self, target: MatchTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Returns the part of a path the regex is matched against.
+    pub fn target(&self) -> MatchTarget {
+        self.target
+    }
+
+    /// Returns the pattern the regex was compiled from.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::RegexFilter;
+    ///
+    /// let filter = RegexFilter::new_str("^src/").unwrap();
+    /// assert_eq!(filter.as_str(), "^src/");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    fn recompile(&mut self) {
+        // The pattern already compiled once, so only the size limit could make it fail
+        // with different flags; lifting the limit makes recompiling infallible.
+        self.regex = regex::RegexBuilder::new(self.regex.as_str())
+            .case_insensitive(self.options().is_case_insensitive())
+            .size_limit(usize::MAX)
+            .build()
+            .expect("regex compiled before");
+    }
+
+    /// Returns a cheaper filter that is equivalent to this regex, if there is one.
+    ///
+    /// Currently recognized are regexes that only check for one or more extensions,
+    /// like `^.*\.rs$`, `\.rs$` or `\.(rs|txt)$`, which become extension filters,
+    /// and regexes for a literal leading directory, like `^vendor/`, which become a
+    /// [`PrefixFilter`].
+    ///
+    /// The equivalence holds for ordinary file paths; it differs for dotfiles that
+    /// consist only of the extension (e.g. `.rs`), paths containing line breaks and
+    /// paths that are not valid UTF-8. A prefix filter also matches the directory
+    /// itself and paths starting with `./`. Options set through `regex::RegexBuilder`
+    /// are not part of the pattern and are assumed to be the defaults.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{PathFilter, RegexFilter};
+    ///
+    /// let filter = RegexFilter::new_str("^.*\\.rs$").unwrap();
+    /// assert!(matches!(filter.simplify(), Some(PathFilter::Extension(_))));
+    ///
+    /// let filter = RegexFilter::new_str("^vendor/").unwrap();
+    /// assert!(matches!(filter.simplify(), Some(PathFilter::Prefix(_))));
+    ///
+    /// let filter = RegexFilter::new_str("^src/.*\\.rs$").unwrap();
+    /// assert!(filter.simplify().is_none());
+    /// ```
+    pub fn simplify(&self) -> Option<PathFilter> {
+        self.simplify_extensions()
+            .or_else(|| self.simplify_prefix())
+    }
+
+    fn simplify_extensions(&self) -> Option<PathFilter> {
+        let pattern = self.regex.as_str();
+        let any = ["(?:.*)", "(.*)", ".*"];
+        // An anchored regex only checks a suffix if it starts by skipping anything.
+        let pattern = match pattern.strip_prefix('^') {
+            Some(anchored) => any.iter().find_map(|any| anchored.strip_prefix(any))?,
+            None => any
+                .iter()
+                .find_map(|any| pattern.strip_prefix(any))
+                .unwrap_or(pattern),
+        };
+        let extensions = pattern.strip_prefix("\\.")?.strip_suffix('$')?;
+        let extensions: Vec<&str> = match extensions
+            .strip_prefix("(?:")
+            .or_else(|| extensions.strip_prefix('('))
+        {
+            Some(group) => group.strip_suffix(')')?.split('|').collect(),
+            None => vec![extensions],
+        };
+        if !extensions.iter().all(|ext| is_plain_extension(ext)) {
+            return None;
+        }
+
+        Some(match extensions.as_slice() {
+            [extension] => ExtensionFilter::new(extension)
+                .with_options(self.options)
+                .with_overrides(self.overrides)
+                .into(),
+            extensions => ExtensionsFilter::new(extensions)
+                .with_options(self.options)
+                .with_overrides(self.overrides)
+                .into(),
+        })
+    }
+
+    fn simplify_prefix(&self) -> Option<PathFilter> {
+        if self.target != MatchTarget::Path {
+            return None;
+        }
+        let pattern = self.regex.as_str().strip_prefix('^')?.strip_suffix('/')?;
+        let prefix = unescape_literal(pattern)?;
+        if prefix
+            .split('/')
+            .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+        {
+            return None;
+        }
+
+        Some(
+            PrefixFilter::new([prefix])
+                .with_options(self.options)
+                .with_overrides(self.overrides)
+                .into(),
+        )
+    }
+}
+
+fn is_plain_extension(extension: &str) -> bool {
+    !extension.is_empty()
+        && extension
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Returns the text a regex of plain characters, `/` and escaped dots matches.
+fn unescape_literal(pattern: &str) -> Option<String> {
+    let mut literal = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => literal.push(chars.next().filter(|&c| c == '.')?),
+            c if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '/') => literal.push(c),
+            _ => return None,
+        }
     }
+    Some(literal)
 }
 
 #[cfg(test)]
@@ -90,4 +337,185 @@ mod tests {
         assert!(filter.ignore(Path::new("src/lib.rs")));
         assert!(!filter.ignore(Path::new("src/Program.cs")));
     }
+
+    #[test]
+    fn regex_filter_verbatim_paths() {
+        use crate::{regex::RegexFilter, IgnorePath, MatchOptions};
+
+        let options = MatchOptions::new().normalize_separators(true);
+        let filter = RegexFilter::new_str("^C:/Temp/")
+            .unwrap()
+            .with_options(options);
+        assert!(filter.ignore(Path::new(r"C:\Temp\a.txt")));
+        assert!(filter.ignore(Path::new(r"\\?\C:\Temp\a.txt")));
+
+        let filter = RegexFilter::new_str("^//server/share/")
+            .unwrap()
+            .with_options(options);
+        assert!(filter.ignore(Path::new(r"\\?\UNC\server\share\a.txt")));
+
+        #[cfg(not(windows))]
+        assert!(!RegexFilter::new_str(r"^C:\\")
+            .unwrap()
+            .ignore(Path::new(r"\\?\C:\Temp\a.txt")));
+    }
+
+    #[test]
+    fn regex_filter_options() {
+        use crate::{regex::RegexFilter, IgnorePath, MatchOptions};
+
+        let filter = RegexFilter::new_str("^src/lib.rs$").unwrap();
+        assert!(!filter.ignore(Path::new("SRC/lib.rs")));
+        assert!(!filter.ignore(Path::new("src\\lib.rs")));
+
+        let filter = filter.with_options(MatchOptions::new().case_insensitive(true));
+        assert!(filter.ignore(Path::new("SRC/lib.rs")));
+        assert!(!filter.ignore(Path::new("src\\lib.rs")));
+
+        let filter = filter.with_options(MatchOptions::new().normalize_separators(true));
+        assert!(!filter.ignore(Path::new("SRC/lib.rs")));
+        assert!(filter.ignore(Path::new("src\\lib.rs")));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_options() {
+        use crate::{regex::RegexFilter, value::Value, IgnorePath};
+        use serde::{
+            de::{value::Error, IntoDeserializer},
+            Deserialize,
+        };
+
+        let str = |s: &str| Value::Str(s.to_string());
+        let value = Value::Map(vec![
+            (str("regex"), str("^src/lib.rs$")),
+            (
+                str("options"),
+                Value::Map(vec![(str("case_insensitive"), Value::Bool(true))]),
+            ),
+        ]);
+        let filter =
+            RegexFilter::deserialize(IntoDeserializer::<Error>::into_deserializer(value)).unwrap();
+        assert!(filter.options().is_case_insensitive());
+        assert!(filter.ignore(Path::new("SRC/LIB.rs")));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_keeps_configuration() {
+        use crate::{
+            regex::RegexFilter,
+            value::{to_value, Value},
+            IgnorePath, MatchOptions, MatchOverrides, MatchTarget,
+        };
+        use serde::{
+            de::{value::Error, IntoDeserializer},
+            Deserialize,
+        };
+
+        let filter = RegexFilter::new_str("^lib\\.")
+            .unwrap()
+            .with_options(MatchOptions::new().case_insensitive(true))
+            .with_overrides(MatchOverrides::new().normalize_separators(true))
+            .with_target(MatchTarget::FileName);
+        let value = to_value(&filter).unwrap();
+        assert!(matches!(&value, Value::Map(entries) if entries.len() == 4));
+
+        let filter =
+            RegexFilter::deserialize(IntoDeserializer::<Error>::into_deserializer(value)).unwrap();
+        assert_eq!(filter.as_str(), "^lib\\.");
+        assert!(filter.options().is_case_insensitive());
+        assert!(filter.options().normalizes_separators());
+        assert_eq!(filter.target(), MatchTarget::FileName);
+        assert!(filter.ignore(Path::new("src/LIB.rs")));
+    }
+
+    #[test]
+    fn file_name_target() {
+        use crate::{regex::RegexFilter, IgnorePath, MatchTarget};
+
+        let filter = RegexFilter::new_str("^[^/]+\\.rs$").unwrap();
+        assert!(!filter.ignore(Path::new("src/lib.rs")));
+        let filter = filter.with_target(MatchTarget::FileName);
+        assert!(filter.ignore(Path::new("src/lib.rs")));
+        assert!(!filter.ignore(Path::new("src/lib.rs/..")));
+    }
+
+    #[test]
+    fn simplify_keeps_options() {
+        use crate::{regex::RegexFilter, IgnorePath, MatchOptions};
+
+        let options = MatchOptions::new().case_insensitive(true);
+        let filter = RegexFilter::new_str("\\.rs$")
+            .unwrap()
+            .with_options(options);
+        let simplified = filter.simplify().unwrap();
+        assert!(simplified.ignore(Path::new("src/lib.RS")));
+    }
+
+    #[test]
+    fn simplify_extension() {
+        use crate::{regex::RegexFilter, IgnorePath, PathFilter};
+
+        for pattern in ["^.*\\.rs$", ".*\\.rs$", "\\.rs$", "^(.*)\\.rs$", "\\.(rs)$"] {
+            let filter = RegexFilter::new_str(pattern).unwrap();
+            let simplified = filter.simplify().unwrap();
+            assert!(matches!(simplified, PathFilter::Extension(_)), "{pattern}");
+            assert!(simplified.ignore(Path::new("src/lib.rs")));
+            assert!(!simplified.ignore(Path::new("src/lib.rs.bak")));
+        }
+    }
+
+    #[test]
+    fn simplify_extensions() {
+        use crate::{regex::RegexFilter, IgnorePath, PathFilter};
+
+        let filter = RegexFilter::new_str("^.*\\.(?:rs|txt)$").unwrap();
+        let simplified = filter.simplify().unwrap();
+        assert!(matches!(simplified, PathFilter::Extensions(_)));
+        assert!(simplified.ignore(Path::new("src/lib.rs")));
+        assert!(simplified.ignore(Path::new("notes.txt")));
+        assert!(!simplified.ignore(Path::new("image.png")));
+    }
+
+    #[test]
+    fn simplify_prefix() {
+        use crate::{regex::RegexFilter, IgnorePath, MatchTarget, PathFilter};
+
+        let filter = RegexFilter::new_str("^third_party/zlib\\.d/").unwrap();
+        let simplified = filter.simplify().unwrap();
+        let PathFilter::Prefix(prefix) = &simplified else {
+            panic!("expected a prefix filter, got {simplified:?}");
+        };
+        assert_eq!(prefix.prefixes(), [Path::new("third_party/zlib.d")]);
+        assert!(simplified.ignore(Path::new("third_party/zlib.d/zlib.h")));
+        assert!(!simplified.ignore(Path::new("src/third_party/zlib.d/zlib.h")));
+
+        let filter = filter.with_target(MatchTarget::FileName);
+        assert!(filter.simplify().is_none());
+    }
+
+    #[test]
+    fn simplify_keeps_regex() {
+        use crate::regex::RegexFilter;
+
+        for pattern in [
+            "^src/.*\\.rs$",
+            "\\.rs",
+            ".rs$",
+            "\\.tar\\.gz$",
+            "\\.r.$",
+            "\\.(rs|)$",
+            "(?i)\\.rs$",
+            "^\\.rs$",
+            "\\.rs|txt$",
+            "^src/../",
+            "^src//",
+            "^src/[a-z]+/",
+            "^vendor",
+        ] {
+            let filter = RegexFilter::new_str(pattern).unwrap();
+            assert!(filter.simplify().is_none(), "{pattern}");
+        }
+    }
 }
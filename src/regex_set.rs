@@ -0,0 +1,226 @@
+use crate::{options::options_methods, IgnorePath, MatchCandidate, MatchOptions, MatchOverrides};
+use regex::{RegexSet, RegexSetBuilder};
+use std::path::Path;
+
+/// A filter that matches paths against a set of regexes at once.
+///
+/// The whole set is matched in a single pass, which is cheaper than a list of
+/// [`RegexFilter`](crate::RegexFilter)s for many patterns, and an existing
+/// [`RegexSet`] can be used as it is. Paths are prepared the same way as for a
+/// `RegexFilter`.
+///
+/// With the `serde` feature the filter is serialized as its list of `patterns`,
+/// its `options` and its `overrides`.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, RegexSetFilter};
+/// use regex::RegexSet;
+/// use std::path::Path;
+///
+/// let set = RegexSet::new(["^target/", "\\.log$"]).unwrap();
+/// let filter = RegexSetFilter::new(set);
+/// assert!(filter.ignore(Path::new("target/debug")));
+/// assert!(filter.ignore(Path::new("build.log")));
+/// assert!(!filter.ignore(Path::new("src/lib.rs")));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RegexSetFilter {
+    set: RegexSet,
+    options: MatchOptions,
+    overrides: MatchOverrides,
+}
+
+impl IgnorePath for RegexSetFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        candidate
+            .text(&self.options())
+            .is_some_and(|text| self.set.is_match(text))
+    }
+}
+
+impl From<RegexSet> for RegexSetFilter {
+    fn from(value: RegexSet) -> Self {
+        RegexSetFilter::new(value)
+    }
+}
+
+impl RegexSetFilter {
+    /// Creates a new filter for a regex set.
+    pub fn new(set: RegexSet) -> Self {
+        RegexSetFilter {
+            set,
+            options: MatchOptions::new(),
+            overrides: MatchOverrides::new(),
+        }
+    }
+
+    /// Creates a new filter for a list of patterns.
+    ///
+    /// # Errors
+    /// If a pattern is invalid, an error is returned.
+    pub fn from_patterns<I, S>(patterns: I) -> Result<Self, regex::Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Ok(RegexSetFilter::new(RegexSet::new(patterns)?))
+    }
+
+    options_methods! {
+        ///
+        /// Like [`RegexFilter::with_options`](crate::RegexFilter::with_options), the set
+        /// is recompiled from its patterns.
+        on_change = recompile
+    }
+
+    /// Returns the patterns of the set, in the order they were given.
+    pub fn patterns(&self) -> &[String] {
+        self.set.patterns()
+    }
+
+    fn recompile(&mut self) {
+        // See `RegexFilter::recompile`.
+        self.set = RegexSetBuilder::new(self.set.patterns())
+            .case_insensitive(self.options().is_case_insensitive())
+            .size_limit(usize::MAX)
+            .build()
+            .expect("regex set compiled before");
+    }
+
+    /// Returns the regex set.
+    pub fn set(&self) -> &RegexSet {
+        &self.set
+    }
+
+    /// Returns the indices of the patterns that match the path, in ascending order.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::RegexSetFilter;
+    ///
+    /// let filter = RegexSetFilter::from_patterns(["^src/", "\\.rs$", "\\.md$"]).unwrap();
+    /// assert_eq!(filter.matches("src/lib.rs"), [0, 1]);
+    /// ```
+    pub fn matches<P: AsRef<Path>>(&self, path: P) -> Vec<usize> {
+        MatchCandidate::new(path.as_ref())
+            .text(&self.options())
+            .map_or_else(Vec::new, |text| {
+                self.set.matches(text).into_iter().collect()
+            })
+    }
+}
+
+#[cfg(feature = "serde")]
+mod regex_set_serde {
+    use super::RegexSetFilter;
+    use crate::{MatchOptions, MatchOverrides};
+    use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Deserialize)]
+    struct RegexSetFilterDef {
+        patterns: Vec<String>,
+        #[serde(default)]
+        options: MatchOptions,
+        #[serde(default)]
+        overrides: MatchOverrides,
+    }
+
+    impl Serialize for RegexSetFilter {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let skip_options = self.options.is_default();
+            let skip_overrides = self.overrides.is_empty();
+            let len = 1 + usize::from(!skip_options) + usize::from(!skip_overrides);
+            let mut state = serializer.serialize_struct("RegexSetFilter", len)?;
+            state.serialize_field("patterns", self.patterns())?;
+            if skip_options {
+                state.skip_field("options")?;
+            } else {
+                state.serialize_field("options", &self.options)?;
+            }
+            if skip_overrides {
+                state.skip_field("overrides")?;
+            } else {
+                state.serialize_field("overrides", &self.overrides)?;
+            }
+            state.end()
+        }
+    }
+
+    impl<'de> Deserialize<'de> for RegexSetFilter {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let def = RegexSetFilterDef::deserialize(deserializer)?;
+            Ok(RegexSetFilter::from_patterns(def.patterns)
+                .map_err(de::Error::custom)?
+                .with_options(def.options)
+                .with_overrides(def.overrides))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IgnorePath, MatchOptions, MatchOverrides, RegexSetFilter};
+    use std::path::Path;
+
+    #[test]
+    fn regex_set() {
+        let filter = RegexSetFilter::from_patterns(["^src/", "\\.md$"]).unwrap();
+        assert!(filter.ignore(Path::new("src/lib.rs")));
+        assert!(filter.ignore(Path::new("README.md")));
+        assert!(!filter.ignore(Path::new("SRC/lib.rs")));
+        assert!(!filter.ignore(Path::new("Cargo.toml")));
+
+        let filter = filter.with_options(
+            MatchOptions::new()
+                .case_insensitive(true)
+                .normalize_separators(true),
+        );
+        assert!(filter.ignore(Path::new("SRC\\lib.rs")));
+        assert_eq!(filter.patterns(), ["^src/", "\\.md$"]);
+        let filter = filter.with_overrides(MatchOverrides::new().case_insensitive(false));
+        assert!(!filter.ignore(Path::new("SRC/lib.rs")));
+        assert!(filter.ignore(Path::new("src\\lib.rs")));
+        assert_eq!(filter.matches("src/README.md"), [0, 1]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn regex_set_serde() {
+        use crate::value::{to_value, Value};
+        use serde::{
+            de::{value::Error, IntoDeserializer},
+            Deserialize,
+        };
+
+        let str = |s: &str| Value::Str(s.to_string());
+        let filter = RegexSetFilter::from_patterns(["^src/"]).unwrap();
+        let value = to_value(&filter).unwrap();
+        assert_eq!(
+            value,
+            Value::Map(vec![(str("patterns"), Value::Seq(vec![str("^src/")]))])
+        );
+
+        let value = Value::Map(vec![
+            (str("patterns"), Value::Seq(vec![str("^src/")])),
+            (
+                str("options"),
+                Value::Map(vec![(str("case_insensitive"), Value::Bool(true))]),
+            ),
+        ]);
+        let filter =
+            RegexSetFilter::deserialize(IntoDeserializer::<Error>::into_deserializer(value))
+                .unwrap();
+        assert!(filter.ignore(Path::new("SRC/lib.rs")));
+
+        let invalid = Value::Map(vec![(str("patterns"), Value::Seq(vec![str("(")]))]);
+        assert!(
+            RegexSetFilter::deserialize(IntoDeserializer::<Error>::into_deserializer(invalid))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! Loading restic exclude files.
+//!
+//! An exclude file, as passed to `--exclude-file`, has a glob on every line:
+//! - surrounding whitespace is removed, and blank lines and lines starting with
+//!   `#` are skipped,
+//! - `$NAME` and `${NAME}` are replaced with environment variables, unset ones
+//!   with nothing,
+//! - `*` and `?` do not match `/`, and `**` matches across directories,
+//! - a pattern starting with `/` matches from the root, others at any depth,
+//! - a pattern matching a directory also matches everything inside of it,
+//! - a leading `!` re-includes paths an earlier pattern excludes.
+//!
+//! As restic backs up absolute paths, anchored patterns are only matched against
+//! absolute paths. For `--iexclude-file`, set the options of the set to match
+//! case-insensitively with [`FilterSet::with_options`].
+//!
+//! # Examples
+//! ```
+//! use pathfilter::{restic, IgnorePath};
+//! use std::path::Path;
+//!
+//! let set = restic::parse("# caches\n/home/*/.cache\nnode_modules\n*.tmp\n!keep.tmp\n").unwrap();
+//! assert!(set.ignore(Path::new("/home/me/.cache/pip")));
+//! assert!(set.ignore(Path::new("/srv/web/node_modules/react/index.js")));
+//! assert!(set.ignore(Path::new("/tmp/a.tmp")));
+//! assert!(!set.ignore(Path::new("/tmp/keep.tmp")));
+//! assert!(!set.ignore(Path::new("/srv/home/me/.cache")));
+//! ```
+
+use crate::{
+    glob::{translate, Dialect},
+    patterns::{PatternError, PatternErrorKind},
+    FilterSet, GlobError, PathFilter, RegexFilter,
+};
+use std::env;
+
+/// Parses the contents of an exclude file.
+///
+/// # Errors
+/// If a pattern is invalid, an error with its location is returned.
+pub fn parse(text: &str) -> Result<FilterSet, PatternError> {
+    let mut set = FilterSet::new();
+    for (index, line) in text.lines().enumerate() {
+        let pattern = line.trim();
+        if pattern.is_empty() || pattern.starts_with('#') {
+            continue;
+        }
+        let error = |kind| PatternError {
+            file: None,
+            line: index + 1,
+            column: line[..line.len() - line.trim_start().len()].chars().count() + 1,
+            text: pattern.to_string(),
+            kind,
+        };
+
+        let pattern = expand_env(pattern);
+        let (negated, glob) = match pattern.strip_prefix('!') {
+            Some(glob) => (true, glob),
+            None => (false, pattern.as_str()),
+        };
+        if glob.is_empty() {
+            return Err(error(PatternErrorKind::MissingValue));
+        }
+        let (anchor, glob) = match glob.strip_prefix('/') {
+            Some(glob) => ("/", glob),
+            None => ("(?:.*/)?", glob),
+        };
+        let body = translate(glob.trim_end_matches('/'), Dialect::default()).map_err(|reason| {
+            let glob = GlobError {
+                glob: pattern.clone(),
+                reason,
+            };
+            error(PatternErrorKind::Glob(glob))
+        })?;
+        let filter: PathFilter = RegexFilter::new_str(&format!("^{anchor}{body}(?:/.*)?$"))
+            .map_err(|err| error(PatternErrorKind::Regex(err)))?
+            .into();
+
+        if negated {
+            set.add_allow(filter);
+        } else {
+            set.add_ignore(filter);
+        }
+    }
+    Ok(set)
+}
+
+/// Replaces `$NAME` and `${NAME}` with the value of the environment variable.
+fn expand_env(pattern: &str) -> String {
+    let mut expanded = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, len) = match after
+            .strip_prefix('{')
+            .and_then(|braced| braced.split_once('}'))
+        {
+            Some((name, _)) => (name, name.len() + 2),
+            None => {
+                let len = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..len], len)
+            }
+        };
+        if len == 0 {
+            expanded.push('$');
+        } else {
+            expanded.push_str(&env::var(name).unwrap_or_default());
+        }
+        rest = &after[len..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        patterns::PatternErrorKind,
+        restic::{expand_env, parse},
+        IgnorePath, MatchOptions,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn patterns() {
+        let set = parse("  *.log  \nfoo/bar\n/var/**/cache/\n!/var/lib/cache\n").unwrap();
+        assert_eq!(set.len(), 4);
+        assert!(set.ignore(Path::new("/srv/debug.log")));
+        assert!(set.ignore(Path::new("/srv/foo/bar/baz")));
+        assert!(!set.ignore(Path::new("/srv/foo/barbaz")));
+        assert!(set.ignore(Path::new("/var/cache/a")));
+        assert!(set.ignore(Path::new("/var/spool/x/cache")));
+        assert!(!set.ignore(Path::new("/var/lib/cache/b")));
+        assert!(!set.ignore(Path::new("var/cache")));
+        assert!(!set.ignore(Path::new("/srv/app.LOG")));
+
+        let set = set.with_options(MatchOptions::new().case_insensitive(true));
+        assert!(set.ignore(Path::new("/srv/app.LOG")));
+    }
+
+    #[test]
+    fn environment() {
+        std::env::set_var("PATHFILTER_TEST_RESTIC", "/home/me");
+        assert_eq!(
+            expand_env("$PATHFILTER_TEST_RESTIC/.cache"),
+            "/home/me/.cache"
+        );
+        assert_eq!(expand_env("${PATHFILTER_TEST_RESTIC}x/$"), "/home/mex/$");
+        let set = parse("${PATHFILTER_TEST_RESTIC}/.cache\n").unwrap();
+        std::env::remove_var("PATHFILTER_TEST_RESTIC");
+        assert!(set.ignore(Path::new("/home/me/.cache/pip")));
+        assert_eq!(expand_env("a$PATHFILTER_TEST_UNSET_VAR/b"), "a/b");
+    }
+
+    #[test]
+    fn errors() {
+        let err = parse("ok\n  [abc\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::Glob(_)));
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = parse("!\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::MissingValue));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::IgnorePath;
+use std::path::Path;
+
+/// Removes every path the filter ignores, in place.
+///
+/// The remaining paths keep their order and the vector is not reallocated.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{retain_not_ignored, ExtensionFilter};
+/// use std::path::PathBuf;
+///
+/// let mut paths: Vec<PathBuf> = vec!["src/lib.rs".into(), "README.md".into()];
+/// retain_not_ignored(&ExtensionFilter::new(".rs"), &mut paths);
+/// assert_eq!(paths, [PathBuf::from("README.md")]);
+/// ```
+pub fn retain_not_ignored<F, P>(filter: &F, paths: &mut Vec<P>)
+where
+    F: IgnorePath + ?Sized,
+    P: AsRef<Path>,
+{
+    paths.retain(|path| !filter.ignore(path));
+}
+
+/// Removes every path the filter does not ignore, in place.
+///
+/// The remaining paths keep their order and the vector is not reallocated.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{retain_ignored, ExtensionFilter};
+/// use std::path::PathBuf;
+///
+/// let mut paths: Vec<PathBuf> = vec!["src/lib.rs".into(), "README.md".into()];
+/// retain_ignored(&ExtensionFilter::new(".rs"), &mut paths);
+/// assert_eq!(paths, [PathBuf::from("src/lib.rs")]);
+/// ```
+pub fn retain_ignored<F, P>(filter: &F, paths: &mut Vec<P>)
+where
+    F: IgnorePath + ?Sized,
+    P: AsRef<Path>,
+{
+    paths.retain(|path| filter.ignore(path));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{retain_ignored, retain_not_ignored, PathFilter};
+    use std::path::PathBuf;
+
+    fn paths() -> Vec<PathBuf> {
+        ["src/lib.rs", "README.md", "src/main.rs", "Cargo.toml"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect()
+    }
+
+    #[test]
+    fn not_ignored() {
+        let filters = [PathFilter::new_extension(".rs")];
+        let mut paths = paths();
+        let capacity = paths.capacity();
+        retain_not_ignored(&filters[..], &mut paths);
+        assert_eq!(paths, [PathBuf::from("README.md"), "Cargo.toml".into()]);
+        assert_eq!(paths.capacity(), capacity);
+    }
+
+    #[test]
+    fn ignored() {
+        let mut paths = paths();
+        retain_ignored(&PathFilter::new_extension(".rs"), &mut paths);
+        assert_eq!(paths, [PathBuf::from("src/lib.rs"), "src/main.rs".into()]);
+
+        let mut names = vec!["a.rs", "b.md"];
+        retain_ignored(&PathFilter::new_extension(".md"), &mut names);
+        assert_eq!(names, ["b.md"]);
+    }
+}
@@ -0,0 +1,273 @@
+use crate::IgnorePath;
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    fmt,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+type GroupKey = dyn Fn(&Path) -> String;
+
+/// Rules for pruning old files, e.g. backups.
+///
+/// Files are grouped, by default by their parent directory. In every group the
+/// newest [`keep_latest`](Self::keep_latest) files and all files newer than
+/// [`keep_newer_than`](Self::keep_newer_than) are kept. Without any rule every
+/// file is kept.
+///
+/// Deciding requires all files of a group, so a [`Retention`] is not a filter
+/// itself. [`plan`](Self::plan) looks at all files and returns a [`RetentionPlan`]
+/// that ignores the files to prune.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, Retention};
+/// use std::time::{Duration, SystemTime};
+///
+/// let day = |n: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(n * 86400);
+/// let plan = Retention::new().keep_latest(2).plan([
+///     ("backups/db/1.sql", day(1)),
+///     ("backups/db/2.sql", day(2)),
+///     ("backups/db/3.sql", day(3)),
+///     ("backups/www/1.tar", day(1)),
+/// ]);
+/// assert!(plan.ignore("backups/db/1.sql"));
+/// assert!(!plan.ignore("backups/db/2.sql"));
+/// assert!(!plan.ignore("backups/www/1.tar"));
+/// ```
+#[derive(Default)]
+pub struct Retention {
+    keep_latest: Option<usize>,
+    keep_newer_than: Option<SystemTime>,
+    group_by: Option<Box<GroupKey>>,
+}
+
+impl fmt::Debug for Retention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Retention")
+            .field("keep_latest", &self.keep_latest)
+            .field("keep_newer_than", &self.keep_newer_than)
+            .field("group_by_key", &self.group_by.is_some())
+            .finish()
+    }
+}
+
+impl Retention {
+    /// Creates rules that keep every file.
+    pub fn new() -> Self {
+        Retention::default()
+    }
+
+    /// Keeps the newest `count` files of every group.
+    pub fn keep_latest(mut self, count: usize) -> Self {
+        self.keep_latest = Some(count);
+        self
+    }
+
+    /// Keeps all files modified after `cutoff`.
+    pub fn keep_newer_than(mut self, cutoff: SystemTime) -> Self {
+        self.keep_newer_than = Some(cutoff);
+        self
+    }
+
+    /// Groups files by their parent directory. This is the default.
+    pub fn group_by_directory(mut self) -> Self {
+        self.group_by = None;
+        self
+    }
+
+    /// Groups files by a key computed from their path, e.g. a name without its date.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{IgnorePath, Retention};
+    /// use std::path::Path;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let day = |n: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(n * 86400);
+    /// let name = |path: &Path| {
+    ///     let name = path.file_name().unwrap().to_string_lossy();
+    ///     name.split('-').next().unwrap().to_string()
+    /// };
+    /// let plan = Retention::new().keep_latest(1).group_by(name).plan([
+    ///     ("db-1.sql", day(1)),
+    ///     ("db-2.sql", day(2)),
+    ///     ("www-1.tar", day(1)),
+    /// ]);
+    /// assert!(plan.ignore("db-1.sql"));
+    /// assert!(!plan.ignore("www-1.tar"));
+    /// ```
+    pub fn group_by<K>(mut self, key: K) -> Self
+    where
+        K: Fn(&Path) -> String + 'static,
+    {
+        self.group_by = Some(Box::new(key));
+        self
+    }
+
+    /// Decides which of the files to keep, given their modification times.
+    pub fn plan<I, P>(&self, files: I) -> RetentionPlan
+    where
+        I: IntoIterator<Item = (P, SystemTime)>,
+        P: Into<PathBuf>,
+    {
+        let mut groups: HashMap<OsString, Vec<(PathBuf, SystemTime)>> = HashMap::new();
+        for (path, modified) in files {
+            let path = path.into();
+            let key = match &self.group_by {
+                Some(key) => key(&path).into(),
+                None => path.parent().unwrap_or(Path::new("")).into(),
+            };
+            groups.entry(key).or_default().push((path, modified));
+        }
+
+        let mut plan = RetentionPlan::default();
+        for mut files in groups.into_values() {
+            files.sort_by(|(a_path, a), (b_path, b)| b.cmp(a).then_with(|| a_path.cmp(b_path)));
+            for (index, (path, modified)) in files.into_iter().enumerate() {
+                if self.keeps(index, modified) {
+                    plan.kept.insert(path);
+                } else {
+                    plan.pruned.insert(path);
+                }
+            }
+        }
+        plan
+    }
+
+    /// Decides which of the files to keep, reading their modification times.
+    ///
+    /// Files whose modification time cannot be read are kept.
+    #[cfg(feature = "io")]
+    pub fn plan_files<I, P>(&self, files: I) -> RetentionPlan
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        let mut unreadable = Vec::new();
+        let mut files_with_times = Vec::new();
+        for path in files {
+            let path = path.into();
+            match std::fs::metadata(&path).and_then(|metadata| metadata.modified()) {
+                Ok(modified) => files_with_times.push((path, modified)),
+                Err(_) => unreadable.push(path),
+            }
+        }
+
+        let mut plan = self.plan(files_with_times);
+        plan.kept.extend(unreadable);
+        plan
+    }
+
+    fn keeps(&self, index: usize, modified: SystemTime) -> bool {
+        match (self.keep_latest, self.keep_newer_than) {
+            (None, None) => true,
+            (count, cutoff) => {
+                count.is_some_and(|count| index < count)
+                    || cutoff.is_some_and(|cutoff| modified > cutoff)
+            }
+        }
+    }
+}
+
+/// The result of [`Retention::plan`]. Ignores the files that should be pruned.
+///
+/// Paths that were not part of the plan are not ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetentionPlan {
+    kept: HashSet<PathBuf>,
+    pruned: HashSet<PathBuf>,
+}
+
+impl IgnorePath for RetentionPlan {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.pruned.contains(path.as_ref())
+    }
+}
+
+impl RetentionPlan {
+    /// Returns the files to keep, in no particular order.
+    pub fn kept(&self) -> impl Iterator<Item = &Path> {
+        self.kept.iter().map(PathBuf::as_path)
+    }
+
+    /// Returns the files to prune, in no particular order.
+    pub fn pruned(&self) -> impl Iterator<Item = &Path> {
+        self.pruned.iter().map(PathBuf::as_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IgnorePath, Retention};
+    use std::{
+        path::Path,
+        time::{Duration, SystemTime},
+    };
+
+    fn day(n: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(n * 86400)
+    }
+
+    fn sorted<'a>(paths: impl Iterator<Item = &'a Path>) -> Vec<&'a Path> {
+        let mut paths: Vec<_> = paths.collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn retention() {
+        let files = [
+            ("a/1", day(1)),
+            ("a/2", day(2)),
+            ("a/3", day(3)),
+            ("a/4", day(4)),
+            ("b/1", day(1)),
+        ];
+
+        let plan = Retention::new().plan(files);
+        assert_eq!(plan.pruned().count(), 0);
+        assert_eq!(plan.kept().count(), 5);
+
+        let plan = Retention::new().keep_latest(2).plan(files);
+        assert_eq!(sorted(plan.pruned()), [Path::new("a/1"), Path::new("a/2")]);
+
+        let plan = Retention::new().keep_newer_than(day(2)).plan(files);
+        assert_eq!(
+            sorted(plan.pruned()),
+            [Path::new("a/1"), Path::new("a/2"), Path::new("b/1")]
+        );
+
+        let plan = Retention::new()
+            .keep_latest(1)
+            .keep_newer_than(day(2))
+            .plan(files);
+        assert_eq!(sorted(plan.pruned()), [Path::new("a/1"), Path::new("a/2")]);
+        assert!(plan.ignore("a/1"));
+        assert!(!plan.ignore("a/3"));
+        assert!(!plan.ignore("c/1"));
+
+        let plan = Retention::new()
+            .keep_latest(1)
+            .group_by(|_| String::new())
+            .plan(files);
+        assert_eq!(sorted(plan.kept()), [Path::new("a/4")]);
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn plan_files() {
+        use crate::content::tests::TempDir;
+
+        let dir = TempDir::new();
+        let file = dir.write("backup.tar", "");
+        let missing = dir.path().join("missing.tar");
+
+        let plan = Retention::new()
+            .keep_latest(0)
+            .plan_files([file.clone(), missing.clone()]);
+        assert!(plan.ignore(file));
+        assert!(!plan.ignore(missing));
+    }
+}
@@ -0,0 +1,342 @@
+//! Exporting filters as rsync filter rules.
+//!
+//! The generated rules can be passed to rsync with `--exclude-from` or
+//! `--filter='merge FILE'`, so a transfer honors the same filters.
+//!
+//! Only filters that have an exact rsync equivalent are exported; regular
+//! expressions, for example, have no rsync counterpart and result in an error.
+//! The rules of a [`FilterSet`] are exported with
+//! [`FilterSet::to_rsync_rules`].
+
+use crate::{FilterSet, PathFilter};
+use std::{
+    error::Error,
+    ffi::OsStr,
+    fmt,
+    path::{Component, Path},
+};
+
+/// An error returned when a filter cannot be expressed as an rsync rule.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RsyncError {
+    /// The position of the filter that could not be exported.
+    pub index: usize,
+    /// Why the filter could not be exported.
+    pub reason: &'static str,
+}
+
+impl fmt::Display for RsyncError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "filter {} cannot be expressed as an rsync rule: {}",
+            self.index, self.reason
+        )
+    }
+}
+
+impl Error for RsyncError {}
+
+/// Converts the filters into the contents of an rsync exclude file.
+///
+/// Every ignored extension becomes an exclude rule `- *.ext`. Note that rsync also
+/// applies these rules to dotfiles consisting only of the extension, e.g. `.rs`.
+/// Every component and file name becomes `- name`, which rsync matches at any depth, and
+/// every prefix becomes `- /prefix`, anchored at the root of the transfer.
+///
+/// rsync applies all of these rules to directories as well and skips the contents
+/// of an excluded directory, so `- *.o` also excludes a directory `x.o` with
+/// everything inside of it, which an extension filter keeps.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{rsync, PathFilter};
+///
+/// let filters = [PathFilter::new_extension(".o"), PathFilter::new_extensions([".log", ".tmp"])];
+/// assert_eq!(rsync::to_rules(&filters).unwrap(), "- *.o\n- *.log\n- *.tmp\n");
+/// ```
+/// # Errors
+/// If a filter has no rsync equivalent, an error naming that filter is returned.
+pub fn to_rules(filters: &[PathFilter]) -> Result<String, RsyncError> {
+    write_rules(filters.iter().map(|filter| ('-', filter)).enumerate())
+}
+
+/// Converts the rules of a set into rsync filter rules, see [`FilterSet::to_rsync_rules`].
+pub(crate) fn set_to_rules(set: &FilterSet) -> Result<String, RsyncError> {
+    // A trailing `- *` would also exclude the directories rsync has to descend into.
+    if set.ignores_unmatched() {
+        return Err(RsyncError {
+            index: set.len(),
+            reason: "ignoring unmatched paths has no rsync equivalent",
+        });
+    }
+
+    // rsync does not descend into an excluded directory, so an allow rule can't
+    // re-include paths inside a directory an earlier ignore rule matches.
+    let mut excludes_dirs = false;
+    for (index, rule) in set.rules().iter().enumerate() {
+        if rule.is_ignore() {
+            excludes_dirs |= matches_dirs(rule.filter());
+        } else if excludes_dirs {
+            return Err(RsyncError {
+                index,
+                reason: "allowing paths inside a directory an earlier rule ignores has no rsync equivalent",
+            });
+        }
+    }
+
+    // rsync stops at the first matching rule, a set at the last one.
+    let rules = set.rules().iter().enumerate().rev().map(|(index, rule)| {
+        let action = if rule.is_ignore() { '-' } else { '+' };
+        (index, (action, rule.filter()))
+    });
+    write_rules(rules)
+}
+
+fn write_rules<'a, I>(filters: I) -> Result<String, RsyncError>
+where
+    I: Iterator<Item = (usize, (char, &'a PathFilter))>,
+{
+    let mut rules = String::new();
+    for (index, (action, filter)) in filters {
+        for pattern in patterns(filter).map_err(|reason| RsyncError { index, reason })? {
+            rules.push(action);
+            rules.push(' ');
+            rules.push_str(&pattern);
+            rules.push('\n');
+        }
+    }
+
+    Ok(rules)
+}
+
+fn patterns(filter: &PathFilter) -> Result<Vec<String>, &'static str> {
+    // rsync matches case-sensitively and only splits on `/`.
+    if !filter.options().is_default() || !filter.overrides().is_empty() {
+        return Err("filters with match options have no rsync equivalent");
+    }
+
+    match filter {
+        PathFilter::Extension(x) => Ok(vec![extension_pattern(x.extension())?]),
+        PathFilter::Extensions(x) => {
+            let mut patterns = x
+                .extensions()
+                .map(extension_pattern)
+                .collect::<Result<Vec<_>, _>>()?;
+            patterns.sort();
+            Ok(patterns)
+        }
+        PathFilter::Component(x) => {
+            let mut patterns = x
+                .components()
+                .map(name_pattern)
+                .collect::<Result<Vec<_>, _>>()?;
+            patterns.sort();
+            Ok(patterns)
+        }
+        PathFilter::FileName(x) => {
+            let mut patterns = x.names().map(name_pattern).collect::<Result<Vec<_>, _>>()?;
+            patterns.sort();
+            Ok(patterns)
+        }
+        PathFilter::Stem(_) => Err("file stems have no rsync equivalent"),
+        PathFilter::Prefix(x) => x.prefixes().iter().map(|p| prefix_pattern(p)).collect(),
+        PathFilter::Wildcard(_) => Err("wildcards are not translated to rsync patterns"),
+        PathFilter::Not(_) => Err("negated filters have no rsync equivalent"),
+        PathFilter::And(_) => Err("combined filters have no rsync equivalent"),
+        PathFilter::Or(x) => Ok(x
+            .iter()
+            .map(patterns)
+            .collect::<Result<Vec<_>, _>>()?
+            .concat()),
+        #[cfg(feature = "regex")]
+        PathFilter::Regex(_) => Err("regular expressions have no rsync equivalent"),
+        #[cfg(feature = "glob")]
+        PathFilter::Glob(_) => Err("globs are not translated to rsync patterns"),
+        #[cfg(all(feature = "io", unix))]
+        PathFilter::Owner(_) => Err("owners have no rsync equivalent"),
+    }
+}
+
+/// Returns `true` if the filter ignores paths because of a directory they are in.
+fn matches_dirs(filter: &PathFilter) -> bool {
+    match filter {
+        PathFilter::Component(_) | PathFilter::FileName(_) | PathFilter::Prefix(_) => true,
+        PathFilter::Or(x) => x.iter().any(matches_dirs),
+        _ => false,
+    }
+}
+
+fn extension_pattern(extension: &OsStr) -> Result<String, &'static str> {
+    let extension = extension.to_str().ok_or("extension is not valid UTF-8")?;
+    if extension.contains(['/', '\n']) {
+        return Err("extension contains a path separator or line break");
+    }
+
+    Ok(format!("*.{}", escape(extension)))
+}
+
+fn name_pattern(name: &str) -> Result<String, &'static str> {
+    if name.contains(['/', '\n']) {
+        return Err("name contains a path separator or line break");
+    }
+
+    Ok(escape(name))
+}
+
+fn prefix_pattern(prefix: &Path) -> Result<String, &'static str> {
+    let mut pattern = String::new();
+    for component in prefix.components() {
+        match component {
+            Component::Normal(name) => {
+                let name = name.to_str().ok_or("prefix is not valid UTF-8")?;
+                if name.contains('\n') {
+                    return Err("prefix contains a line break");
+                }
+                pattern.push('/');
+                pattern.push_str(&escape(name));
+            }
+            Component::CurDir => {}
+            _ => return Err("prefix is not a relative path"),
+        }
+    }
+    if pattern.is_empty() {
+        return Err("prefix is empty");
+    }
+
+    Ok(pattern)
+}
+
+fn escape(pattern: &str) -> String {
+    let mut escaped = String::with_capacity(pattern.len());
+    for c in pattern.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{rsync::to_rules, PathFilter};
+
+    #[test]
+    fn extensions() {
+        let filters = [
+            PathFilter::new_extension(".rs"),
+            PathFilter::new_extensions([".txt", ".md"]),
+        ];
+        assert_eq!(to_rules(&filters).unwrap(), "- *.rs\n- *.md\n- *.txt\n");
+        assert_eq!(to_rules(&[]).unwrap(), "");
+    }
+
+    #[test]
+    fn extensions_match_directories() {
+        use crate::IgnorePath;
+
+        // The filter keeps the contents of a directory `x.o`, rsync skips them.
+        let filter = PathFilter::new_extension(".o");
+        assert!(!filter.ignore("x.o/main.c"));
+        assert_eq!(to_rules(&[filter]).unwrap(), "- *.o\n");
+    }
+
+    #[test]
+    fn filter_set() {
+        use crate::{rsync::RsyncError, FilterSet};
+
+        let set = FilterSet::new()
+            .with_ignore(PathFilter::new_extension(".log"))
+            .with_allow(PathFilter::new_file_names(["keep.log"]));
+        assert_eq!(set.to_rsync_rules().unwrap(), "+ keep.log\n- *.log\n");
+
+        let set = set.with_ignore(PathFilter::new_stems(["README"]));
+        assert_eq!(set.to_rsync_rules().unwrap_err().index, 2);
+
+        let set = FilterSet::new()
+            .with_ignore(PathFilter::new_components(["build"]))
+            .with_allow(PathFilter::new_file_names(["keep.txt"]));
+        assert_eq!(
+            set.to_rsync_rules().unwrap_err(),
+            RsyncError {
+                index: 1,
+                reason: "allowing paths inside a directory an earlier rule ignores has no rsync equivalent"
+            }
+        );
+        // An ignore rule after the allow rule decides inside the directory anyway.
+        let set = FilterSet::new()
+            .with_allow(PathFilter::new_file_names(["keep.txt"]))
+            .with_ignore(PathFilter::new_components(["build"]));
+        assert_eq!(set.to_rsync_rules().unwrap(), "- build\n+ keep.txt\n");
+    }
+
+    #[test]
+    fn rejects_options() {
+        use crate::{MatchOptions, MatchOverrides};
+
+        let filters = [
+            PathFilter::new_extension(".rs"),
+            PathFilter::new_extension(".o")
+                .with_options(MatchOptions::new().case_insensitive(true)),
+        ];
+        let err = to_rules(&filters).unwrap_err();
+        assert_eq!(err.index, 1);
+        assert_eq!(
+            err.reason,
+            "filters with match options have no rsync equivalent"
+        );
+
+        let filters = [PathFilter::new_prefixes(["build"])
+            .with_overrides(MatchOverrides::new().normalize_separators(true))];
+        assert!(to_rules(&filters).is_err());
+    }
+
+    #[test]
+    fn components() {
+        let filters = [PathFilter::new_components(["target", "node_modules"])];
+        assert_eq!(to_rules(&filters).unwrap(), "- node_modules\n- target\n");
+    }
+
+    #[test]
+    fn prefixes() {
+        let filters = [PathFilter::new_prefixes([
+            "./build/",
+            "third_party/vendored",
+        ])];
+        assert_eq!(
+            to_rules(&filters).unwrap(),
+            "- /build\n- /third_party/vendored\n"
+        );
+        assert!(to_rules(&[PathFilter::new_prefixes(["../up"])]).is_err());
+    }
+
+    #[test]
+    fn escapes_wildcards() {
+        let filters = [PathFilter::new_extension("b[a]k*")];
+        assert_eq!(to_rules(&filters).unwrap(), "- *.b\\[a\\]k\\*\n");
+    }
+
+    #[test]
+    fn rejects_separators() {
+        let filters = [PathFilter::new_extension("a/b")];
+        assert_eq!(to_rules(&filters).unwrap_err().index, 0);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn rejects_regex() {
+        use regex::Regex;
+
+        let filters = [
+            PathFilter::new_extension(".rs"),
+            PathFilter::new_regex(Regex::new("^src/").unwrap()),
+        ];
+        let err = to_rules(&filters).unwrap_err();
+        assert_eq!(err.index, 1);
+        assert_eq!(
+            err.to_string(),
+            "filter 1 cannot be expressed as an rsync rule: regular expressions have no rsync equivalent"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{IgnorePath, MatchCandidate};
+use std::{path::Path, sync::Arc};
+
+/// An object-safe version of [`IgnorePath`], for filters behind `dyn`.
+///
+/// Every [`IgnorePath`] implements this trait, and `dyn DynIgnorePath` implements
+/// [`IgnorePath`] again, so filters of different types can be stored together.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{presets, DynIgnorePath, IgnorePath, PathFilter};
+/// use std::path::Path;
+///
+/// let filters: Vec<Box<dyn DynIgnorePath>> = vec![
+///     Box::new(PathFilter::new_extension(".rs")),
+///     Box::new(presets::IMAGES),
+/// ];
+/// assert!(filters.ignore(Path::new("src/lib.rs")));
+/// assert!(filters.ignore(Path::new("logo.png")));
+/// ```
+pub trait DynIgnorePath {
+    /// Returns `true` if the path should be ignored.
+    fn ignore_path(&self, path: &Path) -> bool;
+
+    /// Returns `true` if the path of the candidate should be ignored.
+    fn ignore_path_candidate(&self, candidate: &MatchCandidate<'_>) -> bool;
+}
+
+impl<T: IgnorePath> DynIgnorePath for T {
+    fn ignore_path(&self, path: &Path) -> bool {
+        self.ignore(path)
+    }
+
+    fn ignore_path_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        self.ignore_candidate(candidate)
+    }
+}
+
+macro_rules! impl_dyn {
+    ($($ty:ty),*) => {$(
+        impl IgnorePath for $ty {
+            fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+                self.ignore_path(path.as_ref())
+            }
+
+            fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+                self.ignore_path_candidate(candidate)
+            }
+        }
+    )*};
+}
+
+impl_dyn!(
+    dyn DynIgnorePath + '_,
+    dyn DynIgnorePath + Send + '_,
+    dyn DynIgnorePath + Send + Sync + '_
+);
+
+impl<T: IgnorePath + ?Sized> IgnorePath for Box<T> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        (**self).ignore(path)
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        (**self).ignore_candidate(candidate)
+    }
+}
+
+impl<T: IgnorePath + ?Sized> IgnorePath for Arc<T> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        (**self).ignore(path)
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        (**self).ignore_candidate(candidate)
+    }
+}
+
+/// A filter of any type that can be shared between threads.
+///
+/// All filters of this crate are `Send` and `Sync`, so any of them can be turned
+/// into a shared filter with [`shared`]. `shared & filter` and `shared | filter`
+/// work for the filters of this crate; negating or combining two shared filters
+/// needs [`Not::new`](crate::Not::new) or [`And::new`](crate::And::new), as their
+/// operators cannot be implemented for `Arc`.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{shared, IgnorePath, PathFilter, SharedFilter};
+/// use std::{path::Path, thread};
+///
+/// let filter: SharedFilter = shared(PathFilter::new_extension(".o"));
+/// let handle = thread::spawn({
+///     let filter = filter.clone();
+///     move || filter.ignore(Path::new("main.o"))
+/// });
+/// assert!(handle.join().unwrap());
+/// ```
+pub type SharedFilter = Arc<dyn DynIgnorePath + Send + Sync>;
+
+/// Wraps a filter into a [`SharedFilter`].
+pub fn shared<F: IgnorePath + Send + Sync + 'static>(filter: F) -> SharedFilter {
+    Arc::new(filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shared, ExtensionFilter, IgnorePath, SharedFilter};
+    use std::path::Path;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn send_sync() {
+        use crate::*;
+
+        assert_send_sync::<PathFilter>();
+        assert_send_sync::<ExtensionFilter>();
+        assert_send_sync::<ExtensionsFilter>();
+        assert_send_sync::<StaticExtensionsFilter>();
+        assert_send_sync::<FilterChain>();
+        assert_send_sync::<IncludeFilter>();
+        assert_send_sync::<LimitFilter>();
+        assert_send_sync::<RetentionPlan>();
+        assert_send_sync::<MatchContext>();
+        assert_send_sync::<patterns::PatternFile>();
+        assert_send_sync::<archive::ArchiveEntryFilter<PathFilter>>();
+        assert_send_sync::<presets::StaticSiteFilter>();
+        assert_send_sync::<SharedFilter>();
+        #[cfg(feature = "regex")]
+        {
+            assert_send_sync::<RegexFilter>();
+            assert_send_sync::<RegexSetFilter>();
+        }
+        #[cfg(feature = "io")]
+        {
+            assert_send_sync::<HashSetContentFilter<Fnv1a>>();
+            assert_send_sync::<LfsPointerFilter>();
+            assert_send_sync::<MarkerFilter>();
+            assert_send_sync::<SidecarFilter>();
+            assert_send_sync::<SortedPathFile>();
+            assert_send_sync::<presets::CodeSearchFilter>();
+        }
+        #[cfg(all(feature = "xattr", any(target_os = "linux", target_os = "macos")))]
+        assert_send_sync::<XattrFilter>();
+    }
+
+    #[test]
+    fn shared_filters() {
+        let filters: Vec<SharedFilter> = vec![
+            shared(ExtensionFilter::new(".rs")),
+            shared(vec![ExtensionFilter::new(".md")]),
+        ];
+        assert!(filters.ignore(Path::new("src/lib.rs")));
+        assert!(filters.ignore(Path::new("README.md")));
+        assert!(!filters.ignore(Path::new("Cargo.toml")));
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{content::io_error_policy_methods, IgnorePath, IoErrorPolicy, PathFilter};
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum SidecarRule {
+    IgnoreWithout,
+    IgnoreWith,
+    Sidecars,
+}
+
+/// A filter that decides based on sidecar files, e.g. `texture.png.meta` next to `texture.png`.
+///
+/// The sidecar of a file is named like the file with a suffix appended. Files the
+/// inner filter does not match are never ignored.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{ExtensionFilter, IgnorePath, SidecarFilter};
+///
+/// // skip textures that have not been imported yet
+/// let filter = SidecarFilter::ignore_without(ExtensionFilter::new(".png"), ".meta");
+/// assert!(filter.ignore("assets/new.png"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SidecarFilter<F = PathFilter> {
+    filter: F,
+    suffix: OsString,
+    rule: SidecarRule,
+    on_error: IoErrorPolicy,
+}
+
+impl<F: IgnorePath> IgnorePath for SidecarFilter<F> {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        match self.rule {
+            SidecarRule::IgnoreWithout | SidecarRule::IgnoreWith => {
+                if !self.filter.ignore(path) {
+                    return false;
+                }
+                let exists = self.sidecar_exists(path);
+                self.on_error.resolve(match self.rule {
+                    SidecarRule::IgnoreWith => exists,
+                    _ => exists.map(|exists| !exists),
+                })
+            }
+            SidecarRule::Sidecars => self
+                .main_file(path)
+                .is_some_and(|main| self.filter.ignore(main)),
+        }
+    }
+}
+
+impl<F> SidecarFilter<F> {
+    /// Ignores files matched by `filter` that have no sidecar with the given suffix.
+    pub fn ignore_without<S: AsRef<OsStr>>(filter: F, suffix: S) -> Self {
+        SidecarFilter::with_rule(filter, suffix, SidecarRule::IgnoreWithout)
+    }
+
+    /// Ignores files matched by `filter` that have a sidecar with the given suffix.
+    pub fn ignore_with<S: AsRef<OsStr>>(filter: F, suffix: S) -> Self {
+        SidecarFilter::with_rule(filter, suffix, SidecarRule::IgnoreWith)
+    }
+
+    /// Ignores sidecars with the given suffix whose main file `filter` ignores.
+    ///
+    /// This only looks at paths, so the main file does not need to exist. Sidecars
+    /// with names that are not valid UTF-8 are not ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{ExtensionFilter, IgnorePath, SidecarFilter};
+    ///
+    /// let filter = SidecarFilter::sidecars(ExtensionFilter::new(".psd"), ".meta");
+    /// assert!(filter.ignore("art/hero.psd.meta"));
+    /// assert!(!filter.ignore("art/hero.png.meta"));
+    /// assert!(!filter.ignore("art/hero.psd"));
+    /// ```
+    pub fn sidecars<S: AsRef<OsStr>>(filter: F, suffix: S) -> Self {
+        SidecarFilter::with_rule(filter, suffix, SidecarRule::Sidecars)
+    }
+
+    fn with_rule<S: AsRef<OsStr>>(filter: F, suffix: S, rule: SidecarRule) -> Self {
+        SidecarFilter {
+            filter,
+            suffix: suffix.as_ref().to_os_string(),
+            rule,
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    io_error_policy_methods!("files whose sidecar cannot be checked");
+
+    /// Returns the suffix of sidecar files.
+    pub fn suffix(&self) -> &OsStr {
+        &self.suffix
+    }
+
+    /// Returns the sidecar path of `path`.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::{ExtensionFilter, SidecarFilter};
+    /// use std::path::Path;
+    ///
+    /// let filter = SidecarFilter::ignore_without(ExtensionFilter::new(".png"), ".meta");
+    /// assert_eq!(filter.sidecar_path("a/b.png"), Path::new("a/b.png.meta"));
+    /// ```
+    pub fn sidecar_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        let mut sidecar = path.as_ref().as_os_str().to_os_string();
+        sidecar.push(&self.suffix);
+        sidecar.into()
+    }
+
+    fn sidecar_exists(&self, path: &Path) -> io::Result<bool> {
+        self.sidecar_path(path).try_exists()
+    }
+
+    fn main_file(&self, path: &Path) -> Option<PathBuf> {
+        let name = path.file_name()?.to_str()?;
+        let main = name.strip_suffix(self.suffix.to_str()?)?;
+        (!main.is_empty()).then(|| path.with_file_name(main))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        content::tests::TempDir, ExtensionFilter, IgnorePath, IoErrorPolicy, SidecarFilter,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn sidecar_filter() {
+        let dir = TempDir::new();
+        let imported = dir.write("imported.png", "");
+        dir.write("imported.png.meta", "");
+        let new = dir.write("new.png", "");
+        let text = dir.write("notes.txt", "");
+
+        let without = SidecarFilter::ignore_without(ExtensionFilter::new(".png"), ".meta");
+        assert!(!without.ignore(&imported));
+        assert!(without.ignore(&new));
+        assert!(!without.ignore(&text));
+
+        let with = SidecarFilter::ignore_with(ExtensionFilter::new(".png"), ".meta");
+        assert!(with.ignore(&imported));
+        assert!(!with.ignore(&new));
+        assert!(!with.ignore(&text));
+        assert!(!with
+            .with_io_error_policy(IoErrorPolicy::Ignore)
+            .ignore(&new));
+    }
+
+    #[test]
+    fn sidecars() {
+        let filter = SidecarFilter::sidecars(ExtensionFilter::new(".png"), ".meta");
+        assert!(filter.ignore(Path::new("a/b.png.meta")));
+        assert!(!filter.ignore(Path::new("a/b.txt.meta")));
+        assert!(!filter.ignore(Path::new("a/.meta")));
+        assert!(!filter.ignore(Path::new("a/b.png")));
+        assert!(!filter.ignore(Path::new("a/b.meta")));
+    }
+}
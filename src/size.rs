@@ -0,0 +1,112 @@
+use crate::{content::io_error_policy_methods, IgnorePath, IoErrorPolicy};
+use std::{fs, io, path::Path};
+
+/// A filter that matches files by their size.
+///
+/// The size is read with [`fs::metadata`], so symlinks are followed. Directories
+/// and other entries that are not files are never matched.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{IgnorePath, SizeFilter};
+///
+/// const MB: u64 = 1024 * 1024;
+///
+/// let filter = SizeFilter::larger_than(100 * MB);
+/// assert!(filter.ignore("videos/holiday.mp4"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SizeFilter {
+    limit: u64,
+    larger: bool,
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for SizeFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+impl SizeFilter {
+    /// Creates a new filter for files larger than `size` bytes.
+    pub fn larger_than(size: u64) -> Self {
+        SizeFilter {
+            limit: size,
+            larger: true,
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    /// Creates a new filter for files smaller than `size` bytes.
+    pub fn smaller_than(size: u64) -> Self {
+        SizeFilter {
+            limit: size,
+            larger: false,
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    io_error_policy_methods!(
+        "paths whose metadata cannot be read",
+        "e.g. because they do not exist"
+    );
+
+    /// Returns whether a file of `size` bytes is matched.
+    ///
+    /// # Examples
+    /// ```
+    /// use pathfilter::SizeFilter;
+    ///
+    /// let filter = SizeFilter::smaller_than(10);
+    /// assert!(filter.matches_size(9));
+    /// assert!(!filter.matches_size(10));
+    /// ```
+    pub fn matches_size(&self, size: u64) -> bool {
+        if self.larger {
+            size > self.limit
+        } else {
+            size < self.limit
+        }
+    }
+
+    /// Returns whether the path is a file of a matching size.
+    ///
+    /// # Errors
+    /// If the metadata cannot be read, an error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let metadata = fs::metadata(path)?;
+        Ok(metadata.is_file() && self.matches_size(metadata.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{content::tests::TempDir, IgnorePath, IoErrorPolicy, SizeFilter};
+
+    #[test]
+    fn size_filter() {
+        let dir = TempDir::new();
+        let empty = dir.write("empty", "");
+        let small = dir.write("small", [0; 10]);
+        let large = dir.write("large", [0; 4096]);
+
+        let filter = SizeFilter::larger_than(10);
+        assert!(!filter.ignore(&empty));
+        assert!(!filter.ignore(&small));
+        assert!(filter.ignore(&large));
+        assert!(!filter.ignore(dir.path()));
+
+        let filter = SizeFilter::smaller_than(10);
+        assert!(filter.ignore(&empty));
+        assert!(!filter.ignore(&small));
+        assert!(!filter.ignore(&large));
+        assert!(!filter.ignore(dir.path()));
+
+        let missing = dir.path().join("missing");
+        assert!(!filter.ignore(&missing));
+        assert!(filter
+            .with_io_error_policy(IoErrorPolicy::Ignore)
+            .ignore(&missing));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{content::io_error_policy_methods, IgnorePath, IoErrorPolicy};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// A filter for exact paths listed in a sorted file, looked up without loading the file.
+///
+/// The file contains one path per line, separated by `\n` and sorted by their
+/// bytes, e.g. with `LC_ALL=C sort -u`. Every lookup is a binary search that reads
+/// a few lines from disk, so lists with millions of paths need almost no memory.
+/// Paths must match exactly, see [`write`](Self::write) to create a file.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{IgnorePath, SortedPathFile};
+///
+/// SortedPathFile::write("exclude.txt", ["b/2", "a/1"]).unwrap();
+/// let filter = SortedPathFile::open("exclude.txt").unwrap();
+/// assert!(filter.ignore("a/1"));
+/// assert!(!filter.ignore("a/2"));
+/// ```
+#[derive(Debug)]
+pub struct SortedPathFile {
+    file: Mutex<BufReader<File>>,
+    len: u64,
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for SortedPathFile {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.contains(path))
+    }
+}
+
+impl SortedPathFile {
+    /// Opens a sorted file of paths.
+    ///
+    /// The order is not checked. Lookups in a file that is not sorted can miss paths.
+    ///
+    /// # Errors
+    /// If the file cannot be opened, an error is returned.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        Ok(SortedPathFile {
+            file: Mutex::new(BufReader::new(file)),
+            len,
+            on_error: IoErrorPolicy::Keep,
+        })
+    }
+
+    /// Writes the paths sorted and without duplicates to `file`.
+    ///
+    /// # Errors
+    /// If a path contains a newline or the file cannot be written, an error is returned.
+    pub fn write<F, I, P>(file: F, paths: I) -> io::Result<()>
+    where
+        F: AsRef<Path>,
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        let mut paths: Vec<PathBuf> = paths.into_iter().map(Into::into).collect();
+        paths.sort_by(|a, b| a.as_os_str().cmp(b.as_os_str()));
+        paths.dedup();
+
+        let mut writer = BufWriter::new(File::create(file)?);
+        for path in &paths {
+            let line = path.as_os_str().as_encoded_bytes();
+            if line.contains(&b'\n') {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("path contains a newline: {}", path.display()),
+                ));
+            }
+            writer.write_all(line)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()
+    }
+
+    io_error_policy_methods!("paths that cannot be looked up because the file cannot be read");
+
+    /// Returns whether the path is listed in the file.
+    ///
+    /// # Errors
+    /// If the file cannot be read, an error is returned.
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        let target = path.as_ref().as_os_str().as_encoded_bytes();
+        let mut file = self.file.lock().unwrap_or_else(|err| err.into_inner());
+        let mut line = Vec::new();
+
+        let (mut lo, mut hi) = (0, self.len);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let Some(start) = line_at_or_after(&mut file, mid, &mut line)? else {
+                hi = mid;
+                continue;
+            };
+            match line.as_slice().cmp(target) {
+                std::cmp::Ordering::Equal => return Ok(true),
+                std::cmp::Ordering::Less => lo = start + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Reads the first line starting at or after `offset` into `line` and returns its start.
+fn line_at_or_after(
+    file: &mut BufReader<File>,
+    offset: u64,
+    line: &mut Vec<u8>,
+) -> io::Result<Option<u64>> {
+    let mut start = offset;
+    line.clear();
+    if offset > 0 {
+        file.seek(SeekFrom::Start(offset - 1))?;
+        start += file.read_until(b'\n', line)? as u64 - 1;
+        line.clear();
+    } else {
+        file.seek(SeekFrom::Start(0))?;
+    }
+
+    if file.read_until(b'\n', line)? == 0 {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+    }
+    Ok(Some(start))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{content::tests::TempDir, IgnorePath, SortedPathFile};
+
+    #[test]
+    fn sorted_path_file() {
+        let dir = TempDir::new();
+        let list = dir.path().join("list.txt");
+        let paths: Vec<String> = (0..500).map(|i| format!("dir{}/file{i}", i % 7)).collect();
+        SortedPathFile::write(&list, paths.iter().rev().chain(&paths)).unwrap();
+
+        let filter = SortedPathFile::open(&list).unwrap();
+        for path in &paths {
+            assert!(filter.ignore(path), "{path}");
+        }
+        for path in ["", "dir0", "dir0/file", "dir9/file1", "zzz", "a"] {
+            assert!(!filter.ignore(path), "{path}");
+        }
+    }
+
+    #[test]
+    fn sorted_path_file_edge_cases() {
+        let dir = TempDir::new();
+        let empty = dir.write("empty.txt", "");
+        assert!(!SortedPathFile::open(empty).unwrap().ignore("a"));
+
+        let unterminated = dir.write("unterminated.txt", "a\nb\nc");
+        let filter = SortedPathFile::open(unterminated).unwrap();
+        assert!(filter.ignore("a"));
+        assert!(filter.ignore("c"));
+        assert!(!filter.ignore("d"));
+
+        assert!(SortedPathFile::write(dir.path().join("bad.txt"), ["a\nb"]).is_err());
+    }
+}
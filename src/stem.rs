@@ -0,0 +1,116 @@
+use crate::{
+    names::{self, NameSet},
+    options::options_methods,
+    IgnorePath, MatchCandidate, MatchOptions, MatchOverrides,
+};
+use std::path::Path;
+
+/// A filter that matches paths whose file stem is one of a set of stems.
+///
+/// The stem is the file name without its extension, see [`Path::file_stem`], so
+/// `README` matches `README`, `README.md` and `docs/README.txt` but not
+/// `README.old.md`, whose stem is `README.old`. Stems that are not valid UTF-8
+/// never match. With [`MatchOptions::normalize_separators`], the file name starts
+/// after the last `\` too.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{StemFilter, IgnorePath};
+/// use std::path::Path;
+///
+/// let filter = StemFilter::new(["README", "LICENSE"]);
+/// assert!(filter.ignore(Path::new("README.md")));
+/// assert!(filter.ignore(Path::new("vendor/zlib/LICENSE")));
+/// assert!(!filter.ignore(Path::new("src/readme.rs")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StemFilter {
+    stems: NameSet,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOptions::is_default")
+    )]
+    options: MatchOptions,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOverrides::is_empty")
+    )]
+    overrides: MatchOverrides,
+}
+
+impl IgnorePath for StemFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        let options = self.options();
+        candidate
+            .path()
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| Path::new(names::file_name(name, options)).file_stem())
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| {
+                candidate.with_buffer(|buffer| self.stems.contains(stem, options, buffer))
+            })
+    }
+}
+
+impl StemFilter {
+    /// Creates a new filter for a list of file stems.
+    pub fn new<I, S>(stems: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        StemFilter {
+            stems: stems.into_iter().collect(),
+            options: MatchOptions::new(),
+            overrides: MatchOverrides::new(),
+        }
+    }
+
+    /// Adds a file stem to the filter.
+    pub fn with_stem<S: Into<String>>(mut self, stem: S) -> Self {
+        self.stems.insert(stem.into());
+        self
+    }
+
+    /// Returns the file stems this filter matches, in unspecified order.
+    pub fn stems(&self) -> impl Iterator<Item = &str> {
+        self.stems.iter()
+    }
+
+    options_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IgnorePath, MatchOptions, StemFilter};
+    use std::path::Path;
+
+    #[test]
+    fn stems() {
+        let filter = StemFilter::new(["README"]).with_stem("archive.tar");
+        assert!(filter.ignore(Path::new("README")));
+        assert!(filter.ignore(Path::new("docs/README.md")));
+        assert!(filter.ignore(Path::new("backups/archive.tar.gz")));
+        assert!(!filter.ignore(Path::new("backups/archive.tar")));
+        assert!(!filter.ignore(Path::new("README.old.md")));
+        assert!(!filter.ignore(Path::new("README/index.md")));
+        assert!(!filter.ignore(Path::new("readme.md")));
+
+        let filter = filter.with_options(MatchOptions::new().case_insensitive(true));
+        assert!(filter.ignore(Path::new("readme.md")));
+
+        let filter = filter.with_options(MatchOptions::new().normalize_separators(true));
+        assert!(filter.ignore(Path::new("docs\\README.md")));
+        assert!(filter.ignore(Path::new("v1.2\\README")));
+
+        let mut stems: Vec<_> = filter.stems().collect();
+        stems.sort();
+        assert_eq!(stems, ["README", "archive.tar"]);
+    }
+}
@@ -0,0 +1,246 @@
+//! Loading Syncthing `.stignore` files.
+//!
+//! Every line is a glob, matched against paths relative to the folder root:
+//! - blank lines and lines starting with `//` are skipped,
+//! - `*` and `?` do not match `/`, `**` matches across directories, and `{a,b}`
+//!   matches either alternative,
+//! - a leading `/` anchors a pattern at the root, other patterns match at any depth,
+//! - a pattern matching a directory also matches everything inside of it,
+//! - the prefixes `!`, `(?i)` and `(?d)`, in any order, re-include the paths,
+//!   match case-insensitively and allow deleting the paths; the last one does not
+//!   change what is matched,
+//! - `#include file` reads the patterns of another file, relative to the
+//!   directory of the including file,
+//! - the first matching pattern decides.
+//!
+//! The patterns are loaded into a [`FilterSet`], whose last matching rule decides,
+//! so its rules are in the reverse order of the lines.
+//!
+//! # Examples
+//! ```
+//! use pathfilter::{stignore, IgnorePath};
+//! use std::path::Path;
+//!
+//! let set = stignore::parse("// build output\n!/target/doc\n/target\n(?i)*.tmp\n").unwrap();
+//! assert!(set.ignore(Path::new("target/debug/main")));
+//! assert!(!set.ignore(Path::new("target/doc/index.html")));
+//! assert!(!set.ignore(Path::new("crates/a/target/main")));
+//! assert!(set.ignore(Path::new("notes/TODO.TMP")));
+//! ```
+
+use crate::{
+    glob::{translate, Dialect},
+    patterns::{PatternError, PatternErrorKind, PatternFileError},
+    FilterRule, FilterSet, GlobError, MatchOptions, PathFilter, RegexFilter,
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Parses the contents of a `.stignore` file.
+///
+/// As there is no file to resolve them against, `#include` lines are reported as
+/// an [`UnknownKind`](PatternErrorKind::UnknownKind) error; use [`from_file`] for
+/// files with includes.
+///
+/// # Errors
+/// If a pattern is invalid, an error with its location is returned.
+pub fn parse(text: &str) -> Result<FilterSet, PatternError> {
+    let mut rules = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if let Some(rule) = parse_line(line, index + 1)? {
+            rules.push(rule);
+        }
+    }
+    Ok(rules.into_iter().rev().collect())
+}
+
+/// Reads and parses a `.stignore` file, including the files it `#include`s.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::stignore;
+///
+/// let set = stignore::from_file("Sync/.stignore").unwrap();
+/// ```
+/// # Errors
+/// If a file cannot be read, includes itself, or a pattern is invalid, an error is
+/// returned. Pattern errors carry the name of the file.
+pub fn from_file<P: AsRef<Path>>(path: P) -> Result<FilterSet, PatternFileError> {
+    let mut rules = Vec::new();
+    load(path.as_ref(), &mut Vec::new(), &mut rules)?;
+    Ok(rules.into_iter().rev().collect())
+}
+
+fn load(
+    path: &Path,
+    including: &mut Vec<PathBuf>,
+    rules: &mut Vec<FilterRule>,
+) -> Result<(), PatternFileError> {
+    let io_error = |source| PatternFileError::Io {
+        file: path.to_path_buf(),
+        source,
+    };
+    let canonical = fs::canonicalize(path).map_err(io_error)?;
+    if including.contains(&canonical) {
+        return Err(io_error(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the file includes itself",
+        )));
+    }
+    let text = fs::read_to_string(path).map_err(io_error)?;
+
+    including.push(canonical);
+    for (index, line) in text.lines().enumerate() {
+        if let Some(file) = line.strip_prefix("#include") {
+            let file = file.trim();
+            if file.is_empty() {
+                return Err(PatternError {
+                    file: Some(path.to_path_buf()),
+                    line: index + 1,
+                    column: line.chars().count() + 1,
+                    text: line.to_string(),
+                    kind: PatternErrorKind::MissingValue,
+                }
+                .into());
+            }
+            let dir = path.parent().unwrap_or(Path::new(""));
+            load(&dir.join(file), including, rules)?;
+        } else if let Some(rule) = parse_line(line, index + 1).map_err(|mut err| {
+            err.file = Some(path.to_path_buf());
+            err
+        })? {
+            rules.push(rule);
+        }
+    }
+    including.pop();
+    Ok(())
+}
+
+fn parse_line(line: &str, number: usize) -> Result<Option<FilterRule>, PatternError> {
+    if line.is_empty() || line.starts_with("//") {
+        return Ok(None);
+    }
+    let error = |rest: &str, kind| PatternError {
+        file: None,
+        line: number,
+        column: line[..line.len() - rest.len()].chars().count() + 1,
+        text: line.to_string(),
+        kind,
+    };
+    if line.starts_with("#include") {
+        let kind = PatternErrorKind::UnknownKind("#include".to_string());
+        return Err(error(line, kind));
+    }
+
+    let (mut negated, mut case_insensitive) = (false, false);
+    let mut pattern = line;
+    loop {
+        if let Some(rest) = pattern.strip_prefix('!') {
+            negated = true;
+            pattern = rest;
+        } else if let Some(rest) = pattern.strip_prefix("(?i)") {
+            case_insensitive = true;
+            pattern = rest;
+        } else if let Some(rest) = pattern.strip_prefix("(?d)") {
+            pattern = rest;
+        } else {
+            break;
+        }
+    }
+    if pattern.is_empty() {
+        return Err(error(pattern, PatternErrorKind::MissingValue));
+    }
+
+    let (anchor, glob) = match pattern.strip_prefix('/') {
+        Some(glob) => ("", glob),
+        None => ("(?:.*/)?", pattern),
+    };
+    let dialect = Dialect {
+        braces: true,
+        ..Dialect::default()
+    };
+    let body = translate(glob.trim_end_matches('/'), dialect).map_err(|reason| {
+        let glob = GlobError {
+            glob: pattern.to_string(),
+            reason,
+        };
+        error(pattern, PatternErrorKind::Glob(glob))
+    })?;
+    let filter: PathFilter = RegexFilter::new_str(&format!("^{anchor}{body}(?:/.*)?$"))
+        .map_err(|err| error(pattern, PatternErrorKind::Regex(err)))?
+        .with_options(MatchOptions::new().case_insensitive(case_insensitive))
+        .into();
+
+    Ok(Some(if negated {
+        FilterRule::Allow(filter)
+    } else {
+        FilterRule::Ignore(filter)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{patterns::PatternErrorKind, stignore::parse, IgnorePath};
+    use std::path::Path;
+
+    #[test]
+    fn patterns() {
+        let set = parse("!keep/important.log\n*.{log,tmp}\n(?d)(?i)/Thumbs.db\nfoo/bar\n").unwrap();
+        assert_eq!(set.len(), 4);
+        assert!(set.ignore(Path::new("a/b/debug.log")));
+        assert!(!set.ignore(Path::new("keep/important.log")));
+        assert!(!set.ignore(Path::new("x/keep/important.log/y")));
+        assert!(set.ignore(Path::new("keep/other.log")));
+        assert!(set.ignore(Path::new("THUMBS.DB")));
+        assert!(!set.ignore(Path::new("photos/Thumbs.db")));
+        assert!(set.ignore(Path::new("x/foo/bar/baz")));
+        assert!(!set.ignore(Path::new("x/foo/barbaz")));
+
+        // The first matching pattern decides.
+        let set = parse("*.log\n!important.log\n").unwrap();
+        assert!(set.ignore(Path::new("important.log")));
+        assert!(parse("// only a comment\n\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let err = parse("ok\n!(?i)[abc\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::Glob(_)));
+        assert_eq!((err.line, err.column), (2, 6));
+
+        let err = parse("!(?d)\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::MissingValue));
+        let err = parse("#include more\n").unwrap_err();
+        assert!(matches!(err.kind, PatternErrorKind::UnknownKind(_)));
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn includes() {
+        use crate::{content::tests::TempDir, patterns::PatternFileError, stignore::from_file};
+
+        let dir = TempDir::new();
+        dir.write("common.txt", "*.tmp\n!keep.log\n");
+        let root = dir.write(".stignore", "#include common.txt\n*.log\n");
+        let set = from_file(&root).unwrap();
+        assert!(set.ignore(Path::new("a.tmp")));
+        assert!(set.ignore(Path::new("debug.log")));
+        assert!(!set.ignore(Path::new("keep.log")));
+
+        let looped = dir.write("loop.txt", "#include loop.txt\n");
+        assert!(matches!(
+            from_file(&looped),
+            Err(PatternFileError::Io { .. })
+        ));
+        let invalid = dir.write("invalid.txt", "#include common.txt\n[\n");
+        match from_file(&invalid) {
+            Err(PatternFileError::Pattern(err)) => {
+                assert_eq!(err.file.as_deref(), Some(invalid.as_path()));
+                assert_eq!(err.line, 2);
+            }
+            other => panic!("{other:?}"),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{options::options_methods, IgnorePath, MatchOptions, MatchOverrides};
+use aho_corasick::AhoCorasick;
+use std::{borrow::Cow, path::Path};
+
+/// A filter that matches paths containing any of a set of substrings.
+///
+/// All substrings are searched for at once with an Aho-Corasick automaton, so
+/// matching stays fast with thousands of them. The path is searched as bytes,
+/// which includes paths that are not valid UTF-8. Case-insensitive matching only
+/// folds ASCII letters, even with [`MatchOptions::unicode_case`].
+///
+/// With the `serde` feature the filter is serialized as its substrings together
+/// with its options and overrides.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, SubstringFilter};
+/// use std::path::Path;
+///
+/// let filter = SubstringFilter::new(["/generated/", ".min.", "~"]);
+/// assert!(filter.ignore(Path::new("web/dist/app.min.js")));
+/// assert!(filter.ignore(Path::new("src/lib.rs~")));
+/// assert!(!filter.ignore(Path::new("src/lib.rs")));
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "SubstringFilterDef")
+)]
+pub struct SubstringFilter {
+    needles: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    automaton: AhoCorasick,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "MatchOptions::is_default")
+    )]
+    options: MatchOptions,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "MatchOverrides::is_empty")
+    )]
+    overrides: MatchOverrides,
+}
+
+/// The serialized form of a [`SubstringFilter`], built with its options on deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SubstringFilterDef {
+    needles: Vec<String>,
+    #[serde(default)]
+    options: MatchOptions,
+    #[serde(default)]
+    overrides: MatchOverrides,
+}
+
+#[cfg(feature = "serde")]
+impl From<SubstringFilterDef> for SubstringFilter {
+    fn from(value: SubstringFilterDef) -> Self {
+        SubstringFilter::new(value.needles)
+            .with_options(value.options)
+            .with_overrides(value.overrides)
+    }
+}
+
+impl IgnorePath for SubstringFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        let bytes = path.as_ref().as_os_str().as_encoded_bytes();
+        self.automaton
+            .is_match(normalized(bytes, self.options()).as_ref())
+    }
+}
+
+fn normalized(bytes: &[u8], options: MatchOptions) -> Cow<'_, [u8]> {
+    if options.normalizes_separators() && bytes.contains(&b'\\') {
+        Cow::Owned(
+            bytes
+                .iter()
+                .map(|&b| if b == b'\\' { b'/' } else { b })
+                .collect(),
+        )
+    } else {
+        Cow::Borrowed(bytes)
+    }
+}
+
+impl SubstringFilter {
+    /// Creates a new filter for a list of substrings.
+    ///
+    /// An empty substring matches every path.
+    ///
+    /// # Panics
+    /// Panics if the automaton for the substrings exceeds the size limits of the
+    /// `aho-corasick` crate, which takes far more substrings than fit in memory.
+    pub fn new<I, S>(needles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let needles: Vec<String> = needles.into_iter().map(Into::into).collect();
+        let options = MatchOptions::new();
+        SubstringFilter {
+            automaton: build(&needles, options),
+            needles,
+            options,
+            overrides: MatchOverrides::new(),
+        }
+    }
+
+    /// Returns the substrings this filter matches, in the order they were added.
+    pub fn needles(&self) -> &[String] {
+        &self.needles
+    }
+
+    options_methods!(on_change = rebuild);
+
+    fn rebuild(&mut self) {
+        self.automaton = build(&self.needles, self.options());
+    }
+}
+
+fn build(needles: &[String], options: MatchOptions) -> AhoCorasick {
+    AhoCorasick::builder()
+        .ascii_case_insensitive(options.is_case_insensitive())
+        .build(
+            needles
+                .iter()
+                .map(|needle| normalized(needle.as_bytes(), options)),
+        )
+        .expect("substrings fit in an automaton")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IgnorePath, MatchOptions, MatchOverrides, SubstringFilter};
+    use std::path::Path;
+
+    #[test]
+    fn substrings() {
+        let needles: Vec<String> = (0..1000).map(|i| format!("/part{i}/")).collect();
+        let filter = SubstringFilter::new(needles);
+        assert!(filter.ignore(Path::new("a/part999/b")));
+        assert!(filter.ignore(Path::new("/part0/")));
+        assert!(!filter.ignore(Path::new("part0/b")));
+        assert!(!filter.ignore(Path::new("a/Part1/b")));
+        assert_eq!(filter.needles().len(), 1000);
+    }
+
+    #[test]
+    fn options() {
+        let filter = SubstringFilter::new(["/Gen/"])
+            .with_options(MatchOptions::new().case_insensitive(true));
+        assert!(filter.ignore(Path::new("src/gen/a.rs")));
+        assert!(!filter.ignore(Path::new("src\\gen\\a.rs")));
+
+        let filter = filter.with_overrides(MatchOverrides::new().normalize_separators(true));
+        assert!(filter.ignore(Path::new("src\\GEN\\a.rs")));
+
+        let filter = SubstringFilter::new(["\\gen\\"])
+            .with_options(MatchOptions::new().normalize_separators(true));
+        assert!(filter.ignore(Path::new("src/gen/a.rs")));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use crate::value::{to_value, Value};
+        use serde::de::IntoDeserializer;
+
+        let options = MatchOptions::new().case_insensitive(true);
+        let filter = SubstringFilter::new([".min."]).with_options(options);
+        let value = to_value(&filter).unwrap();
+        let deserialized: SubstringFilter =
+            serde::Deserialize::deserialize(
+                IntoDeserializer::<serde::de::value::Error>::into_deserializer(value.clone()),
+            )
+            .unwrap();
+        assert_eq!(to_value(&deserialized).unwrap(), value);
+        assert!(deserialized.ignore(Path::new("APP.MIN.JS")));
+        assert!(matches!(value, Value::Map(_)));
+    }
+}
@@ -0,0 +1,643 @@
+use serde::de::{
+    self,
+    value::{MapDeserializer, SeqDeserializer},
+    DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use std::{fmt, marker::PhantomData};
+
+/// A buffered, self-describing serde value.
+///
+/// Deserializing into a `Value` first and then deserializing the actual type
+/// from it allows recovering from errors in single elements, which is not
+/// possible while streaming from the original deserializer.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    Unit,
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Str(String),
+    Bytes(Vec<u8>),
+    None,
+    Some(Box<Value>),
+    Seq(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+}
+
+#[cfg(test)]
+impl Value {
+    /// The serde representation of an `OsString` on the current platform.
+    pub(crate) fn os_str(s: &str) -> Value {
+        let (platform, units) = if cfg!(windows) {
+            (
+                "Windows",
+                s.encode_utf16().map(|c| Value::U64(c.into())).collect(),
+            )
+        } else {
+            ("Unix", s.bytes().map(|b| Value::U64(b.into())).collect())
+        };
+        Value::Map(vec![(Value::Str(platform.to_string()), Value::Seq(units))])
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::I64(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::U64(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::F64(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::Str(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Value, E> {
+        Ok(Value::Str(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Value, E> {
+        Ok(Value::Bytes(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Value, E> {
+        Ok(Value::Bytes(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Unit)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        de::Deserialize::deserialize(deserializer).map(|v| Value::Some(Box::new(v)))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Value, D::Error> {
+        de::Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Value::Seq(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Value::Map(entries))
+    }
+}
+
+impl Value {
+    /// Returns a deserializer that fails on map keys that are not fields of the
+    /// struct being deserialized, at any depth.
+    pub(crate) fn into_strict_deserializer<E>(self) -> ValueDeserializer<E> {
+        ValueDeserializer::new(self, true)
+    }
+}
+
+impl<'de, E: de::Error> IntoDeserializer<'de, E> for Value {
+    type Deserializer = ValueDeserializer<E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ValueDeserializer::new(self, false)
+    }
+}
+
+/// A value nested in another one, which inherits strictness from its parent.
+struct Nested {
+    value: Value,
+    strict: bool,
+}
+
+impl<'de, E: de::Error> IntoDeserializer<'de, E> for Nested {
+    type Deserializer = ValueDeserializer<E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ValueDeserializer::new(self.value, self.strict)
+    }
+}
+
+/// Deserializes any type from a buffered [`Value`].
+pub(crate) struct ValueDeserializer<E> {
+    value: Value,
+    strict: bool,
+    marker: PhantomData<E>,
+}
+
+impl<E> ValueDeserializer<E> {
+    fn new(value: Value, strict: bool) -> Self {
+        ValueDeserializer {
+            value,
+            strict,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, E: de::Error> Deserializer<'de> for ValueDeserializer<E> {
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        let strict = self.strict;
+        let nested = |value| Nested { value, strict };
+        match self.value {
+            Value::Unit => visitor.visit_unit(),
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::I64(v) => visitor.visit_i64(v),
+            Value::U64(v) => visitor.visit_u64(v),
+            Value::F64(v) => visitor.visit_f64(v),
+            Value::Str(v) => visitor.visit_string(v),
+            Value::Bytes(v) => visitor.visit_byte_buf(v),
+            Value::None => visitor.visit_none(),
+            Value::Some(v) => visitor.visit_some(nested(*v).into_deserializer()),
+            Value::Seq(v) => {
+                let mut seq = SeqDeserializer::new(v.into_iter().map(nested));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Map(v) => {
+                let mut map =
+                    MapDeserializer::new(v.into_iter().map(|(k, v)| (nested(k), nested(v))));
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        let strict = self.strict;
+        match self.value {
+            Value::None | Value::Unit => visitor.visit_none(),
+            Value::Some(v) => visitor.visit_some(ValueDeserializer::new(*v, strict)),
+            v => visitor.visit_some(ValueDeserializer::new(v, strict)),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        if let (true, Value::Map(entries)) = (self.strict, &self.value) {
+            for (key, _) in entries {
+                if let Value::Str(key) = key {
+                    if !fields.contains(&key.as_str()) {
+                        return Err(de::Error::unknown_field(key, fields));
+                    }
+                }
+            }
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        let strict = self.strict;
+        match self.value {
+            Value::Str(variant) => visitor.visit_enum(EnumDeserializer {
+                variant: Value::Str(variant),
+                value: Value::Unit,
+                strict,
+                marker: PhantomData,
+            }),
+            Value::Map(entries) if entries.len() == 1 => {
+                let (variant, value) = entries.into_iter().next().unwrap();
+                visitor.visit_enum(EnumDeserializer {
+                    variant,
+                    value,
+                    strict,
+                    marker: PhantomData,
+                })
+            }
+            _ => Err(de::Error::custom(
+                "expected a variant name or a map with a single entry",
+            )),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map
+        identifier ignored_any
+    }
+}
+
+struct EnumDeserializer<E> {
+    variant: Value,
+    value: Value,
+    strict: bool,
+    marker: PhantomData<E>,
+}
+
+impl<'de, E: de::Error> EnumAccess<'de> for EnumDeserializer<E> {
+    type Error = E;
+    type Variant = ValueDeserializer<E>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), E> {
+        let variant = seed.deserialize(ValueDeserializer::new(self.variant, self.strict))?;
+        Ok((variant, ValueDeserializer::new(self.value, self.strict)))
+    }
+}
+
+impl<'de, E: de::Error> VariantAccess<'de> for ValueDeserializer<E> {
+    type Error = E;
+
+    fn unit_variant(self) -> Result<(), E> {
+        match self.value {
+            Value::Unit => Ok(()),
+            _ => Err(de::Error::custom("expected a unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, E> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, E> {
+        self.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        self.deserialize_struct("", fields, visitor)
+    }
+}
+
+#[cfg(test)]
+pub(crate) use ser::to_value;
+
+/// Serializes values into a [`Value`], to test serialized forms without a data format.
+#[cfg(test)]
+mod ser {
+    use super::Value;
+    use serde::{
+        de::value::Error,
+        ser::{self, Serialize},
+    };
+
+    /// Serializes `value`, with externally tagged enums as single-entry maps.
+    pub(crate) fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, Error> {
+        value.serialize(Serializer)
+    }
+
+    fn tagged(variant: Option<&'static str>, value: Value) -> Value {
+        match variant {
+            Some(variant) => Value::Map(vec![(Value::Str(variant.to_string()), value)]),
+            None => value,
+        }
+    }
+
+    struct Serializer;
+
+    struct SeqSerializer {
+        variant: Option<&'static str>,
+        items: Vec<Value>,
+    }
+
+    struct MapSerializer {
+        variant: Option<&'static str>,
+        entries: Vec<(Value, Value)>,
+        key: Option<Value>,
+    }
+
+    impl ser::Serializer for Serializer {
+        type Ok = Value;
+        type Error = Error;
+        type SerializeSeq = SeqSerializer;
+        type SerializeTuple = SeqSerializer;
+        type SerializeTupleStruct = SeqSerializer;
+        type SerializeTupleVariant = SeqSerializer;
+        type SerializeMap = MapSerializer;
+        type SerializeStruct = MapSerializer;
+        type SerializeStructVariant = MapSerializer;
+
+        fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+            Ok(Value::Bool(v))
+        }
+
+        fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+            Ok(Value::I64(v.into()))
+        }
+
+        fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+            Ok(Value::I64(v.into()))
+        }
+
+        fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+            Ok(Value::I64(v.into()))
+        }
+
+        fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+            Ok(Value::I64(v))
+        }
+
+        fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+            Ok(Value::U64(v.into()))
+        }
+
+        fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+            Ok(Value::U64(v.into()))
+        }
+
+        fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+            Ok(Value::U64(v.into()))
+        }
+
+        fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+            Ok(Value::U64(v))
+        }
+
+        fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+            Ok(Value::F64(v.into()))
+        }
+
+        fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+            Ok(Value::F64(v))
+        }
+
+        fn serialize_char(self, v: char) -> Result<Value, Error> {
+            Ok(Value::Str(v.to_string()))
+        }
+
+        fn serialize_str(self, v: &str) -> Result<Value, Error> {
+            Ok(Value::Str(v.to_string()))
+        }
+
+        fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+            Ok(Value::Bytes(v.to_vec()))
+        }
+
+        fn serialize_none(self) -> Result<Value, Error> {
+            Ok(Value::None)
+        }
+
+        fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, Error> {
+            Ok(Value::Some(Box::new(to_value(value)?)))
+        }
+
+        fn serialize_unit(self) -> Result<Value, Error> {
+            Ok(Value::Unit)
+        }
+
+        fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+            Ok(Value::Unit)
+        }
+
+        fn serialize_unit_variant(
+            self,
+            _name: &'static str,
+            _index: u32,
+            variant: &'static str,
+        ) -> Result<Value, Error> {
+            Ok(Value::Str(variant.to_string()))
+        }
+
+        fn serialize_newtype_struct<T: Serialize + ?Sized>(
+            self,
+            _name: &'static str,
+            value: &T,
+        ) -> Result<Value, Error> {
+            to_value(value)
+        }
+
+        fn serialize_newtype_variant<T: Serialize + ?Sized>(
+            self,
+            _name: &'static str,
+            _index: u32,
+            variant: &'static str,
+            value: &T,
+        ) -> Result<Value, Error> {
+            Ok(tagged(Some(variant), to_value(value)?))
+        }
+
+        fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, Error> {
+            Ok(SeqSerializer {
+                variant: None,
+                items: Vec::with_capacity(len.unwrap_or(0)),
+            })
+        }
+
+        fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, Error> {
+            self.serialize_seq(Some(len))
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _name: &'static str,
+            len: usize,
+        ) -> Result<SeqSerializer, Error> {
+            self.serialize_seq(Some(len))
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _name: &'static str,
+            _index: u32,
+            variant: &'static str,
+            len: usize,
+        ) -> Result<SeqSerializer, Error> {
+            Ok(SeqSerializer {
+                variant: Some(variant),
+                items: Vec::with_capacity(len),
+            })
+        }
+
+        fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer, Error> {
+            Ok(MapSerializer {
+                variant: None,
+                entries: Vec::new(),
+                key: None,
+            })
+        }
+
+        fn serialize_struct(
+            self,
+            _name: &'static str,
+            _len: usize,
+        ) -> Result<MapSerializer, Error> {
+            self.serialize_map(None)
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _name: &'static str,
+            _index: u32,
+            variant: &'static str,
+            _len: usize,
+        ) -> Result<MapSerializer, Error> {
+            Ok(MapSerializer {
+                variant: Some(variant),
+                entries: Vec::new(),
+                key: None,
+            })
+        }
+    }
+
+    impl ser::SerializeSeq for SeqSerializer {
+        type Ok = Value;
+        type Error = Error;
+
+        fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            self.items.push(to_value(value)?);
+            Ok(())
+        }
+
+        fn end(self) -> Result<Value, Error> {
+            Ok(tagged(self.variant, Value::Seq(self.items)))
+        }
+    }
+
+    impl ser::SerializeTuple for SeqSerializer {
+        type Ok = Value;
+        type Error = Error;
+
+        fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            ser::SerializeSeq::serialize_element(self, value)
+        }
+
+        fn end(self) -> Result<Value, Error> {
+            ser::SerializeSeq::end(self)
+        }
+    }
+
+    impl ser::SerializeTupleStruct for SeqSerializer {
+        type Ok = Value;
+        type Error = Error;
+
+        fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            ser::SerializeSeq::serialize_element(self, value)
+        }
+
+        fn end(self) -> Result<Value, Error> {
+            ser::SerializeSeq::end(self)
+        }
+    }
+
+    impl ser::SerializeTupleVariant for SeqSerializer {
+        type Ok = Value;
+        type Error = Error;
+
+        fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            ser::SerializeSeq::serialize_element(self, value)
+        }
+
+        fn end(self) -> Result<Value, Error> {
+            ser::SerializeSeq::end(self)
+        }
+    }
+
+    impl ser::SerializeMap for MapSerializer {
+        type Ok = Value;
+        type Error = Error;
+
+        fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+            self.key = Some(to_value(key)?);
+            Ok(())
+        }
+
+        fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            let key = self.key.take().expect("serialize_key is called first");
+            self.entries.push((key, to_value(value)?));
+            Ok(())
+        }
+
+        fn end(self) -> Result<Value, Error> {
+            Ok(tagged(self.variant, Value::Map(self.entries)))
+        }
+    }
+
+    impl ser::SerializeStruct for MapSerializer {
+        type Ok = Value;
+        type Error = Error;
+
+        fn serialize_field<T: Serialize + ?Sized>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<(), Error> {
+            self.entries
+                .push((Value::Str(key.to_string()), to_value(value)?));
+            Ok(())
+        }
+
+        fn end(self) -> Result<Value, Error> {
+            ser::SerializeMap::end(self)
+        }
+    }
+
+    impl ser::SerializeStructVariant for MapSerializer {
+        type Ok = Value;
+        type Error = Error;
+
+        fn serialize_field<T: Serialize + ?Sized>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<(), Error> {
+            ser::SerializeStruct::serialize_field(self, key, value)
+        }
+
+        fn end(self) -> Result<Value, Error> {
+            ser::SerializeMap::end(self)
+        }
+    }
+}
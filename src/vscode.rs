@@ -0,0 +1,212 @@
+//! Loading the `files.exclude` and `search.exclude` settings of VS Code.
+//!
+//! Both settings map globs to whether the matching paths are excluded:
+//!
+//! ```json
+//! {
+//!     "files.exclude": {
+//!         "**/.git": true,
+//!         "**/{build,dist}": true,
+//!         "**/*.js": { "when": "$(basename).ts" },
+//!         "**/.DS_Store": false
+//!     }
+//! }
+//! ```
+//!
+//! Globs are matched against paths relative to the workspace folder. `*` and `?`
+//! do not match `/`, `**` matches any number of directories, and `{a,b}` matches
+//! either alternative. Like in the explorer, excluding a directory excludes
+//! everything inside of it.
+//!
+//! Entries with a `when` clause only exclude a file if a sibling file exists,
+//! which a filter that only sees paths cannot check. They are left out of the
+//! filters and returned separately as [`ExcludeSettings::conditional`].
+//!
+//! # Examples
+//! ```
+//! use pathfilter::vscode::{self, Exclude};
+//! use pathfilter::IgnorePath;
+//! use std::path::Path;
+//!
+//! let settings = vscode::from_entries([
+//!     ("**/.git", Exclude::Enabled(true)),
+//!     ("**/{build,dist}", Exclude::Enabled(true)),
+//!     ("**/.DS_Store", Exclude::Enabled(false)),
+//! ])
+//! .unwrap();
+//! assert!(settings.filters.ignore(Path::new(".git/HEAD")));
+//! assert!(settings.filters.ignore(Path::new("web/dist/app.js")));
+//! assert!(!settings.filters.ignore(Path::new("web/.DS_Store")));
+//! ```
+//!
+//! With the `serde` feature, a setting can be deserialized into
+//! [`ExcludeSettings`] directly, e.g. from the parsed `settings.json`.
+
+use crate::{
+    glob::{translate, Dialect},
+    FilterSet, GlobError, PathFilter, RegexFilter,
+};
+use std::collections::BTreeMap;
+
+/// The value of an entry of `files.exclude` or `search.exclude`.
+///
+/// With the `serde` feature it is deserialized from either a boolean or an
+/// object with a `when` field.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(untagged)
+)]
+pub enum Exclude {
+    /// Whether the paths the glob matches are excluded. `false` turns off an entry
+    /// inherited from another settings file.
+    Enabled(bool),
+    /// Excludes the files the glob matches if a sibling with this name exists.
+    When {
+        /// The name of the sibling, where `$(basename)` stands for the name of the
+        /// matching file without its extension.
+        when: String,
+    },
+}
+
+/// The filters of a `files.exclude` or `search.exclude` setting.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(try_from = "BTreeMap<String, Exclude>")
+)]
+pub struct ExcludeSettings {
+    /// An ignore rule for every enabled entry, in the order of their globs.
+    pub filters: FilterSet,
+    /// The glob and `when` clause of every conditional entry, which are not part
+    /// of [`filters`](Self::filters).
+    pub conditional: Vec<(String, String)>,
+}
+
+impl TryFrom<BTreeMap<String, Exclude>> for ExcludeSettings {
+    type Error = GlobError;
+
+    fn try_from(entries: BTreeMap<String, Exclude>) -> Result<Self, GlobError> {
+        let mut settings = ExcludeSettings::default();
+        for (glob, exclude) in entries {
+            match exclude {
+                Exclude::Enabled(true) => settings.filters.add_ignore(glob_filter(&glob)?),
+                Exclude::Enabled(false) => {}
+                Exclude::When { when } => settings.conditional.push((glob, when)),
+            }
+        }
+        Ok(settings)
+    }
+}
+
+/// Builds the filters for the entries of a setting.
+///
+/// If a glob appears more than once, e.g. because the entries of the user and
+/// the workspace settings are chained, the last entry for it counts.
+///
+/// # Examples
+/// ```
+/// use pathfilter::vscode::{self, Exclude};
+///
+/// let when = Exclude::When { when: "$(basename).ts".to_string() };
+/// let settings = vscode::from_entries([("**/*.js", when)]).unwrap();
+/// assert!(settings.filters.is_empty());
+/// assert_eq!(settings.conditional[0].1, "$(basename).ts");
+/// ```
+/// # Errors
+/// If a glob is invalid, an error is returned.
+pub fn from_entries<I, S>(entries: I) -> Result<ExcludeSettings, GlobError>
+where
+    I: IntoIterator<Item = (S, Exclude)>,
+    S: Into<String>,
+{
+    let entries: BTreeMap<String, Exclude> = entries
+        .into_iter()
+        .map(|(glob, exclude)| (glob.into(), exclude))
+        .collect();
+    ExcludeSettings::try_from(entries)
+}
+
+fn glob_filter(glob: &str) -> Result<PathFilter, GlobError> {
+    let error = |reason| GlobError {
+        glob: glob.to_string(),
+        reason,
+    };
+    if glob.is_empty() {
+        return Err(error("the glob is empty"));
+    }
+
+    let dialect = Dialect {
+        braces: true,
+        ..Dialect::default()
+    };
+    let body = translate(glob.trim_end_matches('/'), dialect).map_err(error)?;
+    let regex = RegexFilter::new_str(&format!("^{body}(?:/.*)?$"))
+        .map_err(|_| error("the glob is not supported"))?;
+    Ok(regex.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        vscode::{from_entries, Exclude},
+        IgnorePath,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn entries() {
+        let settings = from_entries([
+            ("**/.git", Exclude::Enabled(true)),
+            ("out", Exclude::Enabled(true)),
+            ("**/*.{log,tmp}", Exclude::Enabled(true)),
+            ("**/.DS_Store", Exclude::Enabled(true)),
+            ("**/.DS_Store", Exclude::Enabled(false)),
+        ])
+        .unwrap();
+        let filters = &settings.filters;
+        assert_eq!(filters.len(), 3);
+        assert!(filters.ignore(Path::new(".git")));
+        assert!(filters.ignore(Path::new("crates/a/.git/config")));
+        assert!(filters.ignore(Path::new("out/main.js")));
+        assert!(!filters.ignore(Path::new("src/out/main.js")));
+        assert!(filters.ignore(Path::new("logs/a.tmp")));
+        assert!(!filters.ignore(Path::new("logs/a.txt")));
+        assert!(!filters.ignore(Path::new(".DS_Store")));
+        assert!(settings.conditional.is_empty());
+
+        let err = from_entries([("**/{a,b", Exclude::Enabled(true))]).unwrap_err();
+        assert_eq!(err.glob, "**/{a,b");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use crate::{value::Value, vscode::ExcludeSettings};
+        use serde::de::IntoDeserializer;
+
+        let entry = |glob: &str, value| (Value::Str(glob.to_string()), value);
+        let when = Value::Map(vec![(
+            Value::Str("when".to_string()),
+            Value::Str("$(basename).ts".to_string()),
+        )]);
+        let value = Value::Map(vec![
+            entry("**/node_modules", Value::Bool(true)),
+            entry("**/*.js", when),
+        ]);
+        let settings: ExcludeSettings =
+            serde::Deserialize::deserialize(
+                IntoDeserializer::<serde::de::value::Error>::into_deserializer(value),
+            )
+            .unwrap();
+        assert!(settings
+            .filters
+            .ignore(Path::new("web/node_modules/react/index.js")));
+        assert_eq!(
+            settings.conditional,
+            [("**/*.js".to_string(), "$(basename).ts".to_string())]
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{
+    extension::unicode_fold, options::options_methods, IgnorePath, MatchCandidate, MatchOptions,
+    MatchOverrides, MatchTarget,
+};
+use std::path::Path;
+
+/// A filter that matches paths against a simple wildcard pattern.
+///
+/// `*` matches any number of characters and `?` a single character, neither of
+/// them matches `/`. Every other character matches itself; there is no escaping.
+/// The pattern has to match the whole [`MatchTarget`], which is the file name by
+/// default. Paths, or file names, that are not valid UTF-8 never match.
+///
+/// Unlike [`GlobFilter`](crate::GlobFilter), this filter needs no dependencies.
+///
+/// # Examples
+/// ```
+/// use pathfilter::{IgnorePath, MatchTarget, WildcardFilter};
+/// use std::path::Path;
+///
+/// let filter = WildcardFilter::new("*.tmp");
+/// assert!(filter.ignore(Path::new("build/out.tmp")));
+/// assert!(!filter.ignore(Path::new("build/out.tmp.rs")));
+///
+/// let filter = WildcardFilter::new("src/*/mod.rs").with_target(MatchTarget::Path);
+/// assert!(filter.ignore(Path::new("src/net/mod.rs")));
+/// assert!(!filter.ignore(Path::new("src/net/tcp/mod.rs")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WildcardFilter {
+    pattern: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "file_name", skip_serializing_if = "is_file_name")
+    )]
+    target: MatchTarget,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOptions::is_default")
+    )]
+    options: MatchOptions,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MatchOverrides::is_empty")
+    )]
+    overrides: MatchOverrides,
+}
+
+#[cfg(feature = "serde")]
+fn file_name() -> MatchTarget {
+    MatchTarget::FileName
+}
+
+#[cfg(feature = "serde")]
+fn is_file_name(target: &MatchTarget) -> bool {
+    !target.is_path()
+}
+
+impl IgnorePath for WildcardFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.ignore_candidate(&MatchCandidate::new(path.as_ref()))
+    }
+
+    fn ignore_candidate(&self, candidate: &MatchCandidate<'_>) -> bool {
+        let path = candidate.path();
+        let text = if self.target.is_path() {
+            path.to_str()
+        } else {
+            path.file_name().and_then(|name| name.to_str())
+        };
+        let Some(text) = text else {
+            return false;
+        };
+
+        let options = self.options();
+        if options.normalizes_separators() && text.contains('\\') {
+            candidate.with_buffer(|buffer| {
+                buffer.clear();
+                buffer.extend(text.chars().map(|c| if c == '\\' { '/' } else { c }));
+                wildcard_match(&self.pattern, buffer, options)
+            })
+        } else {
+            wildcard_match(&self.pattern, text, options)
+        }
+    }
+}
+
+/// Returns whether `pattern` matches all of `text`.
+///
+/// As neither wildcard matches `/`, the `/`s of the pattern and the text have to
+/// line up, so it is enough to backtrack to the last `*`.
+fn wildcard_match(pattern: &str, text: &str, options: MatchOptions) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut star = None;
+    while let Some(c) = text[t..].chars().next() {
+        match pattern[p..].chars().next() {
+            Some('*') => {
+                star = Some((p + 1, t));
+                p += 1;
+                continue;
+            }
+            Some('?') if c != '/' => {
+                p += 1;
+                t += c.len_utf8();
+                continue;
+            }
+            Some(expected) if char_eq(expected, c, options) => {
+                p += expected.len_utf8();
+                t += c.len_utf8();
+                continue;
+            }
+            _ => {}
+        }
+
+        let Some((after_star, start)) = star else {
+            return false;
+        };
+        let skipped = text[start..].chars().next().expect("start is before t");
+        if skipped == '/' {
+            return false;
+        }
+        star = Some((after_star, start + skipped.len_utf8()));
+        p = after_star;
+        t = start + skipped.len_utf8();
+    }
+
+    pattern[p..].chars().all(|c| c == '*')
+}
+
+fn char_eq(a: char, b: char, options: MatchOptions) -> bool {
+    if a == b {
+        true
+    } else if !options.is_case_insensitive() {
+        false
+    } else if options.is_unicode_case() {
+        let (mut a_buf, mut b_buf) = ([0; 4], [0; 4]);
+        unicode_fold(a.encode_utf8(&mut a_buf)).eq(unicode_fold(b.encode_utf8(&mut b_buf)))
+    } else {
+        a.eq_ignore_ascii_case(&b)
+    }
+}
+
+impl WildcardFilter {
+    /// Creates a new filter that matches file names against `pattern`.
+    pub fn new<S: Into<String>>(pattern: S) -> Self {
+        WildcardFilter {
+            pattern: pattern.into(),
+            target: MatchTarget::FileName,
+            options: MatchOptions::new(),
+            overrides: MatchOverrides::new(),
+        }
+    }
+
+    /// Returns the pattern this filter was created from.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Sets the part of the path the pattern has to match.
+    pub fn with_target(mut self, target: MatchTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Returns the part of the path the pattern has to match.
+    pub fn target(&self) -> MatchTarget {
+        self.target
+    }
+
+    options_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IgnorePath, MatchOptions, MatchTarget, WildcardFilter};
+    use std::path::Path;
+
+    #[test]
+    fn wildcards() {
+        let matches = |pattern: &str, path: &str| {
+            WildcardFilter::new(pattern)
+                .with_target(MatchTarget::Path)
+                .ignore(Path::new(path))
+        };
+        assert!(matches("*", "anything"));
+        assert!(matches("*", ""));
+        assert!(matches("a*b*c", "a__b__c"));
+        assert!(matches("a*b*c", "abbbc"));
+        assert!(matches("*.tar.gz", "x.tar.tar.gz"));
+        assert!(matches("?ä?", "xäy"));
+        assert!(matches("src/**", "src/lib.rs"));
+        assert!(!matches("a*b*c", "a__b__"));
+        assert!(!matches("?", ""));
+        assert!(!matches("?", "ab"));
+        assert!(!matches("*.rs", "src/lib.rs"));
+        assert!(!matches("a?b", "a/b"));
+        assert!(!matches("*/*.rs", "a/b/c.rs"));
+    }
+
+    #[test]
+    fn targets_and_options() {
+        let filter = WildcardFilter::new("Makefile.*");
+        assert!(filter.ignore(Path::new("dir/Makefile.in")));
+        assert!(!filter.ignore(Path::new("dir/makefile.in")));
+        assert!(!filter.ignore(Path::new("Makefile.d/rules")));
+        assert_eq!(filter.target(), MatchTarget::FileName);
+
+        let filter = filter.with_options(MatchOptions::new().case_insensitive(true));
+        assert!(filter.ignore(Path::new("MAKEFILE.IN")));
+
+        let filter = WildcardFilter::new("café*").with_options(
+            MatchOptions::new()
+                .case_insensitive(true)
+                .unicode_case(true),
+        );
+        assert!(filter.ignore(Path::new("CAFÉ.txt")));
+
+        let filter = WildcardFilter::new("src/*.rs")
+            .with_target(MatchTarget::Path)
+            .with_options(MatchOptions::new().normalize_separators(true));
+        assert!(filter.ignore(Path::new("src\\lib.rs")));
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{content::io_error_policy_methods, IgnorePath, IoErrorPolicy};
+use std::{
+    ffi::{CString, OsStr, OsString},
+    io,
+    os::unix::ffi::OsStrExt,
+    path::Path,
+    ptr,
+};
+
+/// A filter that matches files by an extended attribute.
+///
+/// Available on Linux and macOS with the `xattr` feature. Symbolic links are followed.
+///
+/// # Examples
+/// ```no_run
+/// use pathfilter::{IgnorePath, XattrFilter};
+///
+/// let filter = XattrFilter::new("user.backup").with_value("skip");
+/// assert!(filter.ignore("scratch/cache.db"));
+///
+/// let time_machine = XattrFilter::new("com.apple.metadata:com_apple_backup_excludeItem");
+/// assert!(time_machine.ignore("Downloads/movie.mkv"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct XattrFilter {
+    name: OsString,
+    value: Option<Vec<u8>>,
+    on_error: IoErrorPolicy,
+}
+
+impl IgnorePath for XattrFilter {
+    fn ignore<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.on_error.resolve(self.check(path))
+    }
+}
+
+impl XattrFilter {
+    /// Creates a new filter that ignores files that have the attribute, whatever its value.
+    pub fn new<S: AsRef<OsStr>>(name: S) -> Self {
+        XattrFilter {
+            name: name.as_ref().to_os_string(),
+            value: None,
+            on_error: IoErrorPolicy::Keep,
+        }
+    }
+
+    /// Only ignores files where the attribute has exactly this value.
+    pub fn with_value<V: AsRef<[u8]>>(mut self, value: V) -> Self {
+        self.value = Some(value.as_ref().to_vec());
+        self
+    }
+
+    io_error_policy_methods! {
+        ///
+        /// File systems without extended attributes count as files without the
+        /// attribute, not as errors.
+        "files whose attributes cannot be read"
+    }
+
+    /// Returns the name of the attribute.
+    pub fn name(&self) -> &OsStr {
+        &self.name
+    }
+
+    /// Returns whether the file has the attribute with the expected value.
+    ///
+    /// # Errors
+    /// If the attributes of the file cannot be read, an error is returned.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> io::Result<bool> {
+        Ok(match (get(path.as_ref(), &self.name)?, &self.value) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(value), Some(expected)) => value == *expected,
+        })
+    }
+}
+
+fn c_string(s: &OsStr) -> io::Result<CString> {
+    CString::new(s.as_bytes()).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+}
+
+/// Reads an extended attribute, returning `None` if the file does not have it.
+fn get(path: &Path, name: &OsStr) -> io::Result<Option<Vec<u8>>> {
+    let path = c_string(path.as_os_str())?;
+    let name = c_string(name)?;
+
+    loop {
+        // SAFETY: both strings are NUL terminated and a null buffer only queries the length.
+        let len = unsafe { sys::getxattr(path.as_ptr(), name.as_ptr(), ptr::null_mut(), 0) };
+        let Ok(len) = usize::try_from(len) else {
+            return missing_or_err(io::Error::last_os_error());
+        };
+
+        let mut value = vec![0_u8; len];
+        // SAFETY: both strings are NUL terminated and `value` is valid for `len` bytes.
+        let read = unsafe {
+            sys::getxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                value.as_mut_ptr().cast(),
+                value.len(),
+            )
+        };
+        match usize::try_from(read) {
+            Ok(read) => {
+                value.truncate(read);
+                return Ok(Some(value));
+            }
+            // the value grew since its length was queried
+            Err(_) if io::Error::last_os_error().raw_os_error() == Some(sys::ERANGE) => continue,
+            Err(_) => return missing_or_err(io::Error::last_os_error()),
+        }
+    }
+}
+
+fn missing_or_err(err: io::Error) -> io::Result<Option<Vec<u8>>> {
+    match err.raw_os_error() {
+        Some(sys::ENOATTR | sys::ENOTSUP) => Ok(None),
+        _ => Err(err),
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::os::raw::{c_char, c_int, c_void};
+
+    pub(super) const ERANGE: c_int = 34;
+    pub(super) const ENOATTR: c_int = 61;
+    pub(super) const ENOTSUP: c_int = 95;
+
+    extern "C" {
+        pub(super) fn getxattr(
+            path: *const c_char,
+            name: *const c_char,
+            value: *mut c_void,
+            size: usize,
+        ) -> isize;
+        #[cfg(test)]
+        fn setxattr(
+            path: *const c_char,
+            name: *const c_char,
+            value: *const c_void,
+            size: usize,
+            flags: c_int,
+        ) -> c_int;
+    }
+
+    #[cfg(test)]
+    pub(super) unsafe fn set(
+        path: *const c_char,
+        name: *const c_char,
+        value: *const c_void,
+        size: usize,
+    ) -> c_int {
+        setxattr(path, name, value, size, 0)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod sys {
+    use std::os::raw::{c_char, c_int, c_void};
+
+    pub(super) const ERANGE: c_int = 34;
+    pub(super) const ENOATTR: c_int = 93;
+    pub(super) const ENOTSUP: c_int = 45;
+
+    extern "C" {
+        #[link_name = "getxattr"]
+        fn getxattr_at(
+            path: *const c_char,
+            name: *const c_char,
+            value: *mut c_void,
+            size: usize,
+            position: u32,
+            options: c_int,
+        ) -> isize;
+        #[cfg(test)]
+        fn setxattr(
+            path: *const c_char,
+            name: *const c_char,
+            value: *const c_void,
+            size: usize,
+            position: u32,
+            options: c_int,
+        ) -> c_int;
+    }
+
+    pub(super) unsafe fn getxattr(
+        path: *const c_char,
+        name: *const c_char,
+        value: *mut c_void,
+        size: usize,
+    ) -> isize {
+        getxattr_at(path, name, value, size, 0, 0)
+    }
+
+    #[cfg(test)]
+    pub(super) unsafe fn set(
+        path: *const c_char,
+        name: *const c_char,
+        value: *const c_void,
+        size: usize,
+    ) -> c_int {
+        setxattr(path, name, value, size, 0, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        content::tests::TempDir,
+        xattr::{c_string, sys},
+        IgnorePath, IoErrorPolicy, XattrFilter,
+    };
+    use std::{ffi::OsStr, io, path::Path};
+
+    fn set(path: &Path, name: &str, value: &[u8]) -> io::Result<()> {
+        let path = c_string(path.as_os_str())?;
+        let name = c_string(OsStr::new(name))?;
+        // SAFETY: both strings are NUL terminated and `value` is valid for its length.
+        let ret = unsafe {
+            sys::set(
+                path.as_ptr(),
+                name.as_ptr(),
+                value.as_ptr().cast(),
+                value.len(),
+            )
+        };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    #[test]
+    fn xattr_filter() {
+        let dir = TempDir::new();
+        let tagged = dir.write("tagged", "");
+        let plain = dir.write("plain", "");
+        let missing = dir.path().join("missing");
+
+        let filter = XattrFilter::new("user.pathfilter.backup");
+        assert!(!filter.ignore(&plain));
+        assert!(!filter.ignore(&missing));
+        assert!(filter
+            .clone()
+            .with_io_error_policy(IoErrorPolicy::Ignore)
+            .ignore(&missing));
+
+        if let Err(err) = set(&tagged, "user.pathfilter.backup", b"skip") {
+            // the temporary directory does not support user attributes
+            assert_eq!(err.raw_os_error(), Some(sys::ENOTSUP));
+            return;
+        }
+        assert!(filter.ignore(&tagged));
+        assert!(filter.clone().with_value("skip").ignore(&tagged));
+        assert!(!filter.with_value("keep").ignore(&tagged));
+    }
+}